@@ -5,13 +5,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let proto_file = "./proto/store.proto";
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
 
+    // Sorted maps give each message a single encoding, which signed
+    // responses depend on.
+    let mut config = prost_build::Config::new();
+    config.btree_map(["."]);
+
     tonic_build::configure()
         .protoc_arg("--experimental_allow_proto3_optional")
         .build_client(true)
         .build_server(true)
         .file_descriptor_set_path(out_dir.join("store_descriptor.bin"))
         .out_dir("./src")
-        .compile(&[proto_file], &["proto"])?;
+        .compile_with_config(config, &[proto_file], &["proto"])?;
 
     Ok(())
 }
@@ -0,0 +1,163 @@
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::feed::quote;
+
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub at: u64,
+    pub rpc: &'static str,
+    pub sku: String,
+    pub detail: String,
+    /// Why the caller made the change; left out of the JSON when empty.
+    pub reason: String,
+}
+
+impl AuditEntry {
+    pub fn to_json(&self) -> String {
+        let reason = match self.reason.as_str() {
+            "" => String::new(),
+            reason => format!(",\"reason\":{}", quote(reason)),
+        };
+        format!(
+            "{{\"at\":{},\"rpc\":{},\"sku\":{},\"detail\":{}{}}}",
+            self.at,
+            quote(self.rpc),
+            quote(&self.sku),
+            quote(&self.detail),
+            reason
+        )
+    }
+}
+
+/// An entry read back from a JSON line written by `FileSink`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEntry {
+    pub at: u64,
+    pub rpc: String,
+    pub sku: String,
+    pub detail: String,
+    pub reason: String,
+}
+
+impl RecordedEntry {
+    /// Parses a line in exactly the shape `AuditEntry::to_json` writes, or
+    /// `None` for anything else.
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = line.trim_end().strip_prefix("{\"at\":")?;
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let at = rest[..digits].parse().ok()?;
+
+        let (rpc, rest) = unquote(rest[digits..].strip_prefix(",\"rpc\":")?)?;
+        let (sku, rest) = unquote(rest.strip_prefix(",\"sku\":")?)?;
+        let (detail, rest) = unquote(rest.strip_prefix(",\"detail\":")?)?;
+        let (reason, rest) = match rest.strip_prefix(",\"reason\":") {
+            Some(rest) => unquote(rest)?,
+            None => (String::new(), rest),
+        };
+        if rest != "}" {
+            return None;
+        }
+
+        Some(RecordedEntry {
+            at,
+            rpc,
+            sku,
+            detail,
+            reason,
+        })
+    }
+}
+
+/// Reads a string quoted by `feed::quote` off the front of `input`,
+/// returning it and the rest of the input.
+fn unquote(input: &str) -> Option<(String, &str)> {
+    let mut chars = input.strip_prefix('"')?.char_indices();
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &input[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let hex: String = (0..4)
+                        .filter_map(|_| chars.next().map(|(_, c)| c))
+                        .collect();
+                    value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                escaped => value.push(escaped),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Somewhere audit entries are retained beyond the process, such as a file,
+/// syslog or a SIEM collector.
+pub trait AuditSink: Debug + Send + 'static {
+    fn write(&mut self, entry: &AuditEntry) -> io::Result<()>;
+}
+
+/// Appends entries to a file as JSON lines.
+#[derive(Debug)]
+pub struct FileSink {
+    file: File,
+}
+
+impl FileSink {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(FileSink { file })
+    }
+}
+
+impl AuditSink for FileSink {
+    fn write(&mut self, entry: &AuditEntry) -> io::Result<()> {
+        writeln!(self.file, "{}", entry.to_json())
+    }
+}
+
+/// Hands audit entries to a sink on a background thread so writes stay off
+/// the request path. When the bounded queue is full, entries are dropped
+/// with a warning. Without a sink, entries are discarded.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    tx: Option<mpsc::Sender<AuditEntry>>,
+}
+
+impl AuditLog {
+    pub fn spawn(mut sink: impl AuditSink, capacity: usize) -> Self {
+        let (tx, mut rx) = mpsc::channel::<AuditEntry>(capacity);
+        tokio::task::spawn_blocking(move || {
+            while let Some(entry) = rx.blocking_recv() {
+                if let Err(err) = sink.write(&entry) {
+                    tracing::error!(error = ?err, "failed to write audit entry");
+                }
+            }
+        });
+
+        AuditLog { tx: Some(tx) }
+    }
+
+    pub fn record(&self, entry: AuditEntry) {
+        let tx = match self.tx.as_ref() {
+            Some(tx) => tx,
+            None => return,
+        };
+
+        if let Err(TrySendError::Full(entry)) = tx.try_send(entry) {
+            tracing::warn!(
+                rpc = entry.rpc,
+                sku = entry.sku.as_str(),
+                "audit queue full, dropping entry"
+            );
+        }
+    }
+}
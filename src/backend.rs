@@ -0,0 +1,251 @@
+use std::fmt::Debug;
+use std::fs::File;
+use std::future::Future;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use prost::Message;
+use tonic::Status;
+
+use crate::retry;
+use crate::store::Snapshot;
+use crate::wal;
+
+const EXHAUSTED_ERR: &str = "backend unavailable after retries";
+
+/// Where the catalog is persisted. Implementations sort their errors into
+/// ones worth retrying and ones that aren't.
+pub trait Backend: Debug + Send + Sync + 'static {
+    /// Reads the whole persisted catalog. May block.
+    fn load(&self) -> Result<Snapshot, BackendError>;
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    /// A blip that may pass on its own, such as a timeout or a dropped
+    /// connection.
+    Transient(String),
+    /// Retrying won't help; the status is reported as is.
+    Failed(Status),
+}
+
+impl From<io::Error> for BackendError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted => BackendError::Transient(err.to_string()),
+            io::ErrorKind::NotFound => BackendError::Failed(Status::not_found(err.to_string())),
+            _ => BackendError::Failed(Status::internal(err.to_string())),
+        }
+    }
+}
+
+/// A binary-encoded snapshot on disk.
+#[derive(Debug)]
+pub struct SnapshotFile {
+    path: PathBuf,
+}
+
+impl SnapshotFile {
+    pub fn new(path: PathBuf) -> Self {
+        SnapshotFile { path }
+    }
+
+    /// Replaces the file with `snapshot`. It's written alongside first and
+    /// renamed into place, so a crash leaves the old snapshot whole.
+    pub fn save(&self, snapshot: &Snapshot) -> io::Result<()> {
+        let mut partial = self.path.clone().into_os_string();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+
+        let mut file = File::create(&partial)?;
+        file.write_all(&snapshot.encode_to_vec())?;
+        file.sync_all()?;
+        std::fs::rename(&partial, &self.path)
+    }
+}
+
+impl Backend for SnapshotFile {
+    fn load(&self) -> Result<Snapshot, BackendError> {
+        let bytes = std::fs::read(&self.path)?;
+        Snapshot::decode(bytes.as_slice())
+            .map_err(|err| BackendError::Failed(Status::data_loss(err.to_string())))
+    }
+}
+
+/// An optional snapshot with a write-ahead log of the changes since.
+/// Loading replays the log on top of the snapshot and fails with
+/// `failed_precondition` when the two don't line up. Without a snapshot, or
+/// before the first one is saved, the whole log is replayed.
+#[derive(Debug)]
+pub struct Journaled {
+    snapshot: Option<SnapshotFile>,
+    wal: PathBuf,
+}
+
+impl Journaled {
+    pub fn new(snapshot: Option<PathBuf>, wal: &Path) -> Self {
+        Journaled {
+            snapshot: snapshot.map(SnapshotFile::new),
+            wal: wal.to_owned(),
+        }
+    }
+}
+
+impl Backend for Journaled {
+    fn load(&self) -> Result<Snapshot, BackendError> {
+        let loaded = match self.snapshot.as_ref() {
+            Some(snapshot) => snapshot.load(),
+            None => Ok(Snapshot::default()),
+        };
+        let snapshot = match loaded {
+            Err(BackendError::Failed(status)) if status.code() == tonic::Code::NotFound => {
+                Snapshot::default()
+            }
+            loaded => loaded?,
+        };
+        let (entries, _) = wal::read(&self.wal)?;
+
+        let version = snapshot.version;
+        let replayed = wal::reconcile(snapshot, entries).map_err(|conflict| {
+            BackendError::Failed(Status::failed_precondition(conflict.to_string()))
+        })?;
+        tracing::info!(
+            version,
+            replayed = replayed.version - version,
+            "WAL replayed"
+        );
+        Ok(replayed)
+    }
+}
+
+/// How transient backend errors are retried: up to `attempts` tries in
+/// all, waiting `backoff` after the first failure and doubling the wait
+/// after each one after that, up to `max_backoff`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Runs `op` until it succeeds, fails in a way retrying won't fix, or
+    /// has failed transiently on every attempt, which is reported as
+    /// `unavailable`.
+    pub async fn run<T, F, Fut>(&self, mut op: F) -> Result<T, Status>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, BackendError>>,
+    {
+        let mut backoff = self.backoff;
+        let mut attempt = 1;
+        loop {
+            let message = match op().await {
+                Ok(value) => return Ok(value),
+                Err(BackendError::Failed(status)) => return Err(status),
+                Err(BackendError::Transient(message)) => message,
+            };
+
+            if attempt >= self.attempts {
+                let message = format!("{}: {}", EXHAUSTED_ERR, message);
+                return Err(retry::unavailable(&message, self.backoff));
+            }
+            tracing::warn!(
+                attempt,
+                error = message.as_str(),
+                "transient backend error, retrying"
+            );
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.max_backoff);
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tonic::Code;
+
+    use super::*;
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        attempts: 3,
+        backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(4),
+    };
+
+    /// Fails transiently `blips` times, then with `failure` if set, then
+    /// loads an empty catalog.
+    #[derive(Debug, Default)]
+    struct Flaky {
+        blips: u32,
+        failure: Option<Code>,
+        calls: AtomicU32,
+    }
+
+    impl Backend for Flaky {
+        fn load(&self) -> Result<Snapshot, BackendError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.blips {
+                return Err(io::Error::from(io::ErrorKind::TimedOut).into());
+            }
+            match self.failure {
+                Some(code) => Err(BackendError::Failed(Status::new(code, "broken"))),
+                None => Ok(Snapshot::default()),
+            }
+        }
+    }
+
+    async fn load(backend: &Flaky) -> Result<Snapshot, Status> {
+        POLICY.run(|| async { backend.load() }).await
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried_until_success() {
+        let backend = Flaky {
+            blips: 2,
+            ..Default::default()
+        };
+        assert!(load(&backend).await.is_ok());
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_are_unavailable() {
+        let backend = Flaky {
+            blips: 3,
+            ..Default::default()
+        };
+        let status = load(&backend).await.unwrap_err();
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_retried() {
+        let backend = Flaky {
+            failure: Some(Code::NotFound),
+            ..Default::default()
+        };
+        let status = load(&backend).await.unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn io_errors_are_classified() {
+        let transient = BackendError::from(io::Error::from(io::ErrorKind::ConnectionReset));
+        assert!(matches!(transient, BackendError::Transient(_)));
+
+        let missing = BackendError::from(io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(missing, BackendError::Failed(status) if status.code() == Code::NotFound));
+    }
+}
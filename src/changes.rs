@@ -0,0 +1,66 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::futures::Notified;
+use tokio::sync::Notify;
+
+/// Changed SKUs remembered for long polls before the oldest are forgotten.
+const HISTORY: usize = 10_000;
+
+/// Which SKUs changed at which catalog generation, with a wakeup for long
+/// polls waiting on the next change.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeFeed {
+    history: Arc<Mutex<History>>,
+    notify: Arc<Notify>,
+}
+
+#[derive(Debug, Default)]
+struct History {
+    changes: VecDeque<(u64, String)>,
+    /// Every change at or before this generation has been forgotten.
+    floor: u64,
+}
+
+impl ChangeFeed {
+    pub fn record(&self, generation: u64, sku: &str) {
+        let mut history = self.history.lock().unwrap();
+        history.changes.push_back((generation, sku.to_owned()));
+        if history.changes.len() > HISTORY {
+            if let Some((dropped, _)) = history.changes.pop_front() {
+                history.floor = history.floor.max(dropped);
+            }
+        }
+    }
+
+    /// Wakes every long poll waiting on a change.
+    pub fn notify(&self) {
+        self.notify.notify_waiters();
+    }
+
+    /// Resolves on the next `notify`. Enable it before checking for changes
+    /// so one made in between isn't missed.
+    pub fn notified(&self) -> Notified<'_> {
+        self.notify.notified()
+    }
+
+    /// The SKUs changed after `generation`, each once in the order they
+    /// first changed, or `None` when that's further back than the history
+    /// goes.
+    pub fn since(&self, generation: u64) -> Option<Vec<String>> {
+        let history = self.history.lock().unwrap();
+        if generation < history.floor {
+            return None;
+        }
+
+        let mut seen = HashSet::new();
+        let skus = history
+            .changes
+            .iter()
+            .filter(|(changed, _)| *changed > generation)
+            .filter(|(_, sku)| seen.insert(sku.as_str()))
+            .map(|(_, sku)| sku.clone())
+            .collect();
+        Some(skus)
+    }
+}
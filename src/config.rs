@@ -0,0 +1,501 @@
+use std::path::PathBuf;
+
+use clap::builder::PossibleValuesParser;
+use clap::{Args, Parser, ValueEnum};
+
+use crate::deadlines::parse_method_deadline;
+use crate::lifecycle::parse_transition;
+use crate::pricing;
+use crate::store::LifecycleState;
+use crate::warehouses::parse_warehouse_region;
+
+#[derive(Debug, Clone, Parser)]
+#[command(name = "server")]
+pub struct Config {
+    /// Seconds between sweeps removing items whose TTL has elapsed
+    #[arg(long, default_value_t = 1)]
+    pub sweep_interval: u64,
+
+    /// Upper bound on an item's quantity, enforced on add and increase. The
+    /// u64 overflow guard applies regardless, including to overridden requests
+    #[arg(long)]
+    pub max_quantity: Option<u64>,
+
+    /// Token clients send as `x-admin-token` metadata to unlock guarded overrides
+    #[arg(long)]
+    pub admin_token: Option<String>,
+
+    /// Key single-item and catalog reads are signed with. Each response then
+    /// carries `x-signature: sha256=<hex>` metadata, an HMAC-SHA256 of the
+    /// message's protobuf encoding with fields in field-number order and map
+    /// entries sorted by key, so clients can verify it by re-encoding
+    #[arg(long)]
+    pub response_signing_key: Option<String>,
+
+    /// What update_price does with a price below the item's cost. Admins can
+    /// bypass a rejection with `x-allow-below-cost` metadata
+    #[arg(long, value_enum, default_value_t = CostFloorPolicy::Allow)]
+    pub below_cost_policy: CostFloorPolicy,
+
+    /// How computed prices are rounded to cents
+    #[arg(long, value_enum, default_value_t = Rounding::Nearest)]
+    pub price_rounding: Rounding,
+
+    /// How RPCs treat an item without stock: `strict` errors, `lenient`
+    /// treats it as zero quantity
+    #[arg(long, value_enum, default_value_t = StockPolicy::Strict)]
+    pub missing_stock_policy: StockPolicy,
+
+    /// Seconds an increase operation ID is remembered for deduplication
+    #[arg(long, default_value_t = 86400)]
+    pub operation_id_ttl: u64,
+
+    /// Maximum operation IDs remembered per SKU
+    #[arg(long, default_value_t = 1000)]
+    pub operation_id_capacity: usize,
+
+    /// Seconds the result of an add made with an `x-idempotency-key` is
+    /// remembered, answering retries with the same key
+    #[arg(long, default_value_t = 86400)]
+    pub idempotency_key_ttl: u64,
+
+    /// Keep removed items as tombstones instead of dropping them
+    #[arg(long)]
+    pub soft_delete: bool,
+
+    /// Seconds a tombstone is kept before Compact may purge it
+    #[arg(long, default_value_t = 0)]
+    pub tombstone_retention: u64,
+
+    /// Maximum mutating requests running at once; unbounded when unset
+    #[arg(long)]
+    pub max_concurrent_mutations: Option<usize>,
+
+    /// What a mutation does when no permit is free
+    #[arg(long, value_enum, default_value_t = OverloadPolicy::Shed)]
+    pub mutation_overload: OverloadPolicy,
+
+    /// Milliseconds a queued mutation waits for a permit before being shed
+    #[arg(long, default_value_t = 1000)]
+    pub mutation_queue_timeout: u64,
+
+    /// Whether a zero increase or decrease is rejected or a no-op success
+    #[arg(long, value_enum, default_value_t = ZeroQuantityPolicy::Error)]
+    pub zero_quantity_policy: ZeroQuantityPolicy,
+
+    /// Whether add and upsert accept an item with no initial stock. Bundles,
+    /// whose quantity is derived, always are
+    #[arg(long, value_enum, default_value_t = ZeroStockPolicy::Allow)]
+    pub zero_initial_quantity: ZeroStockPolicy,
+
+    /// Minimum milliseconds between total inventory value updates
+    #[arg(long, default_value_t = 1000)]
+    pub total_value_interval: u64,
+
+    /// Bytes a get_all response may encode to before clients are sent to
+    /// StreamAll instead; StreamAll keeps each of its messages under it
+    #[arg(long, default_value_t = 4 * 1024 * 1024)]
+    pub max_response_size: usize,
+
+    /// Largest edit distance a fuzzy search may ask for; larger requests are
+    /// capped to it, and it's the distance used when a request gives none
+    #[arg(long, default_value_t = 2)]
+    pub search_max_distance: u32,
+
+    /// Most matches one search returns
+    #[arg(long, default_value_t = 50)]
+    pub search_max_results: u32,
+
+    /// Maximum SKUs, items or lines one bulk request may carry
+    #[arg(long, default_value_t = 1000)]
+    pub max_batch_size: usize,
+
+    /// Items a bulk write applies per hold of the inventory lock. Unset,
+    /// adjust_price_percent and tag changes hold it for the whole batch and
+    /// apply all or nothing; set, they release it between chunks so other
+    /// requests can interleave, and a failure leaves earlier chunks applied
+    #[arg(long)]
+    pub batch_chunk_size: Option<usize>,
+
+    /// Maximum active reservations per SKU. Every reservation counts on its
+    /// own, so each line of a cart-style batch takes one slot of its SKU
+    #[arg(long)]
+    pub max_reservations_per_sku: Option<usize>,
+
+    /// Whether reservations hold stock back. Soft ones only record intent:
+    /// reserving never runs out and decreases ignore them, so more can end
+    /// up reserved than is on hand, which get reports as oversold
+    #[arg(long, value_enum, default_value_t = ReservationMode::Hard)]
+    pub reservation_mode: ReservationMode,
+
+    /// Which warehouses a decrease naming none draws from first
+    #[arg(long, value_enum, default_value_t = AllocationStrategy::MostStock)]
+    pub allocation_strategy: AllocationStrategy,
+
+    /// A warehouse's region as `WAREHOUSE=REGION`, for the `nearest`
+    /// allocation strategy. Repeatable
+    #[arg(long = "warehouse-region", value_parser = parse_warehouse_region)]
+    pub warehouse_regions: Vec<(String, String)>,
+
+    /// Longest TTL in seconds a reservation or claim may be given. Without
+    /// one, a reservation left without a TTL never expires; with one, it
+    /// gets this maximum instead
+    #[arg(long)]
+    pub max_reservation_ttl: Option<u64>,
+
+    /// What happens to a TTL over the maximum: `reject` fails the request so
+    /// the client learns its hold won't last as long as asked, `clamp`
+    /// shortens it to the maximum and succeeds
+    #[arg(long, value_enum, default_value_t = TtlPolicy::Reject)]
+    pub reservation_ttl_policy: TtlPolicy,
+
+    /// Whether item names must be unique, and how names are normalized
+    /// before being compared
+    #[arg(long, value_enum, default_value_t = NamePolicy::Off)]
+    pub unique_names: NamePolicy,
+
+    /// Milliseconds a lock acquisition may wait before it's logged and
+    /// counted as slow
+    #[arg(long, default_value_t = 100)]
+    pub slow_lock_threshold: u64,
+
+    /// What add does when the SKU already exists: `reject` it, let the
+    /// latest add overwrite the existing item, or, when `idempotent`, succeed
+    /// without changes if the payload is identical and reject it otherwise
+    #[arg(long, value_enum, default_value_t = DuplicateAddPolicy::Reject)]
+    pub duplicate_add_policy: DuplicateAddPolicy,
+
+    /// Default deadline for a gRPC method as `Method=milliseconds`, e.g.
+    /// `GetAll=500`. Repeatable; a tighter client deadline still applies
+    #[arg(long = "method-deadline", value_parser = parse_method_deadline)]
+    pub method_deadlines: Vec<(String, u64)>,
+
+    /// File each audit entry is appended to as a JSON line; auditing is off
+    /// when unset
+    #[arg(long)]
+    pub audit_file: Option<PathBuf>,
+
+    /// Seconds old the first entry an ExportChanges replay starts from may be
+    /// before the client is told to resync instead; unbounded when unset
+    #[arg(long)]
+    pub max_replay_age: Option<u64>,
+
+    /// Entries an ExportChanges replay may have to catch up on before the
+    /// client is told to resync instead; unbounded when unset
+    #[arg(long)]
+    pub max_replay_entries: Option<u64>,
+
+    /// Audit entries buffered for the writer before new ones are dropped
+    #[arg(long, default_value_t = 1024)]
+    pub audit_queue_capacity: usize,
+
+    /// Log line format: `pretty` for reading locally, `json` for log
+    /// aggregation
+    #[arg(long, value_enum, env = "LOG_FORMAT", default_value_t = LogFormat::Pretty)]
+    pub log_format: LogFormat,
+
+    /// Most verbose level logged: error, warn, info, debug or trace
+    #[arg(long, env = "LOG_LEVEL", default_value_t = tracing::Level::INFO)]
+    pub log_level: tracing::Level,
+
+    /// Default milliseconds a debounced or throttled watch waits between
+    /// events
+    #[arg(long, default_value_t = 1000)]
+    pub watch_window: u64,
+
+    /// Milliseconds a watch stream's refresh waits for the inventory lock
+    /// before skipping that round and trying again on the next one
+    #[arg(long, default_value_t = 100)]
+    pub watch_lock_timeout: u64,
+
+    /// URL that receives a POST for every mutation. Repeatable
+    #[arg(long = "webhook-url")]
+    pub webhook_urls: Vec<String>,
+
+    /// Shared secret webhook payloads are signed with, sent as an HMAC-SHA256
+    /// in `x-webhook-signature`
+    #[arg(long)]
+    pub webhook_secret: Option<String>,
+
+    /// Milliseconds a webhook delivery may take before it's retried
+    #[arg(long, default_value_t = 5000)]
+    pub webhook_timeout: u64,
+
+    /// Times a failed webhook delivery is retried, with doubling backoff
+    #[arg(long, default_value_t = 3)]
+    pub webhook_retries: u32,
+
+    /// RPC whose changes are sent to webhooks, e.g. `add`. Repeatable; every
+    /// change is sent when unset
+    #[arg(long = "webhook-event")]
+    pub webhook_events: Vec<String>,
+
+    /// File webhook deliveries that ran out of retries are appended to as
+    /// JSON lines
+    #[arg(long)]
+    pub webhook_dead_letter_file: Option<PathBuf>,
+
+    /// Webhook events buffered for delivery before new ones are dropped
+    #[arg(long, default_value_t = 1024)]
+    pub webhook_queue_capacity: usize,
+
+    /// Binary-encoded `Snapshot` the catalog is loaded from at startup. A
+    /// plain `Items` message loads too
+    #[arg(long)]
+    pub snapshot_file: Option<PathBuf>,
+
+    /// Write-ahead log of item changes, replayed on top of the snapshot at
+    /// startup. The server refuses to start if the two don't line up
+    #[arg(long)]
+    pub wal_file: Option<PathBuf>,
+
+    /// Seconds between snapshots taken while a WAL is kept. Each one is
+    /// written to `--snapshot-file` and empties the WAL
+    #[arg(long, default_value_t = 300)]
+    pub snapshot_interval: u64,
+
+    /// Tries at loading the catalog before giving up on transient errors
+    #[arg(long, default_value_t = 3)]
+    pub backend_retry_attempts: u32,
+
+    /// Milliseconds to wait after the first transient backend error,
+    /// doubling after each one after that
+    #[arg(long, default_value_t = 100)]
+    pub backend_retry_backoff: u64,
+
+    /// Longest wait in milliseconds between backend retries
+    #[arg(long, default_value_t = 2000)]
+    pub backend_retry_max_backoff: u64,
+
+    /// Round prices loaded from a snapshot to the feed currency's minor unit
+    /// using the price rounding, logging each that loses precision
+    #[arg(long)]
+    pub normalize_snapshot_prices: bool,
+
+    /// What loading a snapshot does with an invalid item: `reject` the whole
+    /// snapshot, `skip` it with a warning, or `quarantine` it where
+    /// GetQuarantine can show it
+    #[arg(long, value_enum, default_value_t = SnapshotValidation::Skip)]
+    pub snapshot_validation: SnapshotValidation,
+
+    /// Maximum watch streams open at once across all clients; unbounded
+    /// when unset
+    #[arg(long)]
+    pub max_watch_streams: Option<usize>,
+
+    /// Milliseconds clients are told to wait before retrying while the
+    /// catalog is still loading
+    #[arg(long, default_value_t = 1000)]
+    pub loading_retry_delay: u64,
+
+    /// Milliseconds clients are told to wait before retrying a change during
+    /// maintenance
+    #[arg(long, default_value_t = 30000)]
+    pub maintenance_retry_delay: u64,
+
+    /// What an index lookup does with an entry that disagrees with the
+    /// inventory: `heal` fixes it, `skip` ignores it and leaves it for
+    /// RepairIndexes. Both log the anomaly
+    #[arg(long, value_enum, default_value_t = StaleIndexPolicy::Heal)]
+    pub stale_index_policy: StaleIndexPolicy,
+
+    /// Locale format_price renders prices in when a request doesn't name one
+    #[arg(long, default_value = "en-US", value_parser = PossibleValuesParser::new(pricing::LOCALES))]
+    pub price_locale: String,
+
+    /// Inclusive upper bounds of the quantity histogram's buckets, in
+    /// increasing order. A final bucket holds everything above the last bound
+    #[arg(long, value_delimiter = ',', default_value = "0,10,100")]
+    pub histogram_buckets: Vec<u64>,
+
+    /// Streams a client may open per second across watch, watch_many,
+    /// watch_total_value and interactive; unlimited when unset
+    #[arg(long, value_parser = parse_rate)]
+    pub stream_open_rate: Option<f64>,
+
+    /// Stream opens a client may make in a burst before the rate applies
+    #[arg(long, default_value_t = 5)]
+    pub stream_open_burst: u32,
+
+    /// Reject added items that set server-assigned fields or contradictory
+    /// combinations instead of silently ignoring them
+    #[arg(long)]
+    pub strict_input: bool,
+
+    /// Whether an item may be priced at zero. Negative prices are always
+    /// rejected
+    #[arg(long, value_enum, default_value_t = ZeroPricePolicy::Reject)]
+    pub zero_price_policy: ZeroPricePolicy,
+
+    /// Seconds a client-supplied timestamp may be ahead of the server's clock
+    /// before the request is rejected as coming from a skewed client
+    #[arg(long, default_value_t = 300)]
+    pub clock_skew_tolerance: u64,
+
+    /// Tax category codes an item may be given. An item without one is
+    /// always accepted
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "standard,reduced,zero,exempt"
+    )]
+    pub tax_categories: Vec<String>,
+
+    /// Lifecycle transitions transition_state allows, as `from:to` pairs
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = parse_transition,
+        default_value = "draft:active,active:discontinued,discontinued:active,discontinued:archived"
+    )]
+    pub state_transitions: Vec<(LifecycleState, LifecycleState)>,
+
+    /// Milliseconds of recent lock waiting above which get and get_all are
+    /// served from a cached copy of the inventory, flagged with
+    /// `x-stale-read`, instead of queueing behind writes; off when unset
+    #[arg(long)]
+    pub stale_read_threshold: Option<u64>,
+
+    /// Milliseconds between refreshes of the copy stale reads are served from
+    #[arg(long, default_value_t = 500)]
+    pub stale_read_refresh: u64,
+
+    #[command(flatten)]
+    pub feed: FeedConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotValidation {
+    Reject,
+    Skip,
+    Quarantine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ZeroPricePolicy {
+    Reject,
+    Allow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StaleIndexPolicy {
+    Heal,
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DuplicateAddPolicy {
+    Reject,
+    LastWins,
+    Idempotent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NamePolicy {
+    Off,
+    Exact,
+    /// Trims surrounding whitespace and ignores case
+    Normalized,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ZeroQuantityPolicy {
+    Error,
+    Noop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ZeroStockPolicy {
+    Allow,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReservationMode {
+    Hard,
+    Soft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AllocationStrategy {
+    /// The warehouse holding the most first
+    MostStock,
+    /// Warehouses in the region named by `x-region` first, then by most
+    /// stock
+    Nearest,
+    /// Each decrease starts one warehouse further along than the last
+    RoundRobin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TtlPolicy {
+    Reject,
+    Clamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OverloadPolicy {
+    Shed,
+    Queue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StockPolicy {
+    Strict,
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Rounding {
+    Nearest,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CostFloorPolicy {
+    Allow,
+    Warn,
+    Reject,
+}
+
+/// Field names and values used when rendering the JSON catalog feed
+#[derive(Debug, Clone, Args)]
+pub struct FeedConfig {
+    #[arg(long = "feed-currency", default_value = "USD")]
+    pub currency: String,
+
+    #[arg(long = "feed-id-key", default_value = "id")]
+    pub id_key: String,
+
+    #[arg(long = "feed-title-key", default_value = "title")]
+    pub title_key: String,
+
+    #[arg(long = "feed-price-key", default_value = "price")]
+    pub price_key: String,
+
+    #[arg(long = "feed-availability-key", default_value = "availability")]
+    pub availability_key: String,
+
+    #[arg(long = "feed-currency-key", default_value = "currency")]
+    pub currency_key: String,
+}
+
+fn parse_rate(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!("`{}` isn't a positive rate", value)),
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::parse_from(["server"])
+    }
+}
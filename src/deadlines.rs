@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use prost::Message;
+use prost_types::FileDescriptorSet;
+use tonic::body::BoxBody;
+use tonic::codegen::http::{Request, Response};
+use tonic::codegen::{BoxFuture, Service};
+use tonic::Status;
+use tower::Layer;
+
+const DEADLINE_ERR: &str = "request exceeded the server's default deadline";
+
+/// Aborts requests that run past a per-method default deadline. Client
+/// deadlines are enforced by tonic itself, so whichever is tighter wins.
+#[derive(Debug, Clone)]
+pub struct DeadlineLayer {
+    deadlines: Arc<HashMap<String, Duration>>,
+}
+
+impl DeadlineLayer {
+    /// `deadlines` maps gRPC method names, e.g. `GetAll`, to milliseconds.
+    pub fn new(deadlines: &[(String, u64)]) -> Self {
+        let deadlines = deadlines
+            .iter()
+            .map(|(method, ms)| (method.clone(), Duration::from_millis(*ms)))
+            .collect();
+
+        DeadlineLayer {
+            deadlines: Arc::new(deadlines),
+        }
+    }
+}
+
+impl<S> Layer<S> for DeadlineLayer {
+    type Service = Deadline<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Deadline {
+            inner,
+            deadlines: self.deadlines.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Deadline<S> {
+    inner: S,
+    deadlines: Arc<HashMap<String, Duration>>,
+}
+
+impl<S, B> Service<Request<B>> for Deadline<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let method = request.uri().path().rsplit('/').next().unwrap_or_default();
+        let deadline = self.deadlines.get(method).copied();
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let deadline = match deadline {
+                Some(deadline) => deadline,
+                None => return response.await,
+            };
+
+            match tokio::time::timeout(deadline, response).await {
+                Ok(response) => response,
+                Err(_) => Ok(Status::deadline_exceeded(DEADLINE_ERR).to_http()),
+            }
+        })
+    }
+}
+
+/// Parses a `Method=milliseconds` pair from the command line.
+pub fn parse_method_deadline(arg: &str) -> Result<(String, u64), String> {
+    let (method, ms) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected METHOD=MILLISECONDS, got `{}`", arg))?;
+    let ms = ms
+        .parse()
+        .map_err(|err| format!("bad deadline `{}`: {}", ms, err))?;
+    Ok((method.to_owned(), ms))
+}
+
+/// Fails on any deadline naming a method the services in `descriptors`
+/// don't have, which would otherwise never match a request.
+pub fn check_methods(deadlines: &[(String, u64)], descriptors: &[u8]) -> Result<(), String> {
+    let set = FileDescriptorSet::decode(descriptors).map_err(|err| err.to_string())?;
+    let known: Vec<&str> = set
+        .file
+        .iter()
+        .flat_map(|file| &file.service)
+        .flat_map(|service| &service.method)
+        .filter_map(|method| method.name.as_deref())
+        .collect();
+
+    match deadlines
+        .iter()
+        .find(|(method, _)| !known.contains(&method.as_str()))
+    {
+        Some((method, _)) => Err(format!("deadline for unknown method `{}`", method)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store_proto::FILE_DESCRIPTOR_SET;
+
+    #[test]
+    fn deadlines_parse_and_name_known_methods() {
+        let get = parse_method_deadline("Get=30").unwrap();
+        assert_eq!(get, ("Get".to_owned(), 30));
+        assert!(parse_method_deadline("Get").is_err());
+        assert!(parse_method_deadline("Get=soon").is_err());
+
+        assert!(check_methods(&[get, ("StreamAll".into(), 5000)], FILE_DESCRIPTOR_SET).is_ok());
+        let typo = check_methods(&[("Gett".into(), 30)], FILE_DESCRIPTOR_SET);
+        assert_eq!(typo.unwrap_err(), "deadline for unknown method `Gett`");
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+use crate::config::FeedConfig;
+use crate::store::Item;
+
+pub const IN_STOCK: &str = "in stock";
+pub const OUT_OF_STOCK: &str = "out of stock";
+pub const BACKORDER: &str = "backorder";
+
+pub fn availability(item: &Item) -> &'static str {
+    match item.stock.as_ref() {
+        Some(stock) if stock.quantity > 0 => IN_STOCK,
+        Some(stock) if stock.backorder => BACKORDER,
+        _ => OUT_OF_STOCK,
+    }
+}
+
+/// Renders items as a flat JSON array using the configured keys.
+pub fn render(config: &FeedConfig, items: &[&Item]) -> String {
+    let mut json = String::from("[");
+
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+
+        let sku = item
+            .identifier
+            .as_ref()
+            .map(|id| id.sku.as_str())
+            .unwrap_or_default();
+        let title = item
+            .information
+            .as_ref()
+            .and_then(|info| info.name.as_deref())
+            .unwrap_or(sku);
+        let price = item
+            .stock
+            .as_ref()
+            .map(|stock| stock.price)
+            .unwrap_or_default();
+
+        json.push('{');
+        write_field(&mut json, &config.id_key, &quote(sku));
+        json.push(',');
+        write_field(&mut json, &config.title_key, &quote(title));
+        json.push(',');
+        write_field(&mut json, &config.price_key, &price.to_string());
+        json.push(',');
+        write_field(
+            &mut json,
+            &config.availability_key,
+            &quote(availability(item)),
+        );
+        json.push(',');
+        write_field(&mut json, &config.currency_key, &quote(&config.currency));
+        json.push('}');
+    }
+
+    json.push(']');
+    json
+}
+
+fn write_field(json: &mut String, key: &str, value: &str) {
+    json.push_str(&quote(key));
+    json.push(':');
+    json.push_str(value);
+}
+
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{Mutex as KeyLock, OwnedMutexGuard};
+
+use crate::store::InventoryChangeResponse;
+
+type Slot = Arc<KeyLock<Option<Completed>>>;
+
+#[derive(Debug, Clone)]
+pub struct Completed {
+    pub sku: String,
+    pub response: InventoryChangeResponse,
+    recorded_at: u64,
+}
+
+/// Results of adds made under an idempotency key. Each key has its own
+/// lock, held for the whole add, so a concurrent request with the same key
+/// waits for the first and is answered with its result instead of running
+/// again. Failed adds aren't remembered.
+#[derive(Debug, Clone)]
+pub struct IdempotencyKeys {
+    ttl: u64,
+    keys: Arc<Mutex<HashMap<String, Slot>>>,
+}
+
+pub struct KeyGuard {
+    slot: OwnedMutexGuard<Option<Completed>>,
+    now: u64,
+}
+
+impl IdempotencyKeys {
+    pub fn new(ttl: u64) -> Self {
+        IdempotencyKeys {
+            ttl,
+            keys: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Waits for any add holding `key`, then holds it until the guard is
+    /// dropped. Keys nobody is waiting on are forgotten once their result
+    /// has expired.
+    pub async fn lock(&self, key: &str, now: u64) -> KeyGuard {
+        let ttl = self.ttl;
+        let fresh = |completed: &Completed| completed.recorded_at.saturating_add(ttl) > now;
+
+        let lock = {
+            let mut keys = self.keys.lock().unwrap();
+            keys.retain(|_, lock| {
+                // Clones are only taken under this mutex, so a lone one
+                // means nobody else is holding the key or waiting for it.
+                Arc::strong_count(lock) > 1
+                    || lock
+                        .try_lock()
+                        .is_ok_and(|slot| slot.as_ref().is_some_and(fresh))
+            });
+            keys.entry(key.to_owned()).or_default().clone()
+        };
+
+        let mut slot = lock.lock_owned().await;
+        if slot.as_ref().is_some_and(|completed| !fresh(completed)) {
+            *slot = None;
+        }
+        KeyGuard { slot, now }
+    }
+}
+
+impl KeyGuard {
+    /// The result of the last add made with the key, if it hasn't expired.
+    pub fn completed(&self) -> Option<&Completed> {
+        self.slot.as_ref()
+    }
+
+    pub fn complete(mut self, sku: String, response: InventoryChangeResponse) {
+        *self.slot = Some(Completed {
+            sku,
+            response,
+            recorded_at: self.now,
+        });
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use crate::config::{NamePolicy, StaleIndexPolicy};
+use crate::slug;
+use crate::store::{IndexIssue, Item};
+
+/// Secondary lookups kept in step with the inventory map. Always locked after
+/// the inventory itself.
+#[derive(Debug)]
+pub struct Indexes {
+    policy: NamePolicy,
+    stale: StaleIndexPolicy,
+    pub slugs: HashMap<String, String>,
+    pub names: HashMap<String, String>,
+}
+
+impl Indexes {
+    pub fn new(policy: NamePolicy, stale: StaleIndexPolicy) -> Self {
+        Indexes {
+            policy,
+            stale,
+            slugs: HashMap::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// The key an item's name is unique under, or `None` when names aren't
+    /// required to be unique.
+    pub fn name_key(&self, item: &Item) -> Option<String> {
+        let name = item.information.as_ref()?.name.as_deref()?;
+        match self.policy {
+            NamePolicy::Off => None,
+            NamePolicy::Exact => Some(name.to_owned()),
+            NamePolicy::Normalized => Some(name.trim().to_lowercase()),
+        }
+    }
+
+    /// Whether `item` may take its name under `sku`. An entry held by an item
+    /// that no longer has the name is stale and doesn't count.
+    pub fn name_available(&mut self, map: &HashMap<String, Item>, sku: &str, item: &Item) -> bool {
+        let key = match self.name_key(item) {
+            Some(key) => key,
+            None => return true,
+        };
+        let owner = match self.names.get(&key) {
+            Some(owner) if owner == sku => return true,
+            Some(owner) => owner.clone(),
+            None => return true,
+        };
+
+        let current = live(map, &owner).and_then(|owned| self.name_key(owned));
+        if current.as_ref() == Some(&key) {
+            return false;
+        }
+
+        tracing::warn!(index = NAME_INDEX, key, sku = owner, "stale index entry");
+        if self.stale == StaleIndexPolicy::Heal {
+            self.names.remove(&key);
+        }
+        true
+    }
+
+    /// Resolves a slug to the SKU of the live item that has it. A stale or
+    /// missing entry falls back to scanning `map`, which is logged and, when
+    /// healing, fixes the entry.
+    pub fn resolve_slug(&mut self, map: &HashMap<String, Item>, slug: &str) -> Option<String> {
+        let indexed = self.slugs.get(slug).cloned();
+        if let Some(sku) = indexed.as_ref() {
+            if live(map, sku).is_some_and(|item| item.slug == slug) {
+                return indexed;
+            }
+            tracing::warn!(index = SLUG_INDEX, key = slug, sku, "stale index entry");
+        }
+
+        let found = map
+            .iter()
+            .find(|(_, item)| !item.deleted && item.slug == slug)
+            .map(|(sku, _)| sku.clone());
+        if let Some(sku) = found.as_ref() {
+            if indexed.is_none() {
+                tracing::warn!(index = SLUG_INDEX, key = slug, sku, "missing index entry");
+            }
+        }
+
+        if self.stale == StaleIndexPolicy::Heal {
+            match found.as_ref() {
+                Some(sku) => {
+                    self.slugs.insert(slug.to_owned(), sku.clone());
+                }
+                None => {
+                    self.slugs.remove(slug);
+                }
+            }
+        }
+        found
+    }
+
+    /// Indexes `item` under `sku`, giving it a unique slug derived from its
+    /// name. Items without a name get no slug.
+    pub fn insert(&mut self, sku: &str, item: &mut Item) {
+        let base = item
+            .information
+            .as_ref()
+            .and_then(|info| info.name.as_deref())
+            .map(slug::slugify)
+            .unwrap_or_default();
+
+        item.slug = if base.is_empty() {
+            String::new()
+        } else {
+            slug::unique(&base, |candidate| self.slugs.contains_key(candidate))
+        };
+
+        if !item.slug.is_empty() {
+            self.slugs.insert(item.slug.clone(), sku.to_owned());
+        }
+
+        if let Some(key) = self.name_key(item) {
+            self.names.insert(key, sku.to_owned());
+        }
+    }
+
+    pub fn remove(&mut self, sku: &str, item: &Item) {
+        if self.slugs.get(&item.slug).map(String::as_str) == Some(sku) {
+            self.slugs.remove(&item.slug);
+        }
+
+        if let Some(key) = self.name_key(item) {
+            if self.names.get(&key).map(String::as_str) == Some(sku) {
+                self.names.remove(&key);
+            }
+        }
+    }
+
+    /// Compares both indexes against the live items in `map` without
+    /// changing anything.
+    pub fn verify(&self, map: &HashMap<String, Item>) -> Vec<IndexIssue> {
+        let mut issues = Vec::new();
+        let issue = |kind: &str, index: &str, key: &str, sku: &str| IndexIssue {
+            kind: kind.into(),
+            index: index.into(),
+            key: key.into(),
+            sku: sku.into(),
+        };
+
+        let mut live: Vec<(&String, &Item)> =
+            map.iter().filter(|(_, item)| !item.deleted).collect();
+        live.sort_by_key(|(sku, _)| *sku);
+
+        let mut names_seen: HashMap<String, &str> = HashMap::new();
+        for (sku, item) in &live {
+            if !item.slug.is_empty() {
+                match self.slugs.get(&item.slug) {
+                    Some(owner) if owner == *sku => {}
+                    Some(_) => issues.push(issue(MISMATCHED, SLUG_INDEX, &item.slug, sku)),
+                    None => issues.push(issue(MISSING, SLUG_INDEX, &item.slug, sku)),
+                }
+            }
+
+            if let Some(key) = self.name_key(item) {
+                if names_seen.insert(key.clone(), sku).is_some() {
+                    issues.push(issue(DUPLICATE, NAME_INDEX, &key, sku));
+                }
+                match self.names.get(&key) {
+                    Some(owner) if owner == *sku => {}
+                    Some(_) => issues.push(issue(MISMATCHED, NAME_INDEX, &key, sku)),
+                    None => issues.push(issue(MISSING, NAME_INDEX, &key, sku)),
+                }
+            }
+        }
+
+        let mut orphans: Vec<IndexIssue> = Vec::new();
+        for (slug, sku) in &self.slugs {
+            let owned = map
+                .get(sku)
+                .is_some_and(|item| !item.deleted && &item.slug == slug);
+            if !owned {
+                orphans.push(issue(ORPHANED, SLUG_INDEX, slug, sku));
+            }
+        }
+        for (key, sku) in &self.names {
+            let owned = map
+                .get(sku)
+                .is_some_and(|item| !item.deleted && self.name_key(item).as_ref() == Some(key));
+            if !owned {
+                orphans.push(issue(ORPHANED, NAME_INDEX, key, sku));
+            }
+        }
+        orphans.sort_by(|a, b| (&a.index, &a.key).cmp(&(&b.index, &b.key)));
+        issues.extend(orphans);
+
+        issues
+    }
+
+    /// Rebuilds both indexes from the live items in `map`. Items keep their
+    /// slug unless another item already claimed it, in which case they get a
+    /// fresh one. Duplicate names can't be repaired and stay unindexed.
+    /// Returns the SKUs whose slug changed.
+    pub fn rebuild(&mut self, map: &mut HashMap<String, Item>) -> Vec<String> {
+        self.slugs.clear();
+        self.names.clear();
+
+        let mut skus: Vec<String> = map
+            .iter()
+            .filter(|(_, item)| !item.deleted)
+            .map(|(sku, _)| sku.clone())
+            .collect();
+        skus.sort();
+
+        let mut reslugged = Vec::new();
+        for sku in skus {
+            let item = map.get_mut(&sku).unwrap();
+            let key = self.name_key(item);
+            let owner = key.as_ref().and_then(|key| self.names.get(key).cloned());
+
+            if item.slug.is_empty() || self.slugs.contains_key(&item.slug) {
+                let before = std::mem::take(&mut item.slug);
+                self.insert(&sku, item);
+                if item.slug != before {
+                    reslugged.push(sku.clone());
+                }
+                // A duplicate name stays with the first item that claimed it.
+                if let (Some(key), Some(owner)) = (key, owner) {
+                    self.names.insert(key, owner);
+                }
+            } else {
+                self.slugs.insert(item.slug.clone(), sku.clone());
+                if let (Some(key), None) = (key, owner) {
+                    self.names.insert(key, sku);
+                }
+            }
+        }
+        reslugged
+    }
+}
+
+fn live<'a>(map: &'a HashMap<String, Item>, sku: &str) -> Option<&'a Item> {
+    map.get(sku).filter(|item| !item.deleted)
+}
+
+const SLUG_INDEX: &str = "slug";
+const NAME_INDEX: &str = "name";
+
+const MISSING: &str = "missing";
+const MISMATCHED: &str = "mismatched";
+const ORPHANED: &str = "orphaned";
+const DUPLICATE: &str = "duplicate";
@@ -0,0 +1,46 @@
+use crate::store::LifecycleState;
+
+/// The kinds of change an item's lifecycle state can forbid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Taking stock away for a sale: decreases, reservations and claims.
+    Sell,
+    /// Adding stock.
+    Restock,
+    /// Changing the price, name or tax category.
+    Edit,
+}
+
+/// Whether an item in `state` may undergo `operation`. Drafts can't be sold,
+/// discontinued items can't be restocked and archived items can't change at
+/// all.
+pub fn permits(state: LifecycleState, operation: Operation) -> bool {
+    match state {
+        LifecycleState::Active => true,
+        LifecycleState::Draft => operation != Operation::Sell,
+        LifecycleState::Discontinued => operation != Operation::Restock,
+        LifecycleState::Archived => false,
+    }
+}
+
+/// Whether an item in `state` is offered to customers, and so belongs in
+/// the product feed.
+pub fn is_listed(state: LifecycleState) -> bool {
+    matches!(state, LifecycleState::Active | LifecycleState::Discontinued)
+}
+
+pub fn name(state: LifecycleState) -> String {
+    state.as_str_name().to_ascii_lowercase()
+}
+
+/// Parses a `from:to` transition from the command line.
+pub fn parse_transition(arg: &str) -> Result<(LifecycleState, LifecycleState), String> {
+    let (from, to) = arg
+        .split_once(':')
+        .ok_or_else(|| format!("expected FROM:TO, got `{}`", arg))?;
+    let state = |name: &str| {
+        LifecycleState::from_str_name(&name.to_ascii_uppercase())
+            .ok_or_else(|| format!("unknown lifecycle state `{}`", name))
+    };
+    Ok((state(from)?, state(to)?))
+}
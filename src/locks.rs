@@ -0,0 +1,91 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Times lock acquisitions, warning about and counting any that wait longer
+/// than the configured threshold.
+#[derive(Debug, Clone)]
+pub struct LockTimer {
+    threshold: Duration,
+    slow: Arc<AtomicU64>,
+    /// Moving average of recent waits, in microseconds.
+    recent_wait: Arc<AtomicU64>,
+    abandoned: Arc<AtomicU64>,
+}
+
+impl LockTimer {
+    pub fn new(threshold: Duration) -> Self {
+        LockTimer {
+            threshold,
+            slow: Arc::new(AtomicU64::new(0)),
+            recent_wait: Arc::new(AtomicU64::new(0)),
+            abandoned: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Locks `mutex` on behalf of `rpc`. `sku` is empty when the RPC isn't
+    /// about a single item.
+    pub async fn lock<'a, T>(
+        &self,
+        mutex: &'a Mutex<T>,
+        rpc: &str,
+        sku: &str,
+    ) -> MutexGuard<'a, T> {
+        let started = Instant::now();
+        let guard = mutex.lock().await;
+
+        let waited = started.elapsed();
+        let waited_us = waited.as_micros() as u64;
+        let _ = self
+            .recent_wait
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+                Some((average * 7 + waited_us) / 8)
+            });
+        if waited > self.threshold {
+            self.slow.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                rpc,
+                sku,
+                waited_ms = waited.as_millis() as u64,
+                "slow lock acquisition"
+            );
+        }
+
+        guard
+    }
+
+    /// Like `lock`, but gives up and returns `None` once `limit` passes, so
+    /// background loops can skip a round instead of queueing behind writers.
+    pub async fn lock_within<'a, T>(
+        &self,
+        mutex: &'a Mutex<T>,
+        rpc: &str,
+        sku: &str,
+        limit: Duration,
+    ) -> Option<MutexGuard<'a, T>> {
+        match tokio::time::timeout(limit, self.lock(mutex, rpc, sku)).await {
+            Ok(guard) => Some(guard),
+            Err(_) => {
+                self.abandoned.fetch_add(1, Ordering::Relaxed);
+                tracing::debug!(rpc, sku, "lock acquisition abandoned");
+                None
+            }
+        }
+    }
+
+    pub fn abandoned_acquisitions(&self) -> u64 {
+        self.abandoned.load(Ordering::Relaxed)
+    }
+
+    /// How long lock acquisitions have recently been waiting, weighted
+    /// towards the latest.
+    pub fn recent_wait(&self) -> Duration {
+        Duration::from_micros(self.recent_wait.load(Ordering::Relaxed))
+    }
+
+    pub fn slow_acquisitions(&self) -> u64 {
+        self.slow.load(Ordering::Relaxed)
+    }
+}
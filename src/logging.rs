@@ -0,0 +1,170 @@
+use std::fmt::{self, Write};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use crate::config::LogFormat;
+use crate::feed::quote;
+
+/// Prints tracing events as lines through `out`, either in the same
+/// `LEVEL: message` shape as the rest of the server's output or as one JSON
+/// object per line.
+struct LineSubscriber<W> {
+    format: LogFormat,
+    level: Level,
+    out: W,
+}
+
+impl<W: Fn(String) + Send + Sync + 'static> Subscriber for LineSubscriber<W> {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.level
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let level = event.metadata().level();
+        match self.format {
+            LogFormat::Pretty => {
+                let mut line = Line::default();
+                event.record(&mut line);
+                (self.out)(format!("{}: {}{}", level, line.message, line.fields));
+            }
+            LogFormat::Json => {
+                let mut json = JsonLine {
+                    json: format!("{{\"level\":{}", quote(level.as_str())),
+                };
+                event.record(&mut json);
+                (self.out)(format!("{}}}", json.json));
+            }
+        }
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Line {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_owned();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+struct JsonLine {
+    json: String,
+}
+
+impl JsonLine {
+    fn push(&mut self, field: &Field, value: &str) {
+        let _ = write!(self.json, ",{}:{}", quote(field.name()), value);
+    }
+}
+
+impl Visit for JsonLine {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(field, &value.to_string());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, &value.to_string());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, &value.to_string());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, &quote(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.push(field, &quote(&format!("{:?}", value)));
+    }
+}
+
+pub fn init(format: LogFormat, level: Level) {
+    let subscriber = LineSubscriber {
+        format,
+        level,
+        out: |line| println!("{}", line),
+    };
+    if let Err(err) = tracing::subscriber::set_global_default(subscriber) {
+        println!("ERROR: failed to install log subscriber: {:?}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// The lines logged while running `log` under `format`.
+    fn capture(format: LogFormat, log: impl FnOnce()) -> Vec<String> {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let captured = lines.clone();
+        let subscriber = LineSubscriber {
+            format,
+            level: Level::INFO,
+            out: move |line| captured.lock().unwrap().push(line),
+        };
+        tracing::subscriber::with_default(subscriber, log);
+
+        let lines = lines.lock().unwrap();
+        lines.clone()
+    }
+
+    #[test]
+    fn json_lines_carry_every_field() {
+        let lines = capture(LogFormat::Json, || {
+            tracing::warn!(
+                rpc = "add",
+                sku = "say \"hi\"",
+                waited_ms = 7u64,
+                "slow lock acquisition"
+            );
+            tracing::debug!("below the level");
+        });
+        assert_eq!(
+            lines,
+            [concat!(
+                r#"{"level":"WARN","message":"slow lock acquisition","#,
+                r#""rpc":"add","sku":"say \"hi\"","waited_ms":7}"#
+            )]
+        );
+    }
+
+    #[test]
+    fn pretty_lines_lead_with_level_and_message() {
+        let lines = capture(LogFormat::Pretty, || {
+            tracing::info!(items = 3u64, "snapshot loaded");
+        });
+        assert_eq!(lines, ["INFO: snapshot loaded items=3"]);
+    }
+}
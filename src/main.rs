@@ -1,11 +1,50 @@
+#![allow(clippy::result_large_err)]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::Parser;
 use tonic::transport::Server;
 
+use audit::{AuditLog, FileSink};
+use backend::{Journaled, SnapshotFile};
+use clock::SystemClock;
+use config::Config;
+use deadlines::DeadlineLayer;
 use server::StoreInventory;
 use store::inventory_server::InventoryServer;
+use wal::Wal;
+use webhooks::{DeadLetters, WebhookConfig, Webhooks};
 
+pub mod audit;
+pub mod backend;
+pub mod changes;
+pub mod clock;
+pub mod config;
+pub mod deadlines;
+pub mod feed;
+pub mod idempotency;
+pub mod indexes;
+pub mod lifecycle;
+pub mod locks;
+pub mod logging;
+pub mod operations;
+pub mod pricing;
+pub mod read_cache;
+pub mod reservations;
+pub mod retry;
+pub mod schema;
+pub mod search;
 pub mod server;
+pub mod signing;
+pub mod slug;
 pub mod store;
+pub mod wal;
+pub mod warehouses;
+pub mod watchers;
+pub mod webhooks;
 
+#[allow(dead_code)]
 mod store_proto {
     include!("store.rs");
     pub(crate) const FILE_DESCRIPTOR_SET: &[u8] =
@@ -15,7 +54,57 @@ mod store_proto {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = "127.0.0.1:9000".parse()?;
-    let inventory = StoreInventory::default();
+    let config = Config::parse();
+    logging::init(config.log_format, config.log_level);
+
+    deadlines::check_methods(&config.method_deadlines, store_proto::FILE_DESCRIPTOR_SET)?;
+    let deadlines = DeadlineLayer::new(&config.method_deadlines);
+    let audit = match config.audit_file.as_ref() {
+        Some(path) => AuditLog::spawn(FileSink::open(path)?, config.audit_queue_capacity),
+        None => AuditLog::default(),
+    };
+    let webhooks = if config.webhook_urls.is_empty() {
+        Webhooks::default()
+    } else {
+        let webhook_config = WebhookConfig {
+            urls: config.webhook_urls.clone(),
+            secret: config.webhook_secret.clone(),
+            timeout: Duration::from_millis(config.webhook_timeout),
+            retries: config.webhook_retries,
+            events: config.webhook_events.clone(),
+        };
+        let dead_letters = match config.webhook_dead_letter_file.as_ref() {
+            Some(path) => Some(DeadLetters::open(path)?),
+            None => None,
+        };
+        Webhooks::spawn(webhook_config, config.webhook_queue_capacity, dead_letters)
+    };
+    let snapshot_file = config.snapshot_file.clone();
+    let wal_file = config.wal_file.clone();
+    let inventory = StoreInventory::new(config, Arc::new(SystemClock))
+        .with_audit(audit)
+        .with_webhooks(webhooks);
+    inventory.spawn_sweeper();
+    inventory.spawn_read_cache();
+    match (wal_file, snapshot_file) {
+        // Changes can't be journaled until the WAL has been replayed, so
+        // startup waits for it, and a snapshot and WAL that don't line up
+        // stop the server here.
+        (Some(wal_path), snapshot_path) => {
+            let backend = Journaled::new(snapshot_path.clone(), &wal_path);
+            let version = inventory.spawn_load(Arc::new(backend)).await??;
+            inventory
+                .start_journal(Wal::open(&wal_path, version)?)
+                .await;
+            if let Some(path) = snapshot_path {
+                inventory.spawn_snapshots(SnapshotFile::new(path));
+            }
+        }
+        (None, Some(path)) => {
+            inventory.spawn_load(Arc::new(SnapshotFile::new(path)));
+        }
+        (None, None) => {}
+    }
 
     let reflection_service = tonic_reflection::server::Builder::configure()
         .register_encoded_file_descriptor_set(store_proto::FILE_DESCRIPTOR_SET)
@@ -23,6 +112,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .unwrap();
 
     Server::builder()
+        .layer(deadlines)
         .add_service(InventoryServer::new(inventory))
         .add_service(reflection_service)
         .serve(addr)
@@ -0,0 +1,53 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::store::InventoryUpdateResponse;
+
+#[derive(Debug)]
+struct Operation {
+    id: String,
+    recorded_at: u64,
+    response: InventoryUpdateResponse,
+}
+
+/// Remembers the result of externally identified operations per SKU so a
+/// redelivered request can be answered without being applied twice.
+#[derive(Debug)]
+pub struct OperationLog {
+    ttl: u64,
+    capacity: usize,
+    operations: HashMap<String, VecDeque<Operation>>,
+}
+
+impl OperationLog {
+    pub fn new(ttl: u64, capacity: usize) -> Self {
+        OperationLog {
+            ttl,
+            capacity,
+            operations: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, sku: &str, id: &str, now: u64) -> Option<InventoryUpdateResponse> {
+        let operations = self.operations.get_mut(sku)?;
+        let ttl = self.ttl;
+        operations.retain(|op| op.recorded_at.saturating_add(ttl) > now);
+
+        operations
+            .iter()
+            .find(|op| op.id == id)
+            .map(|op| op.response.clone())
+    }
+
+    pub fn record(&mut self, sku: &str, id: &str, response: InventoryUpdateResponse, now: u64) {
+        let operations = self.operations.entry(sku.to_owned()).or_default();
+        operations.push_back(Operation {
+            id: id.to_owned(),
+            recorded_at: now,
+            response,
+        });
+
+        while operations.len() > self.capacity {
+            operations.pop_front();
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::config::Rounding;
+
+/// Applies a signed percentage to a price and rounds the result to cents.
+pub fn apply_percent(price: f32, percent: f64, rounding: Rounding) -> f32 {
+    round_to_cents(price as f64 * (1.0 + percent / 100.0), rounding) as f32
+}
+
+pub fn round_to_cents(amount: f64, rounding: Rounding) -> f64 {
+    round_to_decimals(amount, 2, rounding)
+}
+
+pub fn round_to_decimals(amount: f64, decimals: u32, rounding: Rounding) -> f64 {
+    let scale = 10f64.powi(decimals as i32);
+    let minor = amount * scale;
+    let minor = match rounding {
+        Rounding::Nearest => minor.round(),
+        Rounding::Up => minor.ceil(),
+        Rounding::Down => minor.floor(),
+    };
+    minor / scale
+}
+
+/// The number of decimal places in an ISO 4217 currency's minor unit, or
+/// `None` for a currency this server doesn't know.
+pub fn minor_unit_decimals(currency: &str) -> Option<u32> {
+    let decimals = match currency.to_ascii_uppercase().as_str() {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        "AED" | "AUD" | "BRL" | "CAD" | "CHF" | "CNY" | "CZK" | "DKK" | "EUR" | "GBP" | "HKD"
+        | "HUF" | "IDR" | "ILS" | "INR" | "MXN" | "MYR" | "NOK" | "NZD" | "PHP" | "PLN" | "SAR"
+        | "SEK" | "SGD" | "THB" | "TRY" | "TWD" | "USD" | "ZAR" => 2,
+        _ => return None,
+    };
+    Some(decimals)
+}
+
+/// The value of `quantity` units at `price` in whole cents. Summing these
+/// instead of floats makes totals exact and independent of iteration order.
+pub fn line_cents(price: f32, quantity: u64) -> i128 {
+    (price as f64 * 100.0).round() as i128 * quantity as i128
+}
+
+pub fn cents_to_amount(cents: i128) -> f64 {
+    cents as f64 / 100.0
+}
+
+/// Like `line_cents`, in a currency's minor units of `decimals` places.
+pub fn line_minor(price: f32, quantity: u64, decimals: u32) -> i128 {
+    (price as f64 * 10f64.powi(decimals as i32)).round() as i128 * quantity as i128
+}
+
+pub fn minor_to_amount(minor: i128, decimals: u32) -> f64 {
+    minor as f64 / 10f64.powi(decimals as i32)
+}
+
+/// `percent` of an amount in minor units, rounded to a whole minor unit.
+pub fn percent_of_minor(minor: i128, percent: f64, rounding: Rounding) -> i128 {
+    round_to_decimals(minor as f64 * percent / 100.0, 0, rounding) as i128
+}
+
+/// Locales `format_price` knows how to render.
+pub const LOCALES: &[&str] = &["en-US", "en-GB", "de-DE", "fr-FR", "ja-JP"];
+
+struct LocaleRules {
+    group: &'static str,
+    decimal: &'static str,
+    /// Separator between the number and a trailing symbol; `None` puts the
+    /// symbol in front.
+    suffix: Option<&'static str>,
+}
+
+fn locale_rules(locale: &str) -> Option<LocaleRules> {
+    let rules = match locale {
+        "en-US" | "en-GB" | "ja-JP" => LocaleRules {
+            group: ",",
+            decimal: ".",
+            suffix: None,
+        },
+        "de-DE" => LocaleRules {
+            group: ".",
+            decimal: ",",
+            suffix: Some("\u{a0}"),
+        },
+        "fr-FR" => LocaleRules {
+            group: "\u{202f}",
+            decimal: ",",
+            suffix: Some("\u{a0}"),
+        },
+        _ => return None,
+    };
+    Some(rules)
+}
+
+fn currency_symbol(currency: &str) -> Option<&'static str> {
+    let symbol = match currency {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "INR" => "₹",
+        "KRW" => "₩",
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+/// Renders an amount in a currency for display under a locale's separators
+/// and symbol placement, rounded to the currency's minor unit. Currencies
+/// without a well-known symbol are shown by their code. `None` when the
+/// currency or locale is unknown.
+pub fn format_amount(
+    amount: f64,
+    currency: &str,
+    locale: &str,
+    rounding: Rounding,
+) -> Option<String> {
+    let currency = currency.to_ascii_uppercase();
+    let decimals = minor_unit_decimals(&currency)?;
+    let rules = locale_rules(locale)?;
+
+    let rounded = round_to_decimals(amount, decimals, rounding);
+    let digits = format!("{:.*}", decimals as usize, rounded.abs());
+    let (whole, fraction) = match digits.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+
+    let mut number = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            number.push_str(rules.group);
+        }
+        number.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        number.push_str(rules.decimal);
+        number.push_str(fraction);
+    }
+
+    let sign = if rounded < 0.0 { "-" } else { "" };
+    let symbol = currency_symbol(&currency).unwrap_or(&currency);
+    Some(match rules.suffix {
+        Some(separator) => format!("{sign}{number}{separator}{symbol}"),
+        None if symbol.len() > 1 && symbol.is_ascii() => format!("{sign}{symbol}\u{a0}{number}"),
+        None => format!("{sign}{symbol}{number}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_follow_locale_separators_and_symbols() {
+        let format = |amount, currency, locale| {
+            format_amount(amount, currency, locale, Rounding::Nearest).unwrap()
+        };
+        assert_eq!(format(1234567.891, "USD", "en-US"), "$1,234,567.89");
+        assert_eq!(format(1234.5, "eur", "de-DE"), "1.234,50\u{a0}€");
+        assert_eq!(format(1234.5, "EUR", "fr-FR"), "1\u{202f}234,50\u{a0}€");
+        assert_eq!(format(1234.5, "JPY", "ja-JP"), "¥1,235");
+        assert_eq!(format(-5.0, "CHF", "en-GB"), "-CHF\u{a0}5.00");
+        assert_eq!(format(0.5, "KWD", "en-US"), "KWD\u{a0}0.500");
+    }
+
+    #[test]
+    fn unknown_currencies_and_locales_are_none() {
+        assert_eq!(format_amount(1.0, "XXX", "en-US", Rounding::Nearest), None);
+        assert_eq!(format_amount(1.0, "USD", "xx-XX", Rounding::Nearest), None);
+    }
+}
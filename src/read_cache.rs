@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::MutexGuard;
+
+use crate::store::Item;
+
+/// A periodically refreshed copy of the inventory that reads can fall back
+/// to when waiting for the live map would take too long.
+#[derive(Debug, Clone, Default)]
+pub struct ReadCache {
+    copy: Arc<RwLock<Option<Cached>>>,
+}
+
+#[derive(Debug)]
+struct Cached {
+    generation: u64,
+    map: Arc<HashMap<String, Item>>,
+}
+
+impl ReadCache {
+    /// The generation the copy was taken at, if there is one.
+    pub fn generation(&self) -> Option<u64> {
+        self.copy
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|cached| cached.generation)
+    }
+
+    pub fn load(&self) -> Option<(u64, Arc<HashMap<String, Item>>)> {
+        let copy = self.copy.read().unwrap();
+        copy.as_ref()
+            .map(|cached| (cached.generation, cached.map.clone()))
+    }
+
+    pub fn store(&self, generation: u64, map: HashMap<String, Item>) {
+        *self.copy.write().unwrap() = Some(Cached {
+            generation,
+            map: Arc::new(map),
+        });
+    }
+}
+
+/// The inventory a read is served from: the live map under its lock, or the
+/// cached copy as of `generation`.
+pub enum ReadView<'a> {
+    Live(MutexGuard<'a, HashMap<String, Item>>),
+    Stale {
+        generation: u64,
+        map: Arc<HashMap<String, Item>>,
+    },
+}
+
+impl ReadView<'_> {
+    pub fn is_stale(&self) -> bool {
+        matches!(self, ReadView::Stale { .. })
+    }
+}
+
+impl Deref for ReadView<'_> {
+    type Target = HashMap<String, Item>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ReadView::Live(map) => map,
+            ReadView::Stale { map, .. } => map,
+        }
+    }
+}
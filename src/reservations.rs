@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct Reservation {
+    pub sku: String,
+    pub quantity: u64,
+    pub expires_at: Option<u64>,
+}
+
+/// Stock held against SKUs. Expired reservations are purged lazily whenever
+/// the set is inspected, freeing their quantity and slot.
+#[derive(Debug, Default)]
+pub struct Reservations {
+    next_id: u64,
+    by_id: HashMap<String, Reservation>,
+    claims: HashMap<String, Vec<String>>,
+}
+
+impl Reservations {
+    /// Purges expired reservations, and claims left with none.
+    pub fn expire(&mut self, now: u64) {
+        self.by_id
+            .retain(|_, reservation| reservation.expires_at.is_none_or(|at| at > now));
+
+        let by_id = &self.by_id;
+        self.claims.retain(|_, ids| {
+            ids.retain(|id| by_id.contains_key(id));
+            !ids.is_empty()
+        });
+    }
+
+    pub fn count(&self, sku: &str) -> usize {
+        self.by_id.values().filter(|r| r.sku == sku).count()
+    }
+
+    /// Every SKU with stock held against it, once each.
+    pub fn skus(&self) -> Vec<&str> {
+        let mut skus: Vec<&str> = self.by_id.values().map(|r| r.sku.as_str()).collect();
+        skus.sort_unstable();
+        skus.dedup();
+        skus
+    }
+
+    pub fn reserved(&self, sku: &str) -> u64 {
+        self.by_id
+            .values()
+            .filter(|r| r.sku == sku)
+            .map(|r| r.quantity)
+            .sum()
+    }
+
+    pub fn insert(&mut self, reservation: Reservation) -> String {
+        self.next_id += 1;
+        let id = format!("r{}", self.next_id);
+        self.by_id.insert(id.clone(), reservation);
+        id
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Reservation> {
+        self.by_id.remove(id)
+    }
+
+    /// Holds a group of reservations under one claim ID so callers can release
+    /// or commit them together.
+    pub fn insert_claim(&mut self, reservations: Vec<Reservation>) -> String {
+        let ids = reservations
+            .into_iter()
+            .map(|reservation| self.insert(reservation))
+            .collect();
+
+        self.next_id += 1;
+        let id = format!("c{}", self.next_id);
+        self.claims.insert(id.clone(), ids);
+        id
+    }
+
+    /// A claim's unexpired reservations, or `None` if the claim is unknown
+    /// or has fully expired.
+    pub fn claim(&self, id: &str) -> Option<Vec<Reservation>> {
+        let held: Vec<Reservation> = self
+            .claims
+            .get(id)?
+            .iter()
+            .filter_map(|id| self.by_id.get(id).cloned())
+            .collect();
+
+        if held.is_empty() {
+            return None;
+        }
+        Some(held)
+    }
+
+    /// Removes a claim and returns its unexpired reservations, or `None` if
+    /// the claim is unknown or has fully expired.
+    pub fn remove_claim(&mut self, id: &str) -> Option<Vec<Reservation>> {
+        let held: Vec<Reservation> = self
+            .claims
+            .remove(id)?
+            .iter()
+            .filter_map(|id| self.by_id.remove(id))
+            .collect();
+
+        if held.is_empty() {
+            return None;
+        }
+        Some(held)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn held(sku: &str, expires_at: Option<u64>) -> Reservation {
+        Reservation {
+            sku: sku.into(),
+            quantity: 1,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn expiring_every_reservation_of_a_claim_drops_the_claim() {
+        let mut reservations = Reservations::default();
+        let gone = reservations.insert_claim(vec![held("a", Some(10)), held("b", Some(10))]);
+        let partly = reservations.insert_claim(vec![held("a", Some(10)), held("b", None)]);
+
+        reservations.expire(10);
+        assert!(!reservations.claims.contains_key(&gone));
+        assert_eq!(reservations.claims[&partly].len(), 1);
+        assert_eq!(reservations.claim(&partly).unwrap()[0].sku, "b");
+    }
+
+    #[test]
+    fn released_members_are_pruned_from_their_claim() {
+        let mut reservations = Reservations::default();
+        let claim = reservations.insert_claim(vec![held("a", None)]);
+        let member = reservations.claims[&claim][0].clone();
+
+        reservations.remove(&member);
+        reservations.expire(0);
+        assert!(reservations.claims.is_empty());
+        assert!(reservations.claim(&claim).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use prost::Message;
+use tonic::{Code, Status};
+
+const RETRY_INFO_TYPE: &str = "type.googleapis.com/google.rpc.RetryInfo";
+
+/// `google.rpc.Status`, the envelope tonic carries in `grpc-status-details-bin`.
+#[derive(Clone, PartialEq, Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<prost_types::Any>,
+}
+
+/// `google.rpc.RetryInfo`
+#[derive(Clone, PartialEq, Message)]
+struct RetryInfo {
+    #[prost(message, optional, tag = "1")]
+    retry_delay: Option<prost_types::Duration>,
+}
+
+/// An `unavailable` status carrying a `RetryInfo` detail that tells clients
+/// how long to wait before trying again.
+pub fn unavailable(message: &str, delay: Duration) -> Status {
+    with_retry(Code::Unavailable, message, delay)
+}
+
+/// A `resource_exhausted` status carrying a `RetryInfo` detail.
+pub fn resource_exhausted(message: &str, delay: Duration) -> Status {
+    with_retry(Code::ResourceExhausted, message, delay)
+}
+
+fn with_retry(code: Code, message: &str, delay: Duration) -> Status {
+    let info = RetryInfo {
+        retry_delay: Some(prost_types::Duration {
+            seconds: delay.as_secs() as i64,
+            nanos: delay.subsec_nanos() as i32,
+        }),
+    };
+
+    let status = RpcStatus {
+        code: code as i32,
+        message: message.to_owned(),
+        details: vec![prost_types::Any {
+            type_url: RETRY_INFO_TYPE.to_owned(),
+            value: info.encode_to_vec(),
+        }],
+    };
+
+    Status::with_details(code, message, status.encode_to_vec().into())
+}
+
+/// The delay a status built by `with_retry` asks clients to wait, decoded
+/// the way a client would.
+#[cfg(test)]
+pub fn retry_delay(status: &Status) -> Option<Duration> {
+    let details = RpcStatus::decode(status.details()).ok()?;
+    let info = details
+        .details
+        .iter()
+        .find(|detail| detail.type_url == RETRY_INFO_TYPE)?;
+    let delay = RetryInfo::decode(info.value.as_slice()).ok()?.retry_delay?;
+    Some(Duration::new(delay.seconds as u64, delay.nanos as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delays_round_trip() {
+        let status = unavailable("down", Duration::from_millis(1500));
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(retry_delay(&status), Some(Duration::from_millis(1500)));
+        assert_eq!(retry_delay(&Status::unavailable("down")), None);
+    }
+}
@@ -0,0 +1,93 @@
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+
+use crate::store::FieldDescription;
+
+/// The fields of `message` and of the messages nested in it, as dotted
+/// paths in declaration order, read from the compiled descriptors so they
+/// always match the proto. Maps aren't descended into.
+pub fn fields(descriptors: &[u8], message: &str) -> Result<Vec<FieldDescription>, String> {
+    let set = FileDescriptorSet::decode(descriptors).map_err(|err| err.to_string())?;
+    let messages: Vec<(String, &DescriptorProto)> = set
+        .file
+        .iter()
+        .flat_map(|file| {
+            let package = file.package().to_owned();
+            file.message_type
+                .iter()
+                .map(move |message| (format!(".{}.{}", package, message.name()), message))
+        })
+        .collect();
+
+    let root = messages
+        .iter()
+        .find(|(name, _)| name.rsplit('.').next() == Some(message))
+        .ok_or_else(|| format!("unknown message `{}`", message))?;
+    let mut fields = Vec::new();
+    describe(&messages, &root.0, root.1, "", &mut fields);
+    Ok(fields)
+}
+
+fn describe(
+    messages: &[(String, &DescriptorProto)],
+    path: &str,
+    message: &DescriptorProto,
+    prefix: &str,
+    fields: &mut Vec<FieldDescription>,
+) {
+    for field in &message.field {
+        let name = format!("{}{}", prefix, field.name());
+        let map_entry = map_entry(path, message, field);
+        fields.push(FieldDescription {
+            name: name.clone(),
+            r#type: match map_entry {
+                Some(entry) => format!(
+                    "map<{}, {}>",
+                    type_name(&entry.field[0]),
+                    type_name(&entry.field[1])
+                ),
+                None => type_name(field),
+            },
+            repeated: field.label() == Label::Repeated && map_entry.is_none(),
+            ..Default::default()
+        });
+
+        if field.r#type() != Type::Message || map_entry.is_some() {
+            continue;
+        }
+        if let Some((nested_path, nested)) = messages
+            .iter()
+            .find(|(nested, _)| nested == field.type_name())
+        {
+            describe(messages, nested_path, nested, &format!("{}.", name), fields);
+        }
+    }
+}
+
+/// The generated entry message when `field` is a map.
+fn map_entry<'a>(
+    path: &str,
+    message: &'a DescriptorProto,
+    field: &FieldDescriptorProto,
+) -> Option<&'a DescriptorProto> {
+    let name = field.type_name().strip_prefix(path)?.strip_prefix('.')?;
+    message.nested_type.iter().find(|nested| {
+        nested.name() == name && nested.options.as_ref().is_some_and(|o| o.map_entry())
+    })
+}
+
+fn type_name(field: &FieldDescriptorProto) -> String {
+    match field.r#type() {
+        Type::Message | Type::Enum => field
+            .type_name()
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+        other => other
+            .as_str_name()
+            .trim_start_matches("TYPE_")
+            .to_ascii_lowercase(),
+    }
+}
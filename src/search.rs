@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+
+/// The Levenshtein distance between `a` and `b` in characters, or `None`
+/// once it's certain to be over `max`. Costs O(len(a) * len(b)) at worst,
+/// but gives up early on strings whose lengths alone rule them out.
+pub fn distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().min().is_some_and(|&best| best > max) {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+/// `text` as matched against: lowercased unless the search is case-sensitive.
+pub fn fold_case(text: &str, case_sensitive: bool) -> Cow<'_, str> {
+    if case_sensitive {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.to_lowercase())
+    }
+}
+
+/// How close `query` comes to an item's SKU, its whole name or any word of
+/// the name, ignoring case unless `case_sensitive`.
+pub fn closeness(
+    query: &str,
+    sku: &str,
+    name: &str,
+    max: usize,
+    case_sensitive: bool,
+) -> Option<usize> {
+    let query = fold_case(query, case_sensitive);
+    let name = fold_case(name, case_sensitive);
+    std::iter::once(fold_case(sku, case_sensitive).as_ref())
+        .chain(std::iter::once(name.as_ref()))
+        .chain(name.split_whitespace())
+        .filter_map(|candidate| distance(&query, candidate, max))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances_past_the_limit_give_up() {
+        assert_eq!(distance("kitten", "sitting", 3), Some(3));
+        assert_eq!(distance("kitten", "sitting", 2), None);
+        assert_eq!(distance("mug", "mugs", 1), Some(1));
+        assert_eq!(distance("a", "abcd", 2), None);
+    }
+
+    #[test]
+    fn closeness_takes_the_nearest_word() {
+        assert_eq!(closeness("blu", "sku-1", "Blue Mug", 2, false), Some(1));
+        assert_eq!(closeness("blu", "sku-1", "Blue Mug", 1, true), None);
+    }
+}
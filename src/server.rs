@@ -1,277 +1,5207 @@
+use futures::future::OptionFuture;
 use futures::Stream;
-use std::borrow::BorrowMut;
-use std::collections::HashMap;
+use prost::Message;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::io;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
-use tokio_stream::wrappers::UnboundedReceiverStream;
-use tonic::{Request, Response, Status};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
+use tokio_stream::StreamExt;
+use tonic::{Request, Response, Status, Streaming};
 
+use crate::audit::{self, AuditEntry, AuditLog};
+use crate::backend::{Backend, BackendError, RetryPolicy, SnapshotFile};
+use crate::changes::ChangeFeed;
+use crate::clock::{Clock, SystemClock};
+use crate::config::{
+    Config, CostFloorPolicy, DuplicateAddPolicy, NamePolicy, OverloadPolicy, ReservationMode,
+    Rounding, SnapshotValidation, StockPolicy, TtlPolicy, ZeroPricePolicy, ZeroQuantityPolicy,
+    ZeroStockPolicy,
+};
+use crate::feed;
+use crate::idempotency::{IdempotencyKeys, KeyGuard};
+use crate::indexes::Indexes;
+use crate::lifecycle::{self, Operation};
+use crate::locks::LockTimer;
+use crate::operations::OperationLog;
+use crate::pricing;
+use crate::read_cache::{ReadCache, ReadView};
+use crate::reservations::{Reservation, Reservations};
+use crate::retry;
+use crate::schema;
+use crate::search;
+use crate::signing;
 use crate::store::inventory_server::Inventory;
+use crate::store::session_request::Command;
+use crate::store::session_response::Outcome;
 use crate::store::{
-    self, InventoryChangeResponse, InventoryUpdateResponse, Item, ItemIdentifier,
-    PriceChangeRequest,
+    self, AuditLogPage, AuditLogRequest, Availability, BatchFailure, BatchSummary, BulkResponse,
+    BundleLine, CancelScheduleRequest, CategoryListRequest, CategoryPage, CategoryRepriceRequest,
+    CategorySort, ChangeEvent, ClaimIdRequest, ClaimRequest, ClaimResponse, CompactReport,
+    CompactRequest, ConsistencyReport, ConsistencyRequest, CreatedRangeRequest, CurrencyTotal,
+    DescribeRequest, ExportChangesRequest, ExportFilter, Feed, FeedRequest, FieldDescription,
+    FormatPriceRequest, FormatPriceResponse, GetManyRequest, GetManyResponse, GetManyResult,
+    HistogramBucket, HistogramRequest, InvariantViolation, InventoryChangeResponse,
+    InventoryUpdateResponse, Item, ItemAll, ItemDescription, ItemEvent, ItemIdentifier, ItemStatus,
+    ItemStock, Items, LifecycleState, MaintenanceRequest, MaintenanceResponse, Margin, Metrics,
+    MetricsRequest, NameChangeRequest, PartialDecreaseResponse, PingRequest, PingResponse,
+    PollRequest, PollResponse, PriceAdjustRequest, PriceAdjustResponse, PriceChange,
+    PriceChangeRequest, PriceHistory, PriceSchedule, PriceWatchRequest, QuantityChangeRequest,
+    QuantityHistogram, Quarantine, QuarantineRequest, QuarantinedItem, Quote, QuoteRequest,
+    QuotedLine, ReleaseRequest, ReorderCost, ReorderCostRequest, ReorderLine, RepriceLine,
+    RepricePreview, ReserveRequest, ReserveResponse, RoundPriceRequest, RoundPriceResponse,
+    ScheduledPrice, SearchMatch, SearchMode, SearchRequest, SearchResults, SessionError,
+    SessionRequest, SessionResponse, SkuResult, SlugRequest, Snapshot, StaleRequest, StateChange,
+    StockCorrection, StockCount, StocktakeSummary, TagMatch, TagRequest, TagsRequest,
+    TaxCategoryChange, TaxCategoryRequest, TopRequest, TotalValue, TotalValueRequest, Unit,
+    UpsertOutcome, UpsertRequest, UpsertResponse, UpsertResult, WarehouseDraw, WatchManyRequest,
+    WeightedRandomRequest,
 };
+use crate::wal::Wal;
+use crate::warehouses;
+use crate::watchers::{Guarded, OpenRateLimit, WatchLimit, WatchSlot};
+use crate::webhooks::Webhooks;
 
 const BAD_PRICE_ERR: &str = "provided PRICE was invalid";
+const NEGATIVE_PRICE_ERR: &str = "provided PRICE was negative";
+const ZERO_PRICE_ERR: &str = "provided PRICE was zero and free items are not allowed";
 const DUP_PRICE_ERR: &str = "item is already at this price";
 const DUP_ITEM_ERR: &str = "item already exists in inventory";
-const DUP_QUANT_ERR: &str = "item is already at this quantity";
+const DUP_NAME_ERR: &str = "another item already has this name";
+const ZERO_QUANT_ERR: &str = "quantity change must be greater than zero";
+const ZERO_INITIAL_ERR: &str = "items must be added with stock on hand";
 const LOW_QUANT_ERR: &str = "invalid decrease quantity cannot bigger than current quantity";
 const EMPTY_SKU_ERR: &str = "provided SKU was empty";
 const NO_ID_ERR: &str = "no ID or SKU provided for item";
 const NO_ITEM_ERR: &str = "the item requested was not found";
 const NO_STOCK_ERR: &str = "no stock provided for item";
+const MAX_QUANT_ERR: &str = "quantity exceeds the configured maximum";
+const OVERFLOW_QUANT_ERR: &str = "quantity would overflow";
+const BELOW_COST_ERR: &str = "price is below the item's cost";
+const EMPTY_CATEGORY_ERR: &str = "provided CATEGORY was empty";
+const BAD_PERCENT_ERR: &str = "percentage would make the price zero or negative";
+const OVERLOADED_ERR: &str = "too many concurrent mutations, try again later";
+const EMPTY_TAG_ERR: &str = "provided TAG was empty";
+const NO_SKUS_ERR: &str = "no SKUs provided";
+const NO_TAGS_ERR: &str = "no tags provided";
+const MAX_RESERVATIONS_ERR: &str = "too many active reservations for this item";
+const NO_RESERVATION_ERR: &str = "the reservation requested was not found";
+const INSUFFICIENT_STOCK_ERR: &str = "not enough unreserved stock for this request";
+const NO_CLAIM_ERR: &str = "the claim requested was not found";
+const EMPTY_NAME_ERR: &str = "provided NAME was empty";
+const EMPTY_SLUG_ERR: &str = "provided SLUG was empty";
+const BAD_CURSOR_ERR: &str = "provided cursor was invalid";
+const BAD_SORT_ERR: &str = "provided sort was invalid";
+const MEASURED_EACH_ERR: &str = "items sold each take a whole quantity, not a measured one";
+const WHOLE_MEASURED_ERR: &str = "measured items take a measured_quantity, not a whole quantity";
+const BAD_MEASURE_ERR: &str = "provided measured_quantity was invalid";
+const BAD_UNITS_ERR: &str =
+    "stock and sale units need a positive sale_units_per and items sold each";
+const UNKNOWN_UNIT_ERR: &str = "unit is neither the item's stock nor sale unit";
+const PARTIAL_UNIT_ERR: &str = "quantity isn't a whole number of stock units";
+const UNKNOWN_CURRENCY_ERR: &str = "provided currency is not supported";
+const UNKNOWN_LOCALE_ERR: &str = "provided locale is not supported";
+const BAD_WATCH_MODE_ERR: &str = "provided watch mode was invalid";
+const BAD_WATCH_WINDOW_ERR: &str = "provided watch window was invalid";
+const NOT_READY_ERR: &str = "the catalog is still loading";
+const MAINTENANCE_ERR: &str = "the catalog is in maintenance and not accepting changes";
+const NOT_ADMIN_ERR: &str = "this operation requires an admin token";
+const WATCH_LIMIT_ERR: &str = "too many watch streams are open";
+const BAD_BUNDLE_ERR: &str =
+    "bundle lines need a positive quantity of an existing item that isn't a bundle";
+const BAD_BUCKETS_ERR: &str = "histogram bounds must be strictly increasing";
+const STREAM_RATE_ERR: &str = "streams are being opened too quickly";
+const SERVER_FIELD_ERR: &str = "field is assigned by the server and must be left unset";
+const BUNDLE_QUANT_ERR: &str = "bundle quantity comes from its components and must be left unset";
+const NO_SCHEDULE_ERR: &str = "no scheduled price change with this ID";
+const FUTURE_TIMESTAMP_ERR: &str = "timestamp is further in the future than the allowed clock skew";
+const NO_CHANGE_LOG_ERR: &str = "change export needs an audit file to replay from";
+const RESYNC_ERR: &str = "resync required: replay backlog exceeds the server's limit";
+const UNKNOWN_TAX_CATEGORY_ERR: &str = "unknown tax category";
+const EMPTY_TAX_CATEGORY_ERR: &str = "provided tax category was empty";
+const BATCH_SIZE_ERR: &str = "batch exceeds the maximum size of";
+const BAD_PARENT_ERR: &str = "a variant's parent must be another item that isn't a variant";
+const RESPONSE_SIZE_ERR: &str = "catalog is too large for get_all, use StreamAll";
+const INVERTED_RANGE_ERR: &str = "range start must not be after its end";
+const EMPTY_QUERY_ERR: &str = "provided search query was empty";
+const BAD_DISCOUNT_ERR: &str = "discount must be between 0 and 100 percent";
+const NO_PRICE_ERR: &str = "item has no price";
+const KEY_REUSED_ERR: &str = "idempotency key was already used to add a different SKU";
+const WATCH_PANIC_ERR: &str = "watch stream failed on the server";
+const TTL_TOO_LONG_ERR: &str = "reservation TTL is over the maximum of";
+const STATE_FORBIDS_ERR: &str = "operation is not permitted while the item is";
+const BAD_STATE_ERR: &str = "unknown lifecycle state";
+const SAME_STATE_ERR: &str = "item is already in that lifecycle state";
+const ILLEGAL_TRANSITION_ERR: &str = "lifecycle transition is not allowed";
+const SNAPSHOT_REJECTED_ERR: &str = "snapshot rejected";
+const WAREHOUSE_TOTAL_ERR: &str = "warehouse stock must add up to the item's quantity";
+const WAREHOUSE_REQUIRED_ERR: &str =
+    "item's stock is kept per warehouse and no warehouse was named";
+const UNKNOWN_WAREHOUSE_ERR: &str = "item has no stock in that warehouse";
+const UNSPLIT_STOCK_ERR: &str = "item's stock isn't kept per warehouse";
+const NO_COMMAND_ERR: &str = "no command provided in session request";
 
-#[derive(Debug)]
+const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+const OVERRIDE_MAX_HEADER: &str = "x-override-max-quantity";
+const ALLOW_BELOW_COST_HEADER: &str = "x-allow-below-cost";
+const INCLUDE_DELETED_HEADER: &str = "x-include-deleted";
+const INCLUDE_MARGIN_HEADER: &str = "x-include-margin";
+const WATCH_MODE_HEADER: &str = "x-watch-mode";
+const WATCH_WINDOW_HEADER: &str = "x-watch-window-ms";
+const PRICE_LIST_HEADER: &str = "x-price-list";
+const STALE_READ_HEADER: &str = "x-stale-read";
+const INCLUDE_AVAILABILITY_HEADER: &str = "x-include-availability";
+const ROLLUP_VARIANTS_HEADER: &str = "x-rollup-variants";
+const IDEMPOTENCY_KEY_HEADER: &str = "x-idempotency-key";
+const SIGNATURE_HEADER: &str = "x-signature";
+const REGION_HEADER: &str = "x-region";
+
+const IN_STOCK_STATUS: &str = "in_stock";
+const LOW_STOCK_STATUS: &str = "low_stock";
+const OUT_OF_STOCK_STATUS: &str = "out_of_stock";
+const BACKORDER_STATUS: &str = "backorder";
+
+/// The RPCs whose audit entries set an item's price: adds and upserts give
+/// it the price it starts from or is replaced with, the rest change it.
+const PRICE_SETTING_RPCS: &[&str] = &["add", "upsert_many", "update_price", "adjust_price_percent"];
+
+/// How often a following change export checks the audit file for new lines.
+const EXPORT_POLL: Duration = Duration::from_millis(200);
+
+/// Change events buffered per export stream before reading pauses.
+const EXPORT_BUFFER: usize = 64;
+
+/// Longest a poll_changes call waits for a change.
+const MAX_POLL_WAIT: Duration = Duration::from_secs(60);
+
+/// How often watch streams poll the inventory for changes.
+const WATCH_POLL: Duration = Duration::from_secs(1);
+
+/// What `put_item` did with an item.
+enum Put {
+    Created,
+    Replaced,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum WatchMode {
+    Immediate,
+    Debounce,
+    Throttle,
+}
+
+#[derive(Debug, Clone)]
 pub struct StoreInventory {
     inventory: Arc<Mutex<HashMap<String, Item>>>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+    generation: Arc<AtomicU64>,
+    started_at: u64,
+    operations: Arc<Mutex<OperationLog>>,
+    idempotency: IdempotencyKeys,
+    mutations: Option<Arc<Semaphore>>,
+    reservations: Arc<Mutex<Reservations>>,
+    indexes: Arc<Mutex<Indexes>>,
+    locks: LockTimer,
+    audit: AuditLog,
+    webhooks: Webhooks,
+    ready: Arc<AtomicBool>,
+    maintenance: Arc<AtomicBool>,
+    watchers: WatchLimit,
+    stream_opens: Option<OpenRateLimit>,
+    schedule_ids: Arc<AtomicU64>,
+    /// Decreases allocated across warehouses so far, for round-robin.
+    allocations: Arc<AtomicU64>,
+    quarantine: Arc<Mutex<Vec<QuarantinedItem>>>,
+    read_cache: ReadCache,
+    changes: ChangeFeed,
+    journal: Arc<Mutex<Option<Journal>>>,
+}
+
+/// The WAL and the generation it has been written up to.
+#[derive(Debug)]
+struct Journal {
+    wal: Wal,
+    written: u64,
 }
 
 impl Default for StoreInventory {
     fn default() -> Self {
+        StoreInventory::new(Config::default(), Arc::new(SystemClock))
+    }
+}
+
+impl StoreInventory {
+    pub fn new(config: Config, clock: Arc<dyn Clock>) -> Self {
         StoreInventory {
             inventory: Arc::new(Mutex::new(HashMap::<String, Item>::new())),
+            started_at: clock.now(),
+            clock,
+            generation: Arc::new(AtomicU64::new(0)),
+            operations: Arc::new(Mutex::new(OperationLog::new(
+                config.operation_id_ttl,
+                config.operation_id_capacity,
+            ))),
+            idempotency: IdempotencyKeys::new(config.idempotency_key_ttl),
+            reservations: Arc::new(Mutex::new(Reservations::default())),
+            indexes: Arc::new(Mutex::new(Indexes::new(
+                config.unique_names,
+                config.stale_index_policy,
+            ))),
+            locks: LockTimer::new(std::time::Duration::from_millis(config.slow_lock_threshold)),
+            audit: AuditLog::default(),
+            webhooks: Webhooks::default(),
+            ready: Arc::new(AtomicBool::new(true)),
+            maintenance: Arc::new(AtomicBool::new(false)),
+            watchers: WatchLimit::new(config.max_watch_streams),
+            stream_opens: config
+                .stream_open_rate
+                .map(|rate| OpenRateLimit::new(rate, config.stream_open_burst)),
+            schedule_ids: Arc::new(AtomicU64::new(0)),
+            allocations: Arc::new(AtomicU64::new(0)),
+            quarantine: Arc::new(Mutex::new(Vec::new())),
+            read_cache: ReadCache::default(),
+            changes: ChangeFeed::default(),
+            journal: Arc::new(Mutex::new(None)),
+            mutations: config
+                .max_concurrent_mutations
+                .map(|permits| Arc::new(Semaphore::new(permits))),
+            config,
         }
     }
-}
 
-#[tonic::async_trait]
-impl Inventory for StoreInventory {
-    async fn add(
-        &self,
-        request: tonic::Request<crate::store::Item>,
-    ) -> Result<tonic::Response<crate::store::InventoryChangeResponse>, tonic::Status> {
-        let item = request.into_inner();
+    /// Periodically removes items whose `created_at + ttl` has passed and
+    /// applies scheduled prices that have come due. A `ttl` of zero never
+    /// expires. Watchers of an evicted item receive `not_found`, and
+    /// WatchMany subscribers a removed event for it.
+    pub fn spawn_sweeper(&self) {
+        let inventory = self.clone();
+        let interval = std::time::Duration::from_secs(self.config.sweep_interval);
 
-        let sku = match item.identifier.as_ref() {
-            Some(id) if id.sku.is_empty() => return Err(Status::invalid_argument(EMPTY_SKU_ERR)),
-            Some(id) => id.sku.to_owned(),
-            None => return Err(Status::invalid_argument(NO_ID_ERR)),
-        };
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                inventory.sweep().await;
+            }
+        });
+    }
 
-        match item.stock.as_ref() {
-            Some(stock) if stock.price <= 0.00 => {
-                return Err(Status::invalid_argument(BAD_PRICE_ERR))
+    /// One pass of the sweeper.
+    async fn sweep(&self) {
+        let now = self.clock.now();
+        let mut map = self.locks.lock(&self.inventory, "sweeper", "").await;
+        let mut indexes = self.locks.lock(&self.indexes, "sweeper", "").await;
+        let mut expired = Vec::new();
+        map.retain(|sku, item| {
+            if !is_expired(item, now) {
+                return true;
             }
-            Some(_) => {}
-            None => return Err(Status::invalid_argument(NO_STOCK_ERR)),
-        };
+            indexes.remove(sku, item);
+            expired.push(sku.clone());
+            false
+        });
+        for sku in &expired {
+            self.audit("sweeper", sku, "expired".into());
+        }
+        let mut changed = !expired.is_empty();
 
-        let mut map = self.inventory.lock().await;
-        if map.get(&sku).is_some() {
-            return Err(Status::already_exists(DUP_ITEM_ERR));
+        for (sku, item) in map.iter_mut().filter(|(_, item)| !item.deleted) {
+            if let Some(stock) = item.stock.as_mut() {
+                changed |= self.promote_due(sku, stock, now);
+            }
         }
 
-        map.insert(sku, item);
+        if changed {
+            self.bump_generation();
+        }
+    }
 
-        Ok(Response::new(InventoryChangeResponse {
-            status: "success".into(),
-        }))
+    /// Keeps the copy of the inventory stale reads are served from up to
+    /// date, when they're enabled. The lock is taken every refresh even when
+    /// nothing changed, so its wait keeps measuring the pressure on it.
+    pub fn spawn_read_cache(&self) {
+        if self.config.stale_read_threshold.is_none() {
+            return;
+        }
+
+        let inventory = self.clone();
+        let interval = std::time::Duration::from_millis(self.config.stale_read_refresh);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let map = inventory
+                    .locks
+                    .lock(&inventory.inventory, "read_cache", "")
+                    .await;
+                let generation = inventory.generation.load(AtomicOrdering::SeqCst);
+                if inventory.read_cache.generation() != Some(generation) {
+                    inventory.read_cache.store(generation, map.clone());
+                }
+            }
+        });
     }
 
-    async fn remove(
+    /// Locks the inventory for a watch stream's refresh, or gives up after
+    /// the watch lock timeout. Watch loops skip a round when it does, so
+    /// many watchers can't pile up behind writers or hold them up, and a
+    /// subscriber only sees its update a round later.
+    async fn watch_lock(
         &self,
-        request: tonic::Request<crate::store::ItemIdentifier>,
-    ) -> Result<tonic::Response<crate::store::InventoryChangeResponse>, tonic::Status> {
-        let item = request.into_inner();
+        rpc: &str,
+        sku: &str,
+    ) -> Option<tokio::sync::MutexGuard<'_, HashMap<String, Item>>> {
+        let limit = std::time::Duration::from_millis(self.config.watch_lock_timeout);
+        self.locks
+            .lock_within(&self.inventory, rpc, sku, limit)
+            .await
+    }
 
-        if item.sku.is_empty() {
-            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+    /// The inventory for a read: the cached copy when recent lock waits are
+    /// over the stale read threshold and a copy exists, the live map
+    /// otherwise.
+    async fn read_view(&self, rpc: &str, sku: &str) -> ReadView<'_> {
+        let pressured = self.config.stale_read_threshold.is_some_and(|threshold| {
+            self.locks.recent_wait() > std::time::Duration::from_millis(threshold)
+        });
+        if pressured {
+            if let Some((generation, map)) = self.read_cache.load() {
+                return ReadView::Stale { generation, map };
+            }
         }
+        ReadView::Live(self.locks.lock(&self.inventory, rpc, sku).await)
+    }
 
-        let mut map = self.inventory.lock().await;
-        let response = match map.remove(&item.sku) {
-            Some(_) => "success: item was removed",
-            None => "sucsees: item did not exist",
-        };
+    /// Attaches an audit log the server records each mutation to.
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.audit = audit;
+        self
+    }
 
-        Ok(Response::new(InventoryChangeResponse {
-            status: response.into(),
-        }))
+    /// Attaches the webhooks each mutation is sent to.
+    pub fn with_webhooks(mut self, webhooks: Webhooks) -> Self {
+        self.webhooks = webhooks;
+        self
     }
 
-    async fn get(
-        &self,
-        request: tonic::Request<crate::store::ItemIdentifier>,
-    ) -> Result<tonic::Response<crate::store::Item>, tonic::Status> {
-        let item = request.into_inner();
+    /// Records a mutation to the audit log, sends it to webhooks and notes
+    /// its SKU as changed at the generation the caller is about to bump to.
+    fn audit(&self, rpc: &'static str, sku: &str, detail: String) {
+        self.audit_with_reason(rpc, sku, detail, String::new());
+    }
 
-        if item.sku.is_empty() {
-            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+    fn audit_with_reason(&self, rpc: &'static str, sku: &str, detail: String, reason: String) {
+        if !sku.is_empty() {
+            let generation = self.generation.load(AtomicOrdering::SeqCst) + 1;
+            self.changes.record(generation, sku);
         }
+        let entry = AuditEntry {
+            at: self.clock.now(),
+            rpc,
+            sku: sku.to_owned(),
+            detail,
+            reason,
+        };
+        self.webhooks.send(&entry);
+        self.audit.record(entry);
+    }
 
-        let map = self.inventory.lock().await;
-        let response = match map.get(&item.sku) {
-            Some(response) => response,
-            None => return Err(Status::not_found(NO_ITEM_ERR)),
+    /// Loads the catalog from `backend` in the background, retrying
+    /// transient errors under the configured policy. Until it finishes,
+    /// reads and mutations fail with `unavailable` and Ping reports the
+    /// server as not ready; a catalog that fails to load, or holds an
+    /// invalid item under the `reject` validation mode, leaves it that way
+    /// rather than serving an empty or partial catalog. The task reports
+    /// the loaded snapshot's version, or why loading failed.
+    pub fn spawn_load(&self, backend: Arc<dyn Backend>) -> JoinHandle<Result<u64, Status>> {
+        self.ready.store(false, AtomicOrdering::SeqCst);
+
+        let inventory = self.clone();
+        let policy = RetryPolicy {
+            attempts: self.config.backend_retry_attempts,
+            backoff: Duration::from_millis(self.config.backend_retry_backoff),
+            max_backoff: Duration::from_millis(self.config.backend_retry_max_backoff),
         };
+        tokio::spawn(async move {
+            let loaded = policy
+                .run(|| {
+                    let backend = backend.clone();
+                    async move {
+                        tokio::task::spawn_blocking(move || backend.load())
+                            .await
+                            .map_err(|err| {
+                                BackendError::Failed(Status::internal(err.to_string()))
+                            })?
+                    }
+                })
+                .await;
+            let snapshot = match loaded {
+                Ok(snapshot) => snapshot,
+                Err(status) => {
+                    tracing::error!(error = status.message(), "failed to load catalog");
+                    return Err(status);
+                }
+            };
 
-        Ok(Response::new(response.clone()))
-    }
+            let mut valid = Vec::with_capacity(snapshot.items.len());
+            let mut invalid = Vec::new();
+            let mut seen = HashSet::new();
+            for item in snapshot.items {
+                let sku = item_sku(&item).to_owned();
+                let checked = inventory
+                    .check_snapshot_item(&item)
+                    .map_err(|status| status.message().to_owned())
+                    .and_then(|()| {
+                        if seen.insert(sku.clone()) {
+                            Ok(())
+                        } else {
+                            Err(DUP_ITEM_ERR.to_owned())
+                        }
+                    });
+                match checked {
+                    Ok(()) => valid.push((sku, item)),
+                    Err(reason) => {
+                        tracing::warn!(sku, reason, "invalid snapshot item");
+                        invalid.push(QuarantinedItem {
+                            item: Some(item),
+                            reason,
+                        });
+                    }
+                }
+            }
 
-    async fn get_all(
-        &self,
-        _request: tonic::Request<crate::store::ItemAll>,
-    ) -> Result<tonic::Response<crate::store::Items>, tonic::Status> {
-        let map = self.inventory.lock().await;
+            match inventory.config.snapshot_validation {
+                SnapshotValidation::Reject if !invalid.is_empty() => {
+                    tracing::error!(invalid = invalid.len() as u64, "snapshot rejected");
+                    let message =
+                        format!("{}: {} invalid items", SNAPSHOT_REJECTED_ERR, invalid.len());
+                    return Err(Status::failed_precondition(message));
+                }
+                SnapshotValidation::Quarantine => {
+                    *inventory
+                        .locks
+                        .lock(&inventory.quarantine, "snapshot_load", "")
+                        .await = invalid;
+                }
+                _ => {}
+            }
 
-        let items = map.values().cloned().collect();
-        let response = store::Items { items };
+            if inventory.config.normalize_snapshot_prices {
+                let (mut converted, mut lossy) = (0u64, 0u64);
+                for (sku, item) in valid.iter_mut() {
+                    let stock = item.stock.as_mut().unwrap();
+                    let (item_converted, item_lossy) = inventory.normalize_prices(sku, stock);
+                    converted += item_converted;
+                    lossy += item_lossy;
+                }
+                tracing::info!(converted, lossy, "snapshot prices normalized");
+            }
 
-        Ok(Response::new(response))
+            let mut map = inventory
+                .locks
+                .lock(&inventory.inventory, "snapshot_load", "")
+                .await;
+            let mut indexes = inventory
+                .locks
+                .lock(&inventory.indexes, "snapshot_load", "")
+                .await;
+            for (sku, mut item) in valid {
+                indexes.insert(&sku, &mut item);
+                map.insert(sku, item);
+            }
+            inventory.bump_generation();
+            inventory.ready.store(true, AtomicOrdering::SeqCst);
+            tracing::info!(items = map.len() as u64, "snapshot loaded");
+            Ok(snapshot.version)
+        })
     }
 
-    async fn decrease_quantity(
-        &self,
-        request: tonic::Request<store::QuantityChangeRequest>,
-    ) -> Result<tonic::Response<store::InventoryUpdateResponse>, tonic::Status> {
-        let item = request.into_inner();
-        let mut map = self.inventory.lock().await;
-        let quantity = match map.get_mut(&item.sku) {
-            Some(quantity) => quantity,
-            None => return Err(Status::not_found(NO_ITEM_ERR)),
+    /// Starts writing every change to `wal`, picking up from the current
+    /// generation.
+    pub async fn start_journal(&self, wal: Wal) {
+        let journal = Journal {
+            wal,
+            written: self.generation.load(AtomicOrdering::SeqCst),
         };
+        *self.locks.lock(&self.journal, "journal", "").await = Some(journal);
 
-        let stock = match quantity.stock.borrow_mut() {
-            Some(stock) => stock,
-            None => return Err(Status::internal(NO_STOCK_ERR)),
+        let inventory = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let notified = inventory.changes.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+
+                let mut journal = inventory
+                    .locks
+                    .lock(&inventory.journal, "journal", "")
+                    .await;
+                if let Some(journal) = journal.as_mut() {
+                    if let Err(err) = inventory.write_journal(journal).await {
+                        tracing::error!(error = ?err, "failed to write WAL");
+                    }
+                }
+                drop(journal);
+
+                notified.await;
+            }
+        });
+    }
+
+    /// Appends the current state of every SKU changed since the journal was
+    /// last written.
+    async fn write_journal(&self, journal: &mut Journal) -> io::Result<()> {
+        let generation = self.generation.load(AtomicOrdering::SeqCst);
+        let map = self.locks.lock(&self.inventory, "journal", "").await;
+        let skus = match self.changes.since(journal.written) {
+            Some(skus) => skus,
+            None => {
+                // Removals in the forgotten stretch can't be recovered.
+                tracing::warn!("change history overflowed, journaling every item");
+                map.keys().cloned().collect()
+            }
         };
+        let changed: Vec<_> = skus
+            .into_iter()
+            .map(|sku| {
+                let item = map.get(&sku).cloned();
+                (sku, item)
+            })
+            .collect();
+        drop(map);
 
-        if item.sku.is_empty() {
-            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        if changed.is_empty() {
+            journal.written = generation;
+            return Ok(());
         }
-
-        if item.quantity == 0 {
-            return Err(Status::invalid_argument(DUP_QUANT_ERR));
+        for (sku, item) in changed {
+            journal.wal.append(&sku, item)?;
         }
+        journal.wal.sync()?;
+        journal.written = generation;
+        Ok(())
+    }
 
-        stock.quantity = match item.quantity {
-            item if item > stock.quantity => {
-                return Err(Status::invalid_argument(LOW_QUANT_ERR));
+    /// Saves the catalog to `file` every `--snapshot-interval` seconds while
+    /// a journal is kept, emptying the WAL each time.
+    pub fn spawn_snapshots(&self, file: SnapshotFile) {
+        let inventory = self.clone();
+        let interval = Duration::from_secs(self.config.snapshot_interval);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(err) = inventory.save_snapshot(&file).await {
+                    tracing::error!(error = ?err, "failed to save snapshot");
+                }
             }
+        });
+    }
 
-            item => stock.quantity - item,
+    /// Writes out the journal, then saves the catalog at the WAL's sequence
+    /// and empties the WAL, which the snapshot now covers.
+    pub async fn save_snapshot(&self, file: &SnapshotFile) -> io::Result<()> {
+        let mut journal = self.locks.lock(&self.journal, "save_snapshot", "").await;
+        let journal = match journal.as_mut() {
+            Some(journal) => journal,
+            None => return Ok(()),
         };
+        self.write_journal(journal).await?;
 
-        Ok(Response::new(InventoryUpdateResponse {
-            status: "success".into(),
-            price: stock.price,
-            quantity: stock.quantity,
-        }))
+        let map = self.locks.lock(&self.inventory, "save_snapshot", "").await;
+        let snapshot = Snapshot {
+            items: map.values().cloned().collect(),
+            version: journal.wal.sequence(),
+            taken_at: self.clock.now(),
+        };
+        drop(map);
+
+        file.save(&snapshot)?;
+        journal.wal.truncate()?;
+        tracing::info!(version = snapshot.version, "snapshot saved");
+        Ok(())
     }
 
-    async fn increase_quantity(
-        &self,
-        request: tonic::Request<store::QuantityChangeRequest>,
-    ) -> Result<tonic::Response<store::InventoryUpdateResponse>, tonic::Status> {
-        let item = request.into_inner();
-        let mut map = self.inventory.lock().await;
-        let quantity = match map.get_mut(&item.sku) {
-            Some(quantity) => quantity,
-            None => return Err(Status::not_found(NO_ITEM_ERR)),
+    /// Rounds a loaded stock's prices, cost and scheduled prices to the feed
+    /// currency's minor unit, or cents for a currency the server doesn't
+    /// know. Returns how many prices were converted and how many of those
+    /// changed in doing so.
+    fn normalize_prices(&self, sku: &str, stock: &mut ItemStock) -> (u64, u64) {
+        let decimals = pricing::minor_unit_decimals(&self.config.feed.currency).unwrap_or(2);
+        let rounding = self.config.price_rounding;
+        let (mut converted, mut lossy) = (0, 0);
+        let mut convert = |field: &str, price: &mut f32| {
+            let normalized = pricing::round_to_decimals(*price as f64, decimals, rounding) as f32;
+            converted += 1;
+            if normalized != *price {
+                lossy += 1;
+                tracing::warn!(
+                    sku,
+                    field,
+                    from = *price as f64,
+                    to = normalized as f64,
+                    "snapshot price lost precision"
+                );
+                *price = normalized;
+            }
         };
 
-        let stock = match quantity.stock.borrow_mut() {
-            Some(stock) => stock,
-            None => return Err(Status::internal(NO_STOCK_ERR)),
-        };
+        convert("price", &mut stock.price);
+        if let Some(cost) = stock.cost.as_mut() {
+            convert("cost", cost);
+        }
+        for (list, price) in stock.prices.iter_mut() {
+            convert(list, price);
+        }
+        for change in stock.schedule.iter_mut() {
+            convert(&change.id, &mut change.price);
+        }
+        (converted, lossy)
+    }
 
-        if item.sku.is_empty() {
-            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+    /// The checks add makes that a snapshot written by an older or buggy
+    /// server might not pass.
+    fn check_snapshot_item(&self, item: &Item) -> Result<(), Status> {
+        match item.identifier.as_ref() {
+            Some(id) if id.sku.is_empty() => return Err(Status::invalid_argument(EMPTY_SKU_ERR)),
+            Some(_) => {}
+            None => return Err(Status::invalid_argument(NO_ID_ERR)),
         }
 
-        if item.quantity == 0 {
-            return Err(Status::invalid_argument(DUP_QUANT_ERR));
+        match item.stock.as_ref() {
+            Some(stock) => {
+                self.check_price(stock.price)?;
+                check_units(stock)?;
+                check_warehouses(stock)?;
+            }
+            None => return Err(Status::invalid_argument(NO_STOCK_ERR)),
         }
 
-        let item = item.quantity;
-        stock.quantity += item;
+        self.check_tax_category(&item.tax_category)
+    }
 
-        Ok(Response::new(InventoryUpdateResponse {
-            status: "success".into(),
-            price: stock.price,
-            quantity: stock.quantity,
-        }))
+    /// Applies a stock's scheduled prices that are due, auditing each, and
+    /// returns whether any were.
+    fn promote_due(&self, sku: &str, stock: &mut ItemStock, now: u64) -> bool {
+        let promoted = promote_scheduled(stock, now);
+        for change in &promoted {
+            let detail = match change.price_list.as_str() {
+                "" => format!("{} applied, price {}", change.id, change.price),
+                list => format!("{} applied, {} price {}", change.id, list, change.price),
+            };
+            self.audit_with_reason("update_price", sku, detail, change.reason.clone());
+        }
+        !promoted.is_empty()
     }
 
-    async fn update_price(
+    /// Marks the catalog as changed. Callers hold the inventory lock so the
+    /// generation read by get_all always matches the items it returns.
+    fn bump_generation(&self) {
+        self.generation.fetch_add(1, AtomicOrdering::SeqCst);
+        self.changes.notify();
+    }
+
+    fn etag(&self) -> String {
+        self.etag_at(self.generation.load(AtomicOrdering::SeqCst))
+    }
+
+    fn etag_at(&self, generation: u64) -> String {
+        format!("{}-{}", self.started_at, generation)
+    }
+
+    /// The ETag of a get_all response. Besides the generation it covers
+    /// `shape`, the parts of the request that shape the response, and the
+    /// scheduled prices reads already apply that the sweeper hasn't yet.
+    fn get_all_etag(
         &self,
-        request: Request<PriceChangeRequest>,
-    ) -> Result<Response<InventoryUpdateResponse>, Status> {
-        let item = request.into_inner();
+        map: &HashMap<String, Item>,
+        generation: u64,
+        shape: impl Hash,
+    ) -> String {
+        let now = self.clock.now();
+        let due = map
+            .values()
+            .filter_map(|item| item.stock.as_ref())
+            .flat_map(|stock| &stock.schedule)
+            .filter(|scheduled| scheduled.effective_at <= now)
+            .count();
 
-        if item.sku.is_empty() {
-            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        let mut hasher = DefaultHasher::new();
+        (shape, due).hash(&mut hasher);
+        format!("{}-{:x}", self.etag_at(generation), hasher.finish())
+    }
+}
+
+impl StoreInventory {
+    fn is_admin<T>(&self, request: &Request<T>) -> bool {
+        let token = match self.config.admin_token.as_ref() {
+            Some(token) => token,
+            None => return false,
+        };
+
+        match request.metadata().get(ADMIN_TOKEN_HEADER) {
+            Some(value) => value.to_str().map(|v| v == token).unwrap_or(false),
+            None => false,
         }
+    }
 
-        if item.price <= 0.0 {
-            return Err(Status::invalid_argument(BAD_PRICE_ERR));
+    fn check_ready(&self) -> Result<(), Status> {
+        if !self.ready.load(AtomicOrdering::SeqCst) {
+            let delay = Duration::from_millis(self.config.loading_retry_delay);
+            return Err(retry::unavailable(NOT_READY_ERR, delay));
         }
+        Ok(())
+    }
 
-        let mut map = self.inventory.lock().await;
-        let price = match map.get_mut(&item.sku) {
-            Some(price) => price,
-            None => return Err(Status::not_found(NO_ITEM_ERR)),
+    /// Rejects a stream opened faster than its client's configured rate.
+    /// Requests without a known peer address aren't limited.
+    fn check_stream_rate<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let (limit, peer) = match (self.stream_opens.as_ref(), request.remote_addr()) {
+            (Some(limit), Some(peer)) => (limit, peer),
+            _ => return Ok(()),
         };
 
-        let stock = match price.stock.borrow_mut() {
-            Some(stock) => stock,
-            None => return Err(Status::internal(NO_STOCK_ERR)),
-        };
+        limit
+            .check(peer.ip())
+            .map_err(|wait| retry::resource_exhausted(STREAM_RATE_ERR, wait))
+    }
 
-        if stock.price == item.price {
-            return Err(Status::invalid_argument(DUP_PRICE_ERR));
+    fn watch_slot(&self) -> Result<WatchSlot, Status> {
+        self.watchers
+            .acquire()
+            .ok_or_else(|| Status::resource_exhausted(WATCH_LIMIT_ERR))
+    }
+
+    /// Bounds concurrent mutations. Reads never take a permit.
+    async fn mutation_permit(&self) -> Result<Option<OwnedSemaphorePermit>, Status> {
+        self.check_ready()?;
+
+        if self.maintenance.load(AtomicOrdering::SeqCst) {
+            let delay = Duration::from_millis(self.config.maintenance_retry_delay);
+            return Err(retry::unavailable(MAINTENANCE_ERR, delay));
         }
 
-        stock.price = item.price;
+        let semaphore = match self.mutations.as_ref() {
+            Some(semaphore) => semaphore.clone(),
+            None => return Ok(None),
+        };
 
-        Ok(Response::new(InventoryUpdateResponse {
-            status: "success".into(),
-            price: stock.price,
-            quantity: stock.quantity,
-        }))
-    }
+        let permit = match self.config.mutation_overload {
+            OverloadPolicy::Shed => semaphore.try_acquire_owned().ok(),
+            OverloadPolicy::Queue => {
+                let timeout = std::time::Duration::from_millis(self.config.mutation_queue_timeout);
+                match tokio::time::timeout(timeout, semaphore.acquire_owned()).await {
+                    Ok(permit) => permit.ok(),
+                    Err(_) => None,
+                }
+            }
+        };
 
-    type WatchStream = Pin<Box<dyn Stream<Item = Result<Item, Status>> + Send>>;
+        match permit {
+            Some(permit) => Ok(Some(permit)),
+            None => Err(Status::resource_exhausted(OVERLOADED_ERR)),
+        }
+    }
 
-    async fn watch(
+    /// Validates and stores an item, handling an existing SKU as `policy`
+    /// says. A replaced item keeps its creation time. Callers hold the
+    /// mutation permit.
+    async fn put_item(
         &self,
-        request: Request<ItemIdentifier>,
-    ) -> Result<Response<Self::WatchStream>, Status> {
-        let id = request.into_inner();
-        let mut item = self.get(Request::new(id.clone())).await?.into_inner();
+        request: Request<Item>,
+        policy: DuplicateAddPolicy,
+        rpc: &'static str,
+    ) -> Result<Put, Status> {
+        if let Some(stock) = request.get_ref().stock.as_ref() {
+            self.check_max_quantity(&request, stock.quantity)?;
+        }
 
-        let (tx, rx) = mpsc::unbounded_channel();
+        let mut item = request.into_inner();
 
-        let inventory = self.inventory.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let sku = match item.identifier.as_ref() {
+            Some(id) if id.sku.is_empty() => return Err(Status::invalid_argument(EMPTY_SKU_ERR)),
+            Some(id) => id.sku.to_owned(),
+            None => return Err(Status::invalid_argument(NO_ID_ERR)),
+        };
 
-                let map = inventory.lock().await;
-                let item_refresh = match map.get(&id.sku) {
-                    Some(item) => item,
-                    None => {
-                        if let Err(err) = tx.send(Err(Status::not_found(NO_ITEM_ERR))) {
-                            println!("ERROR: failed to update stream client: {:?}", err);
-                        }
-                        return;
-                    }
-                };
+        match item.stock.as_ref() {
+            Some(stock) => {
+                self.check_price(stock.price)?;
+                check_units(stock)?;
+                check_warehouses(stock)?;
+                if stock.quantity == 0
+                    && item.bundle.is_empty()
+                    && self.config.zero_initial_quantity == ZeroStockPolicy::Reject
+                {
+                    return Err(Status::invalid_argument(ZERO_INITIAL_ERR));
+                }
+            }
+            None => return Err(Status::invalid_argument(NO_STOCK_ERR)),
+        };
 
-                if item_refresh != &item {
-                    if let Err(err) = tx.send(Ok(item_refresh.clone())) {
-                        println!("ERROR: failed to update stream client: {:?}", err);
-                        return;
-                    }
+        // Stored timestamps always come from the server's clock, but one far
+        // ahead of it points at a badly skewed client.
+        let latest = self
+            .clock
+            .now()
+            .saturating_add(self.config.clock_skew_tolerance);
+        if let Some((field, _)) = [
+            ("created_at", item.created_at),
+            ("updated_at", item.updated_at),
+            ("deleted_at", item.deleted_at),
+        ]
+        .iter()
+        .find(|(_, at)| *at > latest)
+        {
+            let message = format!("{}: {}", field, FUTURE_TIMESTAMP_ERR);
+            return Err(Status::invalid_argument(message));
+        }
+
+        if self.config.strict_input {
+            check_strict(&item)?;
+        }
+
+        self.check_tax_category(&item.tax_category)?;
+
+        let mut seen = HashSet::new();
+        item.tags.retain(|tag| seen.insert(tag.clone()));
+        item.availability = Availability::Unset.into();
+        if let Some(stock) = item.stock.as_mut() {
+            stock.schedule.clear();
+            stock.sale_quantity = 0;
+        }
+
+        let mut map = self.locks.lock(&self.inventory, rpc, &sku).await;
+        let existing = live(&map, &sku);
+        if let Some(existing) = existing {
+            match policy {
+                DuplicateAddPolicy::Reject => return Err(Status::already_exists(DUP_ITEM_ERR)),
+                DuplicateAddPolicy::Idempotent if same_payload(existing, &item) => {
+                    return Ok(Put::Unchanged);
                 }
+                DuplicateAddPolicy::Idempotent => {
+                    return Err(Status::already_exists(DUP_ITEM_ERR));
+                }
+                DuplicateAddPolicy::LastWins => {}
+            }
+            check_lifecycle(existing, Operation::Edit)?;
+        }
+
+        check_bundle(&map, &sku, &mut item.bundle)?;
+        check_parent(&map, &sku, &item.parent_sku)?;
+
+        let mut indexes = self.locks.lock(&self.indexes, rpc, &sku).await;
+        if !indexes.name_available(&map, &sku, &item) {
+            return Err(Status::already_exists(DUP_NAME_ERR));
+        }
 
-                item = item_refresh.clone()
+        let now = self.clock.now();
+        let put = match existing {
+            Some(existing) => {
+                indexes.remove(&sku, existing);
+                item.state = existing.state;
+                item.created_at = existing.created_at;
+                Put::Replaced
             }
-        });
+            None => {
+                item.created_at = now;
+                Put::Created
+            }
+        };
 
-        let stream = UnboundedReceiverStream::new(rx);
+        item.updated_at = now;
+        indexes.insert(&sku, &mut item);
+        let stock = item.stock.clone().unwrap_or_default();
+        self.audit(
+            rpc,
+            &sku,
+            format!("price {} quantity {}", stock.price, stock.quantity),
+        );
+        map.insert(sku, item);
+        self.bump_generation();
+
+        Ok(put)
+    }
+
+    /// Sells a bundle by taking each component's share out of its stock,
+    /// changing nothing unless every component has enough available.
+    /// Components kept per warehouse are drawn from in allocation order.
+    async fn decrease_bundle(
+        &self,
+        map: &mut HashMap<String, Item>,
+        bundle: &Item,
+        request: &QuantityChangeRequest,
+        region: Option<&str>,
+    ) -> Result<Response<InventoryUpdateResponse>, Status> {
+        if !request.warehouse.is_empty() {
+            return Err(Status::invalid_argument(UNSPLIT_STOCK_ERR));
+        }
+        let stock = bundle.stock.clone().unwrap_or_default();
+        let count = requested_quantity(
+            &stock,
+            request.quantity,
+            request.measured_quantity,
+            &request.unit,
+        )?;
+        if count == 0 {
+            return self.zero_quantity_change(&stock);
+        }
+
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "decrease_quantity", &request.sku)
+            .await;
+        reservations.expire(self.clock.now());
+        if count > bundle_quantity(map, &bundle.bundle, self.holding(&reservations)) {
+            return Err(Status::invalid_argument(LOW_QUANT_ERR));
+        }
+
+        for line in &bundle.bundle {
+            let component = live_mut(map, &line.sku).unwrap();
+            component.updated_at = self.clock.now();
+            let component = self.stock_mut(component)?;
+            self.draw(component, line.quantity * count, "", region)?;
+            self.audit(
+                "decrease_quantity",
+                &line.sku,
+                format!("by {} for bundle {}", line.quantity * count, request.sku),
+            );
+        }
+        self.audit("decrease_quantity", &request.sku, format!("by {}", count));
+        self.bump_generation();
+
+        Ok(Response::new(InventoryUpdateResponse {
+            status: "success".into(),
+            price: stock.price,
+            quantity: bundle_quantity(map, &bundle.bundle, None),
+            scheduled_id: String::new(),
+            drawn: Vec::new(),
+        }))
+    }
+
+    /// Reserves a bundle by holding each component's share under one claim,
+    /// which is released or committed with `release_claim`/`commit_claim`.
+    async fn reserve_bundle(
+        &self,
+        map: &HashMap<String, Item>,
+        bundle: &Item,
+        request: &ReserveRequest,
+    ) -> Result<Response<ReserveResponse>, Status> {
+        let stock = bundle.stock.clone().unwrap_or_default();
+        let count = requested_quantity(
+            &stock,
+            request.quantity,
+            request.measured_quantity,
+            &request.unit,
+        )?;
+        if count == 0 {
+            return Err(Status::invalid_argument(ZERO_QUANT_ERR));
+        }
+
+        let now = self.clock.now();
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "reserve", &request.sku)
+            .await;
+        reservations.expire(now);
+
+        if let Some(max) = self.config.max_reservations_per_sku {
+            if let Some(line) = bundle
+                .bundle
+                .iter()
+                .find(|line| reservations.count(&line.sku) >= max)
+            {
+                let message = format!("{}: {}", line.sku, MAX_RESERVATIONS_ERR);
+                return Err(Status::resource_exhausted(message));
+            }
+        }
+
+        let available = bundle_quantity(map, &bundle.bundle, self.holding(&reservations));
+        if count > available {
+            return Err(Status::failed_precondition(INSUFFICIENT_STOCK_ERR));
+        }
+
+        let expires_at = self.reservation_expiry(request.ttl, now)?;
+        let held = bundle
+            .bundle
+            .iter()
+            .map(|line| Reservation {
+                sku: line.sku.clone(),
+                quantity: line.quantity * count,
+                expires_at,
+            })
+            .collect();
+
+        let reservation_id = reservations.insert_claim(held);
+        Ok(Response::new(ReserveResponse {
+            reservation_id,
+            available: bundle_quantity(map, &bundle.bundle, Some(&reservations)),
+        }))
+    }
+
+    fn zero_quantity_change(
+        &self,
+        stock: &ItemStock,
+    ) -> Result<Response<InventoryUpdateResponse>, Status> {
+        match self.config.zero_quantity_policy {
+            ZeroQuantityPolicy::Error => Err(Status::invalid_argument(ZERO_QUANT_ERR)),
+            ZeroQuantityPolicy::Noop => Ok(Response::new(InventoryUpdateResponse {
+                status: "success".into(),
+                price: stock.price,
+                quantity: stock.quantity,
+                scheduled_id: String::new(),
+                drawn: Vec::new(),
+            })),
+        }
+    }
+
+    /// Applies the missing stock policy to an item being mutated, inserting
+    /// zero-quantity stock when lenient.
+    fn stock_mut<'a>(&self, item: &'a mut Item) -> Result<&'a mut ItemStock, Status> {
+        match self.config.missing_stock_policy {
+            StockPolicy::Strict => item
+                .stock
+                .as_mut()
+                .ok_or_else(|| Status::internal(NO_STOCK_ERR)),
+            StockPolicy::Lenient => Ok(item.stock.get_or_insert_with(ItemStock::default)),
+        }
+    }
+
+    /// Takes `quantity` off `stock`: from `warehouse` when one is named,
+    /// otherwise across its warehouses in the order the allocation strategy
+    /// gives, using `region` as the hint for `nearest`. The caller has
+    /// checked there's enough in all; a named warehouse is checked here.
+    fn draw(
+        &self,
+        stock: &mut ItemStock,
+        quantity: u64,
+        warehouse: &str,
+        region: Option<&str>,
+    ) -> Result<Vec<WarehouseDraw>, Status> {
+        let order = if !warehouse.is_empty() {
+            if !warehouses::is_split(stock) {
+                return Err(Status::invalid_argument(UNSPLIT_STOCK_ERR));
+            }
+            match stock.warehouses.get(warehouse) {
+                Some(&held) if held >= quantity => vec![warehouse.to_owned()],
+                Some(_) => return Err(Status::invalid_argument(LOW_QUANT_ERR)),
+                None => return Err(Status::not_found(UNKNOWN_WAREHOUSE_ERR)),
+            }
+        } else if warehouses::is_split(stock) {
+            let turn = self.allocations.fetch_add(1, AtomicOrdering::SeqCst);
+            warehouses::draw_order(
+                stock,
+                self.config.allocation_strategy,
+                &self.config.warehouse_regions,
+                region,
+                turn,
+            )
+        } else {
+            Vec::new()
+        };
+        Ok(warehouses::take(stock, quantity, &order))
+    }
+
+    /// Sets an item's quantity to a stock count, returning the correction
+    /// made, or `None` when the count matches the stock on hand.
+    fn apply_count<T>(
+        &self,
+        map: &mut HashMap<String, Item>,
+        count: &StockCount,
+        request: &Request<T>,
+    ) -> Result<Option<StockCorrection>, Status> {
+        if count.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let item = match live_mut(map, &count.sku) {
+            Some(item) => item,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+        if !item.bundle.is_empty() {
+            return Err(Status::invalid_argument(BUNDLE_QUANT_ERR));
+        }
+        check_lifecycle(item, Operation::Edit)?;
+        self.check_max_quantity(request, count.counted)?;
+
+        let stock = self.stock_mut(item)?;
+        if warehouses::is_split(stock) {
+            return Err(Status::invalid_argument(WAREHOUSE_REQUIRED_ERR));
+        }
+        let previous = stock.quantity;
+        if count.counted == previous {
+            return Ok(None);
+        }
+
+        stock.quantity = count.counted;
+        item.updated_at = self.clock.now();
+        let variance = count.counted as i64 - previous as i64;
+        self.audit(
+            "stream_stocktake",
+            &count.sku,
+            format!("counted {} variance {:+}", count.counted, variance),
+        );
+
+        Ok(Some(StockCorrection {
+            sku: count.sku.clone(),
+            previous,
+            counted: count.counted,
+            variance,
+        }))
+    }
+
+    /// Reprices `skus` for adjust_price_percent, adding their lines to
+    /// `response`. Everything is priced before the map is touched so a
+    /// rejected line leaves these SKUs unchanged. SKUs that have gone or can
+    /// no longer be edited since they were picked are skipped.
+    fn reprice(
+        &self,
+        map: &mut HashMap<String, Item>,
+        skus: &[String],
+        request: &Request<PriceAdjustRequest>,
+        response: &mut PriceAdjustResponse,
+    ) -> Result<(), Status> {
+        let adjust = request.get_ref();
+        let mut lines = Vec::with_capacity(skus.len());
+        let mut clamped = Vec::new();
+        for sku in skus {
+            let stock = match live(map, sku) {
+                Some(item) if lifecycle::permits(item.state(), Operation::Edit) => {
+                    match item.stock.as_ref() {
+                        Some(stock) => stock,
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            let mut new_price =
+                pricing::apply_percent(stock.price, adjust.percent, self.config.price_rounding);
+
+            let floor = match (adjust.floor, stock.cost.filter(|_| adjust.floor_at_cost)) {
+                (Some(floor), Some(cost)) => Some(floor.max(cost)),
+                (floor, cost) => floor.or(cost),
+            };
+            if let Some(floor) = floor.filter(|&floor| new_price < floor) {
+                new_price = floor;
+                clamped.push(sku.clone());
+            }
+            // A floor set below cost doesn't excuse pricing under it.
+            self.check_cost_floor(request, stock.cost, new_price)?;
+
+            lines.push(RepriceLine {
+                sku: sku.clone(),
+                old_price: stock.price,
+                new_price,
+            });
+        }
+
+        let now = self.clock.now();
+        for line in &lines {
+            if let Some(item) = map.get_mut(&line.sku) {
+                item.updated_at = now;
+                if let Some(stock) = item.stock.as_mut() {
+                    stock.price = line.new_price;
+                }
+            }
+            self.audit(
+                "adjust_price_percent",
+                &line.sku,
+                format!("{} to {}", line.old_price, line.new_price),
+            );
+        }
+        if !lines.is_empty() {
+            self.bump_generation();
+        }
+
+        response.lines.extend(lines);
+        response.clamped.extend(clamped);
+        Ok(())
+    }
+
+    /// How many items of a `len`-item bulk write to apply per hold of the
+    /// inventory lock: all of them unless chunking is configured.
+    fn chunk_size(&self, len: usize) -> usize {
+        self.config.batch_chunk_size.unwrap_or(len).max(1)
+    }
+
+    /// Applies the missing stock policy to an item being returned to a client.
+    fn with_stock(&self, item: &Item) -> Result<Item, Status> {
+        let mut item = item.clone();
+        self.stock_mut(&mut item)?;
+        Ok(item)
+    }
+
+    /// Signs a read response with the configured key, if there is one.
+    fn signed<M: Message>(&self, mut response: Response<M>) -> Response<M> {
+        let key = match self.config.response_signing_key.as_ref() {
+            Some(key) => key,
+            None => return response,
+        };
+
+        let mac = signing::hmac_sha256(key.as_bytes(), &response.get_ref().encode_to_vec());
+        let signature = format!("sha256={}", signing::hex(&mac));
+        if let Ok(value) = signature.parse() {
+            response.metadata_mut().insert(SIGNATURE_HEADER, value);
+        }
+        response
+    }
+
+    /// Whether an admin asked for tombstones with `x-include-deleted` metadata.
+    fn include_deleted<T>(&self, request: &Request<T>) -> bool {
+        request.metadata().get(INCLUDE_DELETED_HEADER).is_some() && self.is_admin(request)
+    }
+
+    /// Whether an admin asked for margins with `x-include-margin` metadata.
+    fn include_margin<T>(&self, request: &Request<T>) -> bool {
+        request.metadata().get(INCLUDE_MARGIN_HEADER).is_some() && self.is_admin(request)
+    }
+
+    fn rollup_variants<T>(&self, request: &Request<T>) -> bool {
+        request.metadata().get(ROLLUP_VARIANTS_HEADER).is_some()
+    }
+
+    fn include_availability<T>(&self, request: &Request<T>) -> bool {
+        request
+            .metadata()
+            .get(INCLUDE_AVAILABILITY_HEADER)
+            .is_some()
+    }
+
+    /// Sets a read item's availability from its stock less what's reserved.
+    /// A bundle's reserved components count against it.
+    fn set_availability(
+        &self,
+        map: &HashMap<String, Item>,
+        item: &mut Item,
+        reservations: &Reservations,
+    ) {
+        let sku = item_sku(item).to_owned();
+        let stock = match item.stock.as_ref() {
+            Some(stock) => stock,
+            None => return,
+        };
+        let available = if item.bundle.is_empty() {
+            stock.quantity.saturating_sub(reservations.reserved(&sku))
+        } else {
+            bundle_quantity(map, &item.bundle, Some(reservations))
+        };
+        item.availability = availability(stock, available).into();
+    }
+
+    /// The item as a read returns it, with its margin when requested and its
+    /// cost is known. A bundle's quantity is how many its components make.
+    fn read_item(
+        &self,
+        map: &HashMap<String, Item>,
+        item: &Item,
+        include_margin: bool,
+    ) -> Result<Item, Status> {
+        let mut item = self.with_stock(item)?;
+        item.margin = None;
+        promote_scheduled(item.stock.as_mut().unwrap(), self.clock.now());
+
+        if !item.bundle.is_empty() {
+            item.stock.as_mut().unwrap().quantity = bundle_quantity(map, &item.bundle, None);
+        }
+
+        let stock = item.stock.as_mut().unwrap();
+        if !stock.sale_unit.is_empty() && stock.sale_unit != stock.stock_unit {
+            stock.sale_quantity = stock.quantity.saturating_mul(stock.sale_units_per);
+        }
+
+        let stock = item.stock.as_ref().unwrap();
+        if let (true, Some(cost)) = (include_margin, stock.cost) {
+            let amount = stock.price as f64 - cost as f64;
+            // A free item has no meaningful margin percentage.
+            let percent = if stock.price == 0.0 {
+                0.0
+            } else {
+                amount / stock.price as f64 * 100.0
+            };
+            item.margin = Some(Margin {
+                amount: pricing::round_to_cents(amount, Rounding::Nearest) as f32,
+                percent: pricing::round_to_cents(percent, Rounding::Nearest) as f32,
+            });
+        }
+
+        Ok(item)
+    }
+
+    /// Bulk requests may carry at most the configured batch size.
+    fn check_batch(&self, len: usize) -> Result<(), Status> {
+        if len <= self.config.max_batch_size {
+            return Ok(());
+        }
+        let message = format!("{} {}", BATCH_SIZE_ERR, self.config.max_batch_size);
+        Err(Status::invalid_argument(message))
+    }
+
+    /// When a reservation asked to last `ttl` seconds from `now` expires, if
+    /// ever, under the maximum reservation TTL.
+    fn reservation_expiry(&self, ttl: u64, now: u64) -> Result<Option<u64>, Status> {
+        let ttl = match self.config.max_reservation_ttl {
+            Some(max) if ttl > max && self.config.reservation_ttl_policy == TtlPolicy::Reject => {
+                let message = format!("{} {} seconds", TTL_TOO_LONG_ERR, max);
+                return Err(Status::invalid_argument(message));
+            }
+            Some(max) if ttl == 0 || ttl > max => max,
+            _ => ttl,
+        };
+        Ok((ttl > 0).then(|| now.saturating_add(ttl)))
+    }
+
+    /// The reservations that hold stock back from decreases and further
+    /// reservations: all of them, or none when they're soft.
+    fn holding<'a>(&self, reservations: &'a Reservations) -> Option<&'a Reservations> {
+        match self.config.reservation_mode {
+            ReservationMode::Hard => Some(reservations),
+            ReservationMode::Soft => None,
+        }
+    }
+
+    /// How much of `sku` its reservations hold back.
+    fn held_back(&self, reservations: &Reservations, sku: &str) -> u64 {
+        self.holding(reservations)
+            .map_or(0, |held| held.reserved(sku))
+    }
+
+    /// An empty tax category leaves the item uncategorized; anything else
+    /// must be one of the configured codes.
+    fn check_tax_category(&self, code: &str) -> Result<(), Status> {
+        if code.is_empty() || self.config.tax_categories.iter().any(|known| known == code) {
+            return Ok(());
+        }
+        let message = format!("{}: {}", code, UNKNOWN_TAX_CATEGORY_ERR);
+        Err(Status::invalid_argument(message))
+    }
+
+    /// Item prices must be positive, or zero when free items are allowed.
+    fn check_price(&self, price: f32) -> Result<(), Status> {
+        if price.is_nan() || price.is_infinite() {
+            return Err(Status::invalid_argument(BAD_PRICE_ERR));
+        }
+
+        if price < 0.0 {
+            return Err(Status::invalid_argument(NEGATIVE_PRICE_ERR));
+        }
+
+        if price == 0.0 && self.config.zero_price_policy == ZeroPricePolicy::Reject {
+            return Err(Status::invalid_argument(ZERO_PRICE_ERR));
+        }
+
+        Ok(())
+    }
+
+    /// Fills in what add checks of `field` under the current configuration.
+    fn describe_rules(&self, field: &mut FieldDescription) {
+        let config = &self.config;
+        let mut rule = |constraint: String| field.constraints.push(constraint);
+        match field.name.as_str() {
+            "identifier" | "stock" => field.required = true,
+            "identifier.sku" => {
+                field.required = true;
+                rule("must not be empty".into());
+            }
+            "stock.price" => {
+                rule("must be finite".into());
+                rule(match config.zero_price_policy {
+                    ZeroPricePolicy::Reject => "must be greater than 0".into(),
+                    ZeroPricePolicy::Allow => "must not be negative".into(),
+                });
+            }
+            "stock.quantity" => {
+                if let Some(max) = config.max_quantity {
+                    rule(format!("must be at most {}", max));
+                }
+                if config.zero_initial_quantity == ZeroStockPolicy::Reject {
+                    rule("must be greater than 0 unless the item is a bundle".into());
+                }
+                if config.strict_input {
+                    rule("must be 0 for a bundle".into());
+                }
+            }
+            "stock.sale_units_per" => {
+                rule("must be greater than 0 when sale_unit differs from stock_unit".into());
+            }
+            "stock.unit" => rule("must be EACH when sale_unit differs from stock_unit".into()),
+            "tax_category" => rule(format!(
+                "must be empty or one of: {}",
+                config.tax_categories.join(", ")
+            )),
+            "bundle.sku" => rule("must name another existing item that isn't a bundle".into()),
+            "bundle.quantity" => rule("must be greater than 0".into()),
+            "stock.warehouses" => rule("must add up to stock.quantity when given".into()),
+            "parent_sku" => rule(
+                "must name another existing item that isn't a variant, on an item without \
+                 variants"
+                    .into(),
+            ),
+            "information.name" => match config.unique_names {
+                NamePolicy::Off => {}
+                NamePolicy::Exact => rule("must be unique".into()),
+                NamePolicy::Normalized => {
+                    rule("must be unique, ignoring case and surrounding whitespace".into());
+                }
+            },
+            _ => {}
+        }
+
+        if matches!(
+            field.name.as_str(),
+            "created_at" | "updated_at" | "deleted_at"
+        ) {
+            rule(format!(
+                "must not be more than {} seconds ahead of the server clock",
+                config.clock_skew_tolerance
+            ));
+        }
+
+        if server_fields(&Item::default())
+            .iter()
+            .any(|(name, _)| *name == field.name)
+        {
+            field.server_set = true;
+            if config.strict_input {
+                field.constraints.push(SERVER_FIELD_ERR.into());
+            }
+        }
+    }
+
+    fn check_max_quantity<T>(&self, request: &Request<T>, quantity: u64) -> Result<(), Status> {
+        let max = match self.config.max_quantity {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+
+        let overridden = request.metadata().get(OVERRIDE_MAX_HEADER).is_some();
+        if quantity > max && !(overridden && self.is_admin(request)) {
+            return Err(Status::out_of_range(MAX_QUANT_ERR));
+        }
+
+        Ok(())
+    }
+
+    fn check_cost_floor<T>(
+        &self,
+        request: &Request<T>,
+        cost: Option<f32>,
+        price: f32,
+    ) -> Result<(), Status> {
+        let cost = match cost {
+            Some(cost) if price < cost => cost,
+            _ => return Ok(()),
+        };
+
+        let message = format!(
+            "{}: cost {} attempted price {}",
+            BELOW_COST_ERR, cost, price
+        );
+        match self.config.below_cost_policy {
+            CostFloorPolicy::Allow => Ok(()),
+            CostFloorPolicy::Warn => {
+                tracing::warn!(cost, price, "{}", BELOW_COST_ERR);
+                Ok(())
+            }
+            CostFloorPolicy::Reject => {
+                let overridden = request.metadata().get(ALLOW_BELOW_COST_HEADER).is_some();
+                if overridden && self.is_admin(request) {
+                    return Ok(());
+                }
+                Err(Status::failed_precondition(message))
+            }
+        }
+    }
+}
+
+impl StoreInventory {
+    async fn handle_session_command(
+        &self,
+        message: SessionRequest,
+        watched: &mut HashMap<String, Item>,
+    ) -> SessionResponse {
+        let outcome = match message.command {
+            Some(Command::Get(id)) => self
+                .get(Request::new(id))
+                .await
+                .map(|response| Outcome::Item(response.into_inner())),
+            Some(Command::Decrease(change)) => self
+                .decrease_quantity(Request::new(change))
+                .await
+                .map(|response| Outcome::Update(response.into_inner())),
+            Some(Command::Subscribe(id)) => self.get(Request::new(id)).await.map(|response| {
+                let item = response.into_inner();
+                if let Some(id) = item.identifier.as_ref() {
+                    watched.insert(id.sku.clone(), item.clone());
+                }
+                Outcome::Item(item)
+            }),
+            None => Err(Status::invalid_argument(NO_COMMAND_ERR)),
+        };
+
+        SessionResponse {
+            id: message.id,
+            outcome: Some(outcome.unwrap_or_else(|status| Outcome::Error(session_error(&status)))),
+        }
+    }
+
+    async fn refresh_session_watches(
+        &self,
+        watched: &mut HashMap<String, Item>,
+    ) -> Vec<SessionResponse> {
+        let map = self.locks.lock(&self.inventory, "interactive", "").await;
+
+        diff_watched(&map, watched)
+            .into_iter()
+            .map(|(_, current)| {
+                let outcome = match current {
+                    Some(item) => Outcome::Changed(item),
+                    None => Outcome::Error(session_error(&Status::not_found(NO_ITEM_ERR))),
+                };
+                SessionResponse {
+                    id: 0,
+                    outcome: Some(outcome),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Compares watched items against the map, updating `watched` in place.
+/// Returns each changed item, or `None` for an item that no longer exists,
+/// which is also dropped from `watched`.
+fn diff_watched(
+    map: &HashMap<String, Item>,
+    watched: &mut HashMap<String, Item>,
+) -> Vec<(String, Option<Item>)> {
+    let mut changes = Vec::new();
+
+    watched.retain(|sku, last| match live(map, sku) {
+        Some(current) => {
+            if current != last {
+                *last = current.clone();
+                changes.push((sku.clone(), Some(current.clone())));
+            }
+            true
+        }
+        None => {
+            changes.push((sku.clone(), None));
+            false
+        }
+    });
+
+    changes
+}
+
+/// Runs a watch stream's task. If it panics, the panic is logged with the
+/// SKU and the stream ends with an internal error rather than going quiet.
+fn spawn_watch<T: Send + 'static>(
+    sku: String,
+    tx: mpsc::UnboundedSender<Result<T, Status>>,
+    task: impl Future<Output = ()> + Send + 'static,
+) {
+    let task = tokio::spawn(task);
+    tokio::spawn(async move {
+        let panic = match task.await {
+            Err(err) if err.is_panic() => err.into_panic(),
+            _ => return,
+        };
+        let message = panic
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        tracing::error!(sku = sku.as_str(), panic = message, "watch task panicked");
+        let _ = tx.send(Err(Status::internal(WATCH_PANIC_ERR)));
+    });
+}
+
+impl StoreInventory {
+    /// Unsubscribes a watch_many stream from SKUs, then subscribes it to
+    /// others, remembering each as it is now so only later changes are
+    /// reported. The watched set is capped at the batch size.
+    async fn update_watched(
+        &self,
+        watched: &mut HashMap<String, Item>,
+        request: WatchManyRequest,
+    ) -> Result<(), Status> {
+        for sku in &request.unsubscribe {
+            watched.remove(sku);
+        }
+
+        let added: HashSet<&String> = request
+            .skus
+            .iter()
+            .filter(|sku| !watched.contains_key(*sku))
+            .collect();
+        self.check_batch(watched.len() + added.len())?;
+
+        for sku in request.skus {
+            if watched.contains_key(&sku) {
+                continue;
+            }
+            let item = self
+                .get(Request::new(ItemIdentifier { sku: sku.clone() }))
+                .await?;
+            watched.insert(sku, item.into_inner());
+        }
+        Ok(())
+    }
+
+    /// Applies `change` to each listed item's tags, reporting per SKU and
+    /// auditing each item it modified under `rpc`. `change` returns whether
+    /// it modified the tags. The inventory lock is released between chunks
+    /// when `--batch-chunk-size` is set.
+    async fn retag_items(
+        &self,
+        rpc: &'static str,
+        request: TagRequest,
+        change: impl Fn(&mut Vec<String>, &str) -> bool,
+    ) -> Result<BulkResponse, Status> {
+        if request.tag.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_TAG_ERR));
+        }
+
+        self.check_batch(request.skus.len())?;
+
+        let total = request.skus.len();
+        let size = self.chunk_size(total);
+        let mut map = self.locks.lock(&self.inventory, rpc, "").await;
+        let mut results = Vec::with_capacity(total);
+        for sku in request.skus {
+            if !results.is_empty() && results.len() % size == 0 {
+                chunk_applied(rpc, results.len(), total);
+                drop(map);
+                tokio::task::yield_now().await;
+                map = self.locks.lock(&self.inventory, rpc, "").await;
+            }
+
+            let result = match live_mut(&mut map, &sku) {
+                Some(item) => {
+                    if change(&mut item.tags, &request.tag) {
+                        item.updated_at = self.clock.now();
+                        self.audit(rpc, &sku, request.tag.clone());
+                        self.bump_generation();
+                    }
+                    SkuResult {
+                        sku,
+                        success: true,
+                        error: String::new(),
+                    }
+                }
+                None => SkuResult {
+                    sku,
+                    success: false,
+                    error: NO_ITEM_ERR.into(),
+                },
+            };
+            results.push(result);
+        }
+
+        Ok(BulkResponse { results })
+    }
+}
+
+/// Logs how far a chunked bulk write has got.
+fn chunk_applied(rpc: &str, done: usize, total: usize) {
+    tracing::info!(rpc, done, total, "bulk write chunk applied");
+}
+
+/// Notes on a failure partway through a chunked bulk write how many items
+/// it got through before it.
+fn chunk_failed(status: Status, done: usize, total: usize) -> Status {
+    if done == 0 {
+        return status;
+    }
+    let message = format!(
+        "{} ({} of {} items were processed first)",
+        status.message(),
+        done,
+        total
+    );
+    Status::new(status.code(), message)
+}
+
+fn session_error(status: &Status) -> SessionError {
+    SessionError {
+        code: status.code() as i32,
+        message: status.message().into(),
+    }
+}
+
+/// Orders items by `price * quantity`, preferring the lower SKU on ties.
+struct ByValue<'a> {
+    cents: i128,
+    item: &'a Item,
+}
+
+impl<'a> ByValue<'a> {
+    fn new(item: &'a Item) -> Self {
+        let cents = item
+            .stock
+            .as_ref()
+            .map(|stock| pricing::line_cents(stock.price, stock.quantity))
+            .unwrap_or_default();
+        ByValue { cents, item }
+    }
+}
+
+impl Ord for ByValue<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cents
+            .cmp(&other.cents)
+            .then_with(|| item_sku(other.item).cmp(item_sku(self.item)))
+    }
+}
+
+impl PartialOrd for ByValue<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ByValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ByValue<'_> {}
+
+fn live<'a>(map: &'a HashMap<String, Item>, sku: &str) -> Option<&'a Item> {
+    map.get(sku).filter(|item| !item.deleted)
+}
+
+fn live_mut<'a>(map: &'a mut HashMap<String, Item>, sku: &str) -> Option<&'a mut Item> {
+    map.get_mut(sku).filter(|item| !item.deleted)
+}
+
+fn live_items(map: &HashMap<String, Item>) -> impl Iterator<Item = &Item> {
+    map.values().filter(|item| !item.deleted)
+}
+
+/// Whether the item has any or all of the `wanted` tags.
+fn has_tags(item: &Item, wanted: &HashSet<&str>, mode: TagMatch) -> bool {
+    let found = item
+        .tags
+        .iter()
+        .filter(|tag| wanted.contains(tag.as_str()))
+        .count();
+    match mode {
+        TagMatch::Any => found > 0,
+        TagMatch::All => found == wanted.len(),
+    }
+}
+
+/// Checks an export filter and returns whether an item passes it. Unset
+/// parts pass everything, and a price range includes both ends.
+fn export_filter(filter: &ExportFilter) -> Result<impl Fn(&Item) -> bool + '_, Status> {
+    let tags = filter.tags.as_ref();
+    if tags.is_some_and(|tags| tags.tags.iter().any(String::is_empty)) {
+        return Err(Status::invalid_argument(EMPTY_TAG_ERR));
+    }
+
+    if let (Some(min), Some(max)) = (filter.min_price, filter.max_price) {
+        if min > max {
+            return Err(Status::invalid_argument(INVERTED_RANGE_ERR));
+        }
+    }
+
+    let wanted: HashSet<&str> = tags
+        .map(|tags| tags.tags.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    let mode = tags.map(TagsRequest::mode).unwrap_or_default();
+    Ok(move |item: &Item| {
+        let price = item.stock.as_ref().map_or(0.0, |stock| stock.price);
+        (wanted.is_empty() || has_tags(item, &wanted, mode))
+            && filter.min_price.is_none_or(|min| price >= min)
+            && filter.max_price.is_none_or(|max| price <= max)
+            && (filter.category.is_empty() || item.category == filter.category)
+    })
+}
+
+/// Fails when the item's lifecycle state forbids `operation`.
+fn check_lifecycle(item: &Item, operation: Operation) -> Result<(), Status> {
+    let state = item.state();
+    if lifecycle::permits(state, operation) {
+        return Ok(());
+    }
+    Err(Status::failed_precondition(format!(
+        "{} {}",
+        STATE_FORBIDS_ERR,
+        lifecycle::name(state)
+    )))
+}
+
+/// Sums `price * quantity` over live items. Each line is rounded to whole
+/// cents and the lines are summed as integers, so the total is exact to the
+/// cent whatever order the items are visited in.
+fn total_value(map: &HashMap<String, Item>) -> f64 {
+    let cents = live_items(map)
+        .filter_map(|item| item.stock.as_ref())
+        .map(|stock| pricing::line_cents(stock.price, stock.quantity))
+        .sum();
+    pricing::cents_to_amount(cents)
+}
+
+/// Derives a storefront status from quantity, reorder point and backorder
+/// flag. An item at or below its reorder point is low on stock; a reorder
+/// point of zero never reports low stock.
+fn stock_status(stock: &ItemStock) -> &'static str {
+    match availability(stock, stock.quantity) {
+        Availability::Backordered => BACKORDER_STATUS,
+        Availability::OutOfStock => OUT_OF_STOCK_STATUS,
+        Availability::LowStock => LOW_STOCK_STATUS,
+        Availability::InStock | Availability::Unset => IN_STOCK_STATUS,
+    }
+}
+
+/// Where an item stands given the stock `available` to sell:
+/// - backordered: none available and the item takes backorders
+/// - out_of_stock: none available otherwise
+/// - low_stock: some available, at or below the reorder point
+/// - in_stock: more available than the reorder point
+fn availability(stock: &ItemStock, available: u64) -> Availability {
+    match available {
+        0 if stock.backorder => Availability::Backordered,
+        0 => Availability::OutOfStock,
+        available if available <= stock.reorder_point => Availability::LowStock,
+        _ => Availability::InStock,
+    }
+}
+
+/// Stock of measured goods (kg, liter, meter) is kept in thousandths of the
+/// unit so quantities stay integers: 1.25 kg is stored as 1250. Requests for
+/// measured items carry a fractional `measured_quantity`, rounded to the
+/// nearest thousandth; requests for items sold each carry `quantity`.
+const MEASURED_SCALE: f64 = 1000.0;
+
+/// The stored amount a quantity change or reservation asks for.
+fn requested_quantity(
+    stock: &ItemStock,
+    quantity: u64,
+    measured: f64,
+    unit: &str,
+) -> Result<u64, Status> {
+    if !unit.is_empty() && unit != stock.stock_unit {
+        if unit != stock.sale_unit {
+            let message = format!("{}: {}", unit, UNKNOWN_UNIT_ERR);
+            return Err(Status::invalid_argument(message));
+        }
+        if measured != 0.0 {
+            return Err(Status::invalid_argument(MEASURED_EACH_ERR));
+        }
+        if !quantity.is_multiple_of(stock.sale_units_per) {
+            return Err(Status::invalid_argument(PARTIAL_UNIT_ERR));
+        }
+        return Ok(quantity / stock.sale_units_per);
+    }
+
+    if stock.unit() == Unit::Each {
+        if measured != 0.0 {
+            return Err(Status::invalid_argument(MEASURED_EACH_ERR));
+        }
+        return Ok(quantity);
+    }
+
+    if quantity != 0 {
+        return Err(Status::invalid_argument(WHOLE_MEASURED_ERR));
+    }
+
+    let scaled = (measured * MEASURED_SCALE).round();
+    if !scaled.is_finite() || scaled < 0.0 || scaled > u64::MAX as f64 {
+        return Err(Status::invalid_argument(BAD_MEASURE_ERR));
+    }
+    Ok(scaled as u64)
+}
+
+/// An item stocked in one unit and sold in another converts between them
+/// with a positive factor, and only whole items are sold that way.
+fn check_units(stock: &ItemStock) -> Result<(), Status> {
+    if stock.sale_unit.is_empty() || stock.sale_unit == stock.stock_unit {
+        return Ok(());
+    }
+    if stock.sale_units_per == 0 || stock.unit() != Unit::Each {
+        return Err(Status::invalid_argument(BAD_UNITS_ERR));
+    }
+    Ok(())
+}
+
+/// Stock kept per warehouse has a quantity that is exactly their total.
+fn check_warehouses(stock: &ItemStock) -> Result<(), Status> {
+    if warehouses::is_split(stock) && warehouses::total(stock) != Some(stock.quantity) {
+        return Err(Status::invalid_argument(WAREHOUSE_TOTAL_ERR));
+    }
+    Ok(())
+}
+
+/// Applies the scheduled prices due by `now`, in schedule order, and returns
+/// them.
+fn promote_scheduled(stock: &mut ItemStock, now: u64) -> Vec<ScheduledPrice> {
+    let due = stock
+        .schedule
+        .iter()
+        .take_while(|change| change.effective_at <= now)
+        .count();
+
+    let promoted: Vec<ScheduledPrice> = stock.schedule.drain(..due).collect();
+    for change in &promoted {
+        match change.price_list.as_str() {
+            "" => stock.price = change.price,
+            list => {
+                stock.prices.insert(list.to_owned(), change.price);
+            }
+        }
+    }
+    promoted
+}
+
+/// Rejects an item that sets fields the server fills in, or a quantity on a
+/// bundle, which is derived from its components.
+fn check_strict(item: &Item) -> Result<(), Status> {
+    if let Some((field, _)) = server_fields(item).iter().find(|(_, set)| *set) {
+        let message = format!("{}: {}", field, SERVER_FIELD_ERR);
+        return Err(Status::invalid_argument(message));
+    }
+
+    let quantity = item.stock.as_ref().map_or(0, |stock| stock.quantity);
+    if !item.bundle.is_empty() && quantity != 0 {
+        return Err(Status::invalid_argument(BUNDLE_QUANT_ERR));
+    }
+
+    Ok(())
+}
+
+/// The fields the server fills in itself, and whether `item` sets each.
+fn server_fields(item: &Item) -> [(&'static str, bool); 9] {
+    [
+        ("created_at", item.created_at != 0),
+        ("updated_at", item.updated_at != 0),
+        ("slug", !item.slug.is_empty()),
+        ("margin", item.margin.is_some()),
+        ("deleted", item.deleted),
+        ("deleted_at", item.deleted_at != 0),
+        (
+            "stock.schedule",
+            item.stock
+                .as_ref()
+                .is_some_and(|stock| !stock.schedule.is_empty()),
+        ),
+        ("availability", item.availability != 0),
+        (
+            "stock.sale_quantity",
+            item.stock
+                .as_ref()
+                .is_some_and(|stock| stock.sale_quantity != 0),
+        ),
+    ]
+}
+
+/// Whether an add would store exactly what's already there, ignoring the
+/// fields the server fills in itself.
+fn same_payload(existing: &Item, added: &Item) -> bool {
+    let mut added = added.clone();
+    added.created_at = existing.created_at;
+    added.updated_at = existing.updated_at;
+    added.slug = existing.slug.clone();
+    added.margin = existing.margin.clone();
+    if let (Some(added), Some(existing)) = (added.stock.as_mut(), existing.stock.as_ref()) {
+        added.schedule = existing.schedule.clone();
+    }
+    added == *existing
+}
+
+/// When the item last changed. Items from snapshots taken before changes
+/// were timestamped fall back to when they were created.
+fn last_updated(item: &Item) -> u64 {
+    item.updated_at.max(item.created_at)
+}
+
+fn item_name(item: &Item) -> &str {
+    item.information
+        .as_ref()
+        .and_then(|info| info.name.as_deref())
+        .unwrap_or_else(|| item_sku(item))
+}
+
+/// Checks a bundle's lines refer to existing items that aren't bundles
+/// themselves, merging lines for the same component.
+fn check_bundle(
+    map: &HashMap<String, Item>,
+    sku: &str,
+    bundle: &mut Vec<BundleLine>,
+) -> Result<(), Status> {
+    let mut merged: Vec<BundleLine> = Vec::with_capacity(bundle.len());
+    for line in bundle.drain(..) {
+        if line.sku.is_empty() || line.sku == sku || line.quantity == 0 {
+            return Err(Status::invalid_argument(BAD_BUNDLE_ERR));
+        }
+
+        match live(map, &line.sku) {
+            Some(component) if component.bundle.is_empty() => {}
+            Some(_) => return Err(Status::invalid_argument(BAD_BUNDLE_ERR)),
+            None => {
+                let message = format!("{}: {}", line.sku, NO_ITEM_ERR);
+                return Err(Status::not_found(message));
+            }
+        }
+
+        match merged.iter_mut().find(|existing| existing.sku == line.sku) {
+            Some(existing) => {
+                existing.quantity = existing
+                    .quantity
+                    .checked_add(line.quantity)
+                    .ok_or_else(|| Status::invalid_argument(BAD_BUNDLE_ERR))?;
+            }
+            None => merged.push(line),
+        }
+    }
+
+    *bundle = merged;
+    Ok(())
+}
+
+/// Items breaking the rules add and the quantity RPCs keep, in SKU order:
+/// - `sku`: the item is stored under a SKU other than its own
+/// - `stock`: the item has no stock
+/// - `price`: its price is negative or not a number
+/// - `reserved`: more is reserved than is on hand, for an item that can't
+///   be backordered while reservations are hard, or stock is reserved
+///   against a missing item
+/// - `bundle`: a bundle component is missing or is itself a bundle
+/// - `parent`: a variant's parent is missing or is itself a variant
+fn check_invariants(
+    map: &HashMap<String, Item>,
+    reservations: &Reservations,
+    mode: ReservationMode,
+) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new();
+    let mut violation = |sku: &str, rule: &str, detail: String| {
+        violations.push(InvariantViolation {
+            sku: sku.into(),
+            rule: rule.into(),
+            detail,
+        })
+    };
+
+    let mut skus: Vec<&String> = map
+        .iter()
+        .filter(|(_, item)| !item.deleted)
+        .map(|(sku, _)| sku)
+        .collect();
+    skus.sort();
+    for sku in skus {
+        let item = &map[sku];
+        if item_sku(item) != sku {
+            violation(sku, "sku", format!("stored as {}", item_sku(item)));
+        }
+
+        match item.stock.as_ref() {
+            None => violation(sku, "stock", "no stock".into()),
+            Some(stock) => {
+                if stock.price.is_nan() || stock.price < 0.0 {
+                    violation(sku, "price", format!("price {}", stock.price));
+                }
+                let reserved = reservations.reserved(sku);
+                let oversell = stock.backorder || mode == ReservationMode::Soft;
+                if item.bundle.is_empty() && !oversell && reserved > stock.quantity {
+                    let detail = format!("{} reserved of {}", reserved, stock.quantity);
+                    violation(sku, "reserved", detail);
+                }
+            }
+        }
+
+        for line in &item.bundle {
+            match live(map, &line.sku) {
+                Some(component) if component.bundle.is_empty() => {}
+                Some(_) => violation(sku, "bundle", format!("{} is a bundle", line.sku)),
+                None => violation(sku, "bundle", format!("{} is missing", line.sku)),
+            }
+        }
+
+        if !item.parent_sku.is_empty() {
+            match live(map, &item.parent_sku) {
+                Some(parent) if parent.parent_sku.is_empty() => {}
+                Some(_) => {
+                    let detail = format!("{} is a variant", item.parent_sku);
+                    violation(sku, "parent", detail);
+                }
+                None => violation(sku, "parent", format!("{} is missing", item.parent_sku)),
+            }
+        }
+    }
+
+    for sku in reservations.skus() {
+        if live(map, sku).is_none() {
+            violation(sku, "reserved", "reserved but missing".into());
+        }
+    }
+    violations.sort_by(|a, b| a.sku.cmp(&b.sku));
+    violations
+}
+
+/// Adds the stock of a parent's variants to its own, as reads rolling up
+/// variants report it.
+fn add_variant_stock(map: &HashMap<String, Item>, item: &mut Item) {
+    let sku = item_sku(item).to_owned();
+    let total = variants(map, &sku)
+        .filter_map(|variant| variant.stock.as_ref())
+        .fold(0u64, |total, stock| total.saturating_add(stock.quantity));
+    if let Some(stock) = item.stock.as_mut() {
+        stock.quantity = stock.quantity.saturating_add(total);
+    }
+}
+
+/// Variants hang directly off a live parent, so there's only ever one level:
+/// a parent can't be a variant and an item with variants can't become one.
+fn check_parent(map: &HashMap<String, Item>, sku: &str, parent: &str) -> Result<(), Status> {
+    if parent.is_empty() {
+        return Ok(());
+    }
+    if parent == sku || variants(map, sku).next().is_some() {
+        return Err(Status::invalid_argument(BAD_PARENT_ERR));
+    }
+
+    match live(map, parent) {
+        Some(found) if found.parent_sku.is_empty() => Ok(()),
+        Some(_) => Err(Status::invalid_argument(BAD_PARENT_ERR)),
+        None => {
+            let message = format!("{}: {}", parent, NO_ITEM_ERR);
+            Err(Status::not_found(message))
+        }
+    }
+}
+
+fn variants<'a>(map: &'a HashMap<String, Item>, parent: &'a str) -> impl Iterator<Item = &'a Item> {
+    live_items(map).filter(move |item| item.parent_sku == parent)
+}
+
+/// How many of a bundle its components' stock can make, less whatever is
+/// reserved when `reservations` is given. A missing component makes none.
+fn bundle_quantity(
+    map: &HashMap<String, Item>,
+    bundle: &[BundleLine],
+    reservations: Option<&Reservations>,
+) -> u64 {
+    bundle
+        .iter()
+        .map(|line| {
+            let on_hand = live(map, &line.sku)
+                .and_then(|component| component.stock.as_ref())
+                .map_or(0, |stock| stock.quantity);
+            let reserved = reservations.map_or(0, |held| held.reserved(&line.sku));
+            on_hand.saturating_sub(reserved) / line.quantity
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Marks a response that was read from the cached copy.
+fn stale_flagged<T>(mut response: Response<T>, view: &ReadView) -> Response<T> {
+    if view.is_stale() {
+        let flag = tonic::metadata::MetadataValue::from_static("true");
+        response.metadata_mut().insert(STALE_READ_HEADER, flag);
+    }
+    response
+}
+
+/// The named price list a request targets via `x-price-list`, or `None` for
+/// the default price.
+/// The client's region, the hint the `nearest` allocation strategy uses.
+fn region<T>(request: &Request<T>) -> Option<String> {
+    request
+        .metadata()
+        .get(REGION_HEADER)
+        .and_then(|region| region.to_str().ok())
+        .filter(|region| !region.is_empty())
+        .map(str::to_owned)
+}
+
+fn price_list<T>(request: &Request<T>) -> Option<String> {
+    request
+        .metadata()
+        .get(PRICE_LIST_HEADER)
+        .and_then(|list| list.to_str().ok())
+        .filter(|list| !list.is_empty())
+        .map(str::to_owned)
+}
+
+/// What replaying the audit file from `offset`, skipping entries before
+/// `since`, would have to send.
+struct Backlog {
+    entries: u64,
+    first_at: Option<u64>,
+    /// The offset just past the file's last line.
+    end: u64,
+}
+
+fn replay_backlog(path: &std::path::Path, offset: u64, since: u64) -> std::io::Result<Backlog> {
+    let file = std::fs::File::open(path)?;
+    let mut backlog = Backlog {
+        entries: 0,
+        first_at: None,
+        end: 0,
+    };
+    for line in std::io::BufRead::lines(std::io::BufReader::new(file)) {
+        let line = line?;
+        backlog.end += 1;
+        if backlog.end <= offset {
+            continue;
+        }
+        match audit::RecordedEntry::parse(&line) {
+            Some(entry) if entry.at >= since => {
+                backlog.entries += 1;
+                backlog.first_at.get_or_insert(entry.at);
+            }
+            _ => {}
+        }
+    }
+    Ok(backlog)
+}
+
+/// Whole lines read from an audit file as they're appended.
+struct AuditTail {
+    reader: std::io::BufReader<std::fs::File>,
+    /// A line still being written, finished by a later read.
+    partial: String,
+}
+
+impl AuditTail {
+    fn new(file: std::fs::File) -> Self {
+        AuditTail {
+            reader: std::io::BufReader::new(file),
+            partial: String::new(),
+        }
+    }
+
+    /// Up to `EXPORT_BUFFER` more lines; fewer once the end is reached.
+    fn read(&mut self) -> std::io::Result<Vec<String>> {
+        let mut lines = Vec::new();
+        while lines.len() < EXPORT_BUFFER {
+            std::io::BufRead::read_line(&mut self.reader, &mut self.partial)?;
+            if !self.partial.ends_with('\n') {
+                break;
+            }
+            lines.push(std::mem::take(&mut self.partial));
+        }
+        Ok(lines)
+    }
+}
+
+fn item_sku(item: &Item) -> &str {
+    item.identifier
+        .as_ref()
+        .map(|id| id.sku.as_str())
+        .unwrap_or_default()
+}
+
+fn is_expired(item: &Item, now: u64) -> bool {
+    item.ttl != 0 && item.created_at.saturating_add(item.ttl) <= now
+}
+
+#[tonic::async_trait]
+impl Inventory for StoreInventory {
+    /// Adds an item. With `x-idempotency-key` metadata, a retry with the
+    /// same key gets the first add's result back without adding again, and
+    /// one arriving while the first is still running waits for it.
+    async fn add(
+        &self,
+        request: tonic::Request<crate::store::Item>,
+    ) -> Result<tonic::Response<crate::store::InventoryChangeResponse>, tonic::Status> {
+        let key = request
+            .metadata()
+            .get(IDEMPOTENCY_KEY_HEADER)
+            .and_then(|key| key.to_str().ok())
+            .filter(|key| !key.is_empty())
+            .map(str::to_owned);
+        let key = match key {
+            Some(key) => Some(self.idempotency.lock(&key, self.clock.now()).await),
+            None => None,
+        };
+        let sku = item_sku(request.get_ref()).to_owned();
+        if let Some(completed) = key.as_ref().and_then(KeyGuard::completed) {
+            if completed.sku != sku {
+                return Err(Status::failed_precondition(KEY_REUSED_ERR));
+            }
+            return Ok(Response::new(completed.response.clone()));
+        }
+
+        let _permit = self.mutation_permit().await?;
+        let status = match self
+            .put_item(request, self.config.duplicate_add_policy, "add")
+            .await?
+        {
+            Put::Unchanged => "success: item already exists",
+            Put::Created | Put::Replaced => "success",
+        };
+
+        let response = InventoryChangeResponse {
+            status: status.into(),
+        };
+        if let Some(key) = key {
+            key.complete(sku, response.clone());
+        }
+        Ok(Response::new(response))
+    }
+
+    async fn remove(
+        &self,
+        request: tonic::Request<crate::store::ItemIdentifier>,
+    ) -> Result<tonic::Response<crate::store::InventoryChangeResponse>, tonic::Status> {
+        let _permit = self.mutation_permit().await?;
+        let item = request.into_inner();
+
+        if item.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let mut map = self.locks.lock(&self.inventory, "remove", &item.sku).await;
+        let mut indexes = self.locks.lock(&self.indexes, "remove", &item.sku).await;
+        let removed = if self.config.soft_delete {
+            live_mut(&mut map, &item.sku).map(|tombstone| {
+                tombstone.deleted = true;
+                tombstone.deleted_at = self.clock.now();
+                indexes.remove(&item.sku, tombstone);
+            })
+        } else {
+            map.remove(&item.sku)
+                .map(|removed| indexes.remove(&item.sku, &removed))
+        };
+
+        let response = match removed {
+            Some(_) => {
+                self.audit("remove", &item.sku, String::new());
+                self.bump_generation();
+                "success: item was removed"
+            }
+            None => "sucsees: item did not exist",
+        };
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: response.into(),
+        }))
+    }
+
+    /// Tombstoned items are `not_found` unless an admin sends
+    /// `x-include-deleted` metadata, which is meant for inspecting removed
+    /// records rather than for regular clients. With `x-price-list` metadata
+    /// the item is priced from that list, falling back to the default price
+    /// when the item has no price on it.
+    async fn get(
+        &self,
+        request: tonic::Request<crate::store::ItemIdentifier>,
+    ) -> Result<tonic::Response<crate::store::Item>, tonic::Status> {
+        self.check_ready()?;
+        let include_deleted = self.include_deleted(&request);
+        let include_margin = self.include_margin(&request);
+        let include_availability = self.include_availability(&request);
+        let rollup = self.rollup_variants(&request);
+        let list = price_list(&request);
+        let item = request.into_inner();
+
+        if item.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let map = self.read_view("get", &item.sku).await;
+        let found = if include_deleted {
+            map.get(&item.sku)
+        } else {
+            live(&map, &item.sku)
+        };
+        let response = match found {
+            Some(response) => response,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        let mut response = self.read_item(&map, response, include_margin)?;
+        if self.config.reservation_mode == ReservationMode::Soft && response.bundle.is_empty() {
+            let mut reservations = self.locks.lock(&self.reservations, "get", &item.sku).await;
+            reservations.expire(self.clock.now());
+            let stock = response.stock.as_mut().unwrap();
+            stock.reserved = reservations.reserved(&item.sku);
+            stock.oversold = stock.reserved.saturating_sub(stock.quantity);
+        }
+        if rollup {
+            add_variant_stock(&map, &mut response);
+        }
+        if include_availability {
+            let reservations = self.locks.lock(&self.reservations, "get", &item.sku).await;
+            self.set_availability(&map, &mut response, &reservations);
+        }
+        let stock = response.stock.as_mut().unwrap();
+        if let Some(price) = list.and_then(|list| stock.prices.get(&list).copied()) {
+            stock.price = price;
+        }
+
+        Ok(self.signed(stale_flagged(Response::new(response), &map)))
+    }
+
+    /// Every live item, plus tombstones for an admin sending
+    /// `x-include-deleted` metadata, as with `get`.
+    async fn get_all(
+        &self,
+        request: tonic::Request<crate::store::ItemAll>,
+    ) -> Result<tonic::Response<crate::store::Items>, tonic::Status> {
+        self.check_ready()?;
+        let include_deleted = self.include_deleted(&request);
+        let include_margin = self.include_margin(&request);
+        let include_availability = self.include_availability(&request);
+        let rollup = self.rollup_variants(&request);
+        let request = request.into_inner();
+        let filter = request.filter.clone().unwrap_or_default();
+        let passes = export_filter(&filter)?;
+        let map = self.read_view("get_all", "").await;
+
+        let generation = match &map {
+            ReadView::Live(_) => self.generation.load(AtomicOrdering::SeqCst),
+            ReadView::Stale { generation, .. } => *generation,
+        };
+        let shape = (
+            filter.encode_to_vec(),
+            include_deleted,
+            include_margin,
+            include_availability,
+            rollup,
+        );
+        let etag = self.get_all_etag(&map, generation, shape);
+        // Reservations change availability without bumping the generation.
+        if request.etag == etag && !include_availability {
+            let response = store::Items {
+                items: Vec::new(),
+                etag,
+                not_modified: true,
+            };
+            return Ok(self.signed(stale_flagged(Response::new(response), &map)));
+        }
+
+        let mut items = map
+            .values()
+            .filter(|item| include_deleted || !item.deleted)
+            .filter(|item| passes(item))
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        if rollup {
+            for item in items.iter_mut() {
+                add_variant_stock(&map, item);
+            }
+        }
+        if include_availability {
+            let reservations = self.locks.lock(&self.reservations, "get_all", "").await;
+            for item in items.iter_mut() {
+                self.set_availability(&map, item, &reservations);
+            }
+        }
+        let response = store::Items {
+            items,
+            etag,
+            not_modified: false,
+        };
+
+        let size = response.encoded_len();
+        if size > self.config.max_response_size {
+            let message = format!(
+                "{}: {} items encode to {} bytes, over the {} byte limit",
+                RESPONSE_SIZE_ERR,
+                response.items.len(),
+                size,
+                self.config.max_response_size
+            );
+            return Err(Status::failed_precondition(message));
+        }
+
+        Ok(self.signed(stale_flagged(Response::new(response), &map)))
+    }
+
+    async fn decrease_quantity(
+        &self,
+        request: tonic::Request<store::QuantityChangeRequest>,
+    ) -> Result<tonic::Response<store::InventoryUpdateResponse>, tonic::Status> {
+        let _permit = self.mutation_permit().await?;
+        let region = region(&request);
+        let item = request.into_inner();
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "decrease_quantity", &item.sku)
+            .await;
+        if let Some(bundle) = live(&map, &item.sku).filter(|found| !found.bundle.is_empty()) {
+            check_lifecycle(bundle, Operation::Sell)?;
+            let bundle = bundle.clone();
+            return self
+                .decrease_bundle(&mut map, &bundle, &item, region.as_deref())
+                .await;
+        }
+
+        let quantity = match live_mut(&mut map, &item.sku) {
+            Some(quantity) => quantity,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        check_lifecycle(quantity, Operation::Sell)?;
+        let stock = self.stock_mut(quantity)?;
+
+        if item.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let change = requested_quantity(stock, item.quantity, item.measured_quantity, &item.unit)?;
+        if change == 0 {
+            return self.zero_quantity_change(stock);
+        }
+
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "decrease_quantity", &item.sku)
+            .await;
+        reservations.expire(self.clock.now());
+        let available = stock
+            .quantity
+            .saturating_sub(self.held_back(&reservations, &item.sku));
+
+        if change > available {
+            return Err(Status::invalid_argument(LOW_QUANT_ERR));
+        }
+        let drawn = self.draw(stock, change, &item.warehouse, region.as_deref())?;
+        let response = InventoryUpdateResponse {
+            status: "success".into(),
+            price: stock.price,
+            quantity: stock.quantity,
+            scheduled_id: String::new(),
+            drawn,
+        };
+        quantity.updated_at = self.clock.now();
+        self.audit("decrease_quantity", &item.sku, format!("by {}", change));
+        self.bump_generation();
+
+        Ok(Response::new(response))
+    }
+
+    async fn increase_quantity(
+        &self,
+        request: tonic::Request<store::QuantityChangeRequest>,
+    ) -> Result<tonic::Response<store::InventoryUpdateResponse>, tonic::Status> {
+        let _permit = self.mutation_permit().await?;
+        let item = request.get_ref();
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "increase_quantity", &item.sku)
+            .await;
+        let quantity = match live_mut(&mut map, &item.sku) {
+            Some(quantity) => quantity,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        check_lifecycle(quantity, Operation::Restock)?;
+        let stock = self.stock_mut(quantity)?;
+
+        if item.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let change = requested_quantity(stock, item.quantity, item.measured_quantity, &item.unit)?;
+        if change == 0 {
+            return self.zero_quantity_change(stock);
+        }
+
+        let now = self.clock.now();
+        let mut operations = self
+            .locks
+            .lock(&self.operations, "increase_quantity", &item.sku)
+            .await;
+        if !item.operation_id.is_empty() {
+            if let Some(response) = operations.get(&item.sku, &item.operation_id, now) {
+                return Ok(Response::new(response));
+            }
+        }
+
+        let total = match stock.quantity.checked_add(change) {
+            Some(total) => total,
+            None => return Err(Status::out_of_range(OVERFLOW_QUANT_ERR)),
+        };
+
+        self.check_max_quantity(&request, total)?;
+        if !item.warehouse.is_empty() {
+            // Stock already on hand has no warehouse to count it under.
+            if !warehouses::is_split(stock) && stock.quantity > 0 {
+                return Err(Status::failed_precondition(UNSPLIT_STOCK_ERR));
+            }
+            *stock.warehouses.entry(item.warehouse.clone()).or_default() += change;
+        } else if warehouses::is_split(stock) {
+            return Err(Status::invalid_argument(WAREHOUSE_REQUIRED_ERR));
+        }
+        stock.quantity = total;
+        self.audit("increase_quantity", &item.sku, format!("by {}", change));
+        self.bump_generation();
+
+        let response = InventoryUpdateResponse {
+            status: "success".into(),
+            price: stock.price,
+            quantity: stock.quantity,
+            scheduled_id: String::new(),
+            drawn: Vec::new(),
+        };
+        quantity.updated_at = now;
+
+        if !item.operation_id.is_empty() {
+            operations.record(&item.sku, &item.operation_id, response.clone(), now);
+        }
+
+        Ok(Response::new(response))
+    }
+
+    /// Sets the default price, or with `x-price-list` metadata the price on
+    /// that named list, creating the list entry if needed. An `effective_at`
+    /// in the future schedules the change instead; changes for the same time
+    /// apply in the order they were scheduled.
+    async fn update_price(
+        &self,
+        request: Request<PriceChangeRequest>,
+    ) -> Result<Response<InventoryUpdateResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let item = request.get_ref();
+
+        if item.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        self.check_price(item.price)?;
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "update_price", &item.sku)
+            .await;
+        let price = match live_mut(&mut map, &item.sku) {
+            Some(price) => price,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        check_lifecycle(price, Operation::Edit)?;
+        let stock = self.stock_mut(price)?;
+        let now = self.clock.now();
+        self.promote_due(&item.sku, stock, now);
+        let list = price_list(&request);
+
+        if item.effective_at > now {
+            self.check_cost_floor(&request, stock.cost, item.price)?;
+
+            let id = format!(
+                "s{}",
+                self.schedule_ids.fetch_add(1, AtomicOrdering::SeqCst) + 1
+            );
+            let at = stock
+                .schedule
+                .partition_point(|change| change.effective_at <= item.effective_at);
+            stock.schedule.insert(
+                at,
+                ScheduledPrice {
+                    id: id.clone(),
+                    price: item.price,
+                    effective_at: item.effective_at,
+                    price_list: list.unwrap_or_default(),
+                    reason: item.reason.clone(),
+                },
+            );
+            let response = InventoryUpdateResponse {
+                status: "success: scheduled".into(),
+                price: stock.price,
+                quantity: stock.quantity,
+                scheduled_id: id.clone(),
+                drawn: Vec::new(),
+            };
+            price.updated_at = now;
+            self.audit_with_reason(
+                "update_price",
+                &item.sku,
+                format!("{} to {} at {}", id, item.price, item.effective_at),
+                item.reason.clone(),
+            );
+            self.bump_generation();
+
+            return Ok(Response::new(response));
+        }
+
+        let current = match list.as_ref() {
+            Some(list) => stock.prices.get(list).copied(),
+            None => Some(stock.price),
+        };
+
+        if current == Some(item.price) {
+            return Err(Status::invalid_argument(DUP_PRICE_ERR));
+        }
+
+        self.check_cost_floor(&request, stock.cost, item.price)?;
+
+        match list {
+            Some(list) => {
+                let detail = match current {
+                    Some(current) => format!("{} price {} to {}", list, current, item.price),
+                    None => format!("{} price set to {}", list, item.price),
+                };
+                self.audit_with_reason("update_price", &item.sku, detail, item.reason.clone());
+                stock.prices.insert(list, item.price);
+            }
+            None => {
+                self.audit_with_reason(
+                    "update_price",
+                    &item.sku,
+                    format!("{} to {}", stock.price, item.price),
+                    item.reason.clone(),
+                );
+                stock.price = item.price;
+            }
+        }
+        let response = InventoryUpdateResponse {
+            status: "success".into(),
+            price: item.price,
+            quantity: stock.quantity,
+            scheduled_id: String::new(),
+            drawn: Vec::new(),
+        };
+        price.updated_at = now;
+        self.bump_generation();
+
+        Ok(Response::new(response))
+    }
+
+    type WatchStream = Pin<Box<dyn Stream<Item = Result<Item, Status>> + Send>>;
+
+    /// Streams changes to an item. Clients pick how changes are paced with
+    /// `x-watch-mode` metadata: `immediate` (the default) sends every change
+    /// seen, `debounce` waits until the item has been quiet for the window,
+    /// and `throttle` sends the latest state at most once per window. The
+    /// window defaults to the configured one and can be set per stream with
+    /// `x-watch-window-ms`.
+    async fn watch(
+        &self,
+        request: Request<ItemIdentifier>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        self.check_stream_rate(&request)?;
+        let metadata = request.metadata();
+        let mode = match metadata.get(WATCH_MODE_HEADER).map(|mode| mode.to_str()) {
+            None | Some(Ok("immediate")) => WatchMode::Immediate,
+            Some(Ok("debounce")) => WatchMode::Debounce,
+            Some(Ok("throttle")) => WatchMode::Throttle,
+            Some(_) => return Err(Status::invalid_argument(BAD_WATCH_MODE_ERR)),
+        };
+        let window = match metadata.get(WATCH_WINDOW_HEADER).map(|ms| ms.to_str()) {
+            None => self.config.watch_window,
+            Some(Ok(ms)) => match ms.parse() {
+                Ok(ms) if ms > 0 => ms,
+                _ => return Err(Status::invalid_argument(BAD_WATCH_WINDOW_ERR)),
+            },
+            Some(Err(_)) => return Err(Status::invalid_argument(BAD_WATCH_WINDOW_ERR)),
+        };
+        let window = Duration::from_millis(window);
+        let id = request.into_inner();
+
+        if id.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let mut sent = self.get(Request::new(id.clone())).await?.into_inner();
+
+        let slot = self.watch_slot()?;
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let inventory = self.clone();
+        let poll = match mode {
+            WatchMode::Immediate => WATCH_POLL,
+            WatchMode::Debounce | WatchMode::Throttle => window.min(WATCH_POLL),
+        };
+        spawn_watch(id.sku.clone(), tx.clone(), async move {
+            let mut latest = sent.clone();
+            let mut changed_at = Instant::now();
+            let mut next_emit = Instant::now() + window;
+            while !tx.is_closed() {
+                tokio::time::sleep(poll).await;
+
+                let map = match inventory.watch_lock("watch", &id.sku).await {
+                    Some(map) => map,
+                    None => continue,
+                };
+                let item_refresh = match live(&map, &id.sku) {
+                    Some(item) => item,
+                    None => {
+                        if let Err(err) = tx.send(Err(Status::not_found(NO_ITEM_ERR))) {
+                            tracing::error!(error = ?err, "failed to update stream client");
+                        }
+                        return;
+                    }
+                };
+
+                if item_refresh != &latest {
+                    latest = item_refresh.clone();
+                    changed_at = Instant::now();
+                }
+                drop(map);
+
+                let due = match mode {
+                    WatchMode::Immediate => true,
+                    WatchMode::Debounce => changed_at.elapsed() >= window,
+                    WatchMode::Throttle => {
+                        let due = Instant::now() >= next_emit;
+                        while next_emit <= Instant::now() {
+                            next_emit += window;
+                        }
+                        due
+                    }
+                };
+
+                if due && latest != sent {
+                    if let Err(err) = tx.send(Ok(latest.clone())) {
+                        tracing::error!(error = ?err, "failed to update stream client");
+                        return;
+                    }
+                    sent = latest.clone();
+                }
+            }
+        });
+
+        let stream = Guarded::new(UnboundedReceiverStream::new(rx), slot);
         Ok(Response::new(Box::pin(stream) as Self::WatchStream))
     }
+
+    async fn export_feed(&self, request: Request<FeedRequest>) -> Result<Response<Feed>, Status> {
+        self.check_ready()?;
+        let filter = request.into_inner();
+        let now = self.clock.now();
+
+        let map = self.locks.lock(&self.inventory, "export_feed", "").await;
+        let mut items: Vec<&Item> = live_items(&map)
+            .filter(|item| !filter.active_only || !is_expired(item, now))
+            .filter(|item| !filter.in_stock_only || feed::availability(item) == feed::IN_STOCK)
+            .filter(|item| lifecycle::is_listed(item.state()))
+            .collect();
+        items.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        Ok(Response::new(Feed {
+            json: feed::render(&self.config.feed, &items),
+        }))
+    }
+
+    type InteractiveStream = Pin<Box<dyn Stream<Item = Result<SessionResponse, Status>> + Send>>;
+
+    async fn interactive(
+        &self,
+        request: Request<Streaming<SessionRequest>>,
+    ) -> Result<Response<Self::InteractiveStream>, Status> {
+        self.check_stream_rate(&request)?;
+        let mut inbound = request.into_inner();
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let inventory = self.clone();
+        tokio::spawn(async move {
+            let mut watched = HashMap::new();
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(1));
+
+            loop {
+                let responses = tokio::select! {
+                    message = inbound.next() => match message {
+                        Some(Ok(message)) => {
+                            vec![inventory.handle_session_command(message, &mut watched).await]
+                        }
+                        Some(Err(err)) => {
+                            tracing::error!(error = ?err, "failed to read session request");
+                            return;
+                        }
+                        None => return,
+                    },
+                    _ = ticker.tick() => inventory.refresh_session_watches(&mut watched).await,
+                };
+
+                for response in responses {
+                    if let Err(err) = tx.send(Ok(response)) {
+                        tracing::error!(error = ?err, "failed to update stream client");
+                        return;
+                    }
+                }
+            }
+        });
+
+        let stream = UnboundedReceiverStream::new(rx);
+        Ok(Response::new(Box::pin(stream) as Self::InteractiveStream))
+    }
+
+    /// Samples up to `count` distinct items with probability proportional to
+    /// `featured_weight`. Items with a weight of zero are never selected.
+    async fn get_weighted_random(
+        &self,
+        request: Request<WeightedRandomRequest>,
+    ) -> Result<Response<store::Items>, Status> {
+        self.check_ready()?;
+        let request = request.into_inner();
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "get_weighted_random", "")
+            .await;
+        let mut candidates: Vec<&Item> = live_items(&map)
+            .filter(|item| item.featured_weight > 0)
+            .collect();
+        candidates.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        let mut rng = match request.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let items =
+            match candidates.choose_multiple_weighted(&mut rng, request.count as usize, |item| {
+                item.featured_weight as f64
+            }) {
+                Ok(selected) => selected.map(|item| (*item).clone()).collect(),
+                Err(err) => return Err(Status::internal(err.to_string())),
+            };
+
+        Ok(Response::new(store::Items {
+            items,
+            ..Default::default()
+        }))
+    }
+
+    /// The `k` items with the highest `price * quantity`, most valuable
+    /// first, ties broken by SKU. A `k` of zero returns no items.
+    async fn get_top_by_value(
+        &self,
+        request: Request<TopRequest>,
+    ) -> Result<Response<store::Items>, Status> {
+        self.check_ready()?;
+        let k = request.into_inner().k as usize;
+        if k == 0 {
+            return Ok(Response::new(store::Items::default()));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "get_top_by_value", "")
+            .await;
+        let mut heap = BinaryHeap::with_capacity(k.min(map.len()) + 1);
+        for item in live_items(&map) {
+            heap.push(Reverse(ByValue::new(item)));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        let items = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| ranked.item.clone())
+            .collect();
+
+        Ok(Response::new(store::Items {
+            items,
+            ..Default::default()
+        }))
+    }
+
+    /// Computes the prices a percentage change across a category would
+    /// produce without modifying any item.
+    async fn preview_category_reprice(
+        &self,
+        request: Request<CategoryRepriceRequest>,
+    ) -> Result<Response<RepricePreview>, Status> {
+        self.check_ready()?;
+        let request = request.into_inner();
+
+        if request.category.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_CATEGORY_ERR));
+        }
+
+        if request.percent <= -100.0 {
+            return Err(Status::invalid_argument(BAD_PERCENT_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "preview_category_reprice", "")
+            .await;
+        let mut items: Vec<&Item> = live_items(&map)
+            .filter(|item| item.category == request.category)
+            .collect();
+        items.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        let mut preview = RepricePreview::default();
+        for item in items {
+            let stock = match item.stock.as_ref() {
+                Some(stock) => stock,
+                None => continue,
+            };
+
+            let new_price =
+                pricing::apply_percent(stock.price, request.percent, self.config.price_rounding);
+            let quantity = stock.quantity as f64;
+            preview.revenue_delta += (new_price as f64 - stock.price as f64) * quantity;
+            if let Some(cost) = stock.cost {
+                preview.margin_before += (stock.price as f64 - cost as f64) * quantity;
+                preview.margin_after += (new_price as f64 - cost as f64) * quantity;
+            }
+
+            preview.lines.push(RepriceLine {
+                sku: item_sku(item).into(),
+                old_price: stock.price,
+                new_price,
+            });
+        }
+
+        Ok(Response::new(preview))
+    }
+
+    async fn ping(&self, request: Request<PingRequest>) -> Result<Response<PingResponse>, Status> {
+        Ok(Response::new(PingResponse {
+            nonce: request.into_inner().nonce,
+            server_time: self.clock.now(),
+            ready: self.ready.load(AtomicOrdering::SeqCst),
+        }))
+    }
+
+    /// Describes the fields of an item, as the proto declares them, with
+    /// the constraints add enforces on each under the current configuration.
+    async fn describe_item(
+        &self,
+        _request: Request<DescribeRequest>,
+    ) -> Result<Response<ItemDescription>, Status> {
+        let mut fields = schema::fields(crate::store_proto::FILE_DESCRIPTOR_SET, "Item")
+            .map_err(Status::internal)?;
+        for field in fields.iter_mut() {
+            self.describe_rules(field);
+        }
+
+        Ok(Response::new(ItemDescription { fields }))
+    }
+
+    async fn add_tag_to_items(
+        &self,
+        request: Request<TagRequest>,
+    ) -> Result<Response<BulkResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let response = self
+            .retag_items("add_tag_to_items", request.into_inner(), |tags, tag| {
+                if tags.iter().any(|existing| existing == tag) {
+                    return false;
+                }
+                tags.push(tag.to_owned());
+                true
+            })
+            .await?;
+
+        Ok(Response::new(response))
+    }
+
+    async fn remove_tag_from_items(
+        &self,
+        request: Request<TagRequest>,
+    ) -> Result<Response<BulkResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let response = self
+            .retag_items(
+                "remove_tag_from_items",
+                request.into_inner(),
+                |tags, tag| {
+                    let before = tags.len();
+                    tags.retain(|existing| existing != tag);
+                    tags.len() != before
+                },
+            )
+            .await?;
+
+        Ok(Response::new(response))
+    }
+
+    type WatchManyStream = Pin<Box<dyn Stream<Item = Result<ItemEvent, Status>> + Send>>;
+
+    /// Watches several items on one stream. Each event carries its SKU; a
+    /// removed item is reported once and then no longer watched. The first
+    /// message names the SKUs to start with, and later ones subscribe to or
+    /// unsubscribe from SKUs without reopening the stream. The stream ends
+    /// once nothing is watched and the client has stopped sending.
+    async fn watch_many(
+        &self,
+        request: Request<Streaming<WatchManyRequest>>,
+    ) -> Result<Response<Self::WatchManyStream>, Status> {
+        self.check_stream_rate(&request)?;
+        let mut inbound = request.into_inner();
+
+        let first = match inbound.message().await? {
+            Some(first) if !first.skus.is_empty() => first,
+            _ => return Err(Status::invalid_argument(NO_SKUS_ERR)),
+        };
+        let mut watched = HashMap::new();
+        self.update_watched(&mut watched, first).await?;
+
+        let slot = self.watch_slot()?;
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let inventory = self.clone();
+        tokio::spawn(async move {
+            let mut inbound = Some(inbound);
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(1));
+            ticker.tick().await;
+
+            while (!watched.is_empty() || inbound.is_some()) && !tx.is_closed() {
+                tokio::select! {
+                    Some(message) = OptionFuture::from(inbound.as_mut().map(|inbound| inbound.next())),
+                        if inbound.is_some() => {
+                        let update = match message {
+                            Some(Ok(update)) => update,
+                            Some(Err(err)) => {
+                                tracing::error!(error = ?err, "failed to read watch request");
+                                return;
+                            }
+                            None => {
+                                inbound = None;
+                                continue;
+                            }
+                        };
+                        if let Err(err) = inventory.update_watched(&mut watched, update).await {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        let map = match inventory.watch_lock("watch_many", "").await {
+                            Some(map) => map,
+                            None => continue,
+                        };
+                        for (sku, current) in diff_watched(&map, &mut watched) {
+                            let event = ItemEvent {
+                                sku,
+                                removed: current.is_none(),
+                                item: current,
+                            };
+
+                            if let Err(err) = tx.send(Ok(event)) {
+                                tracing::error!(error = ?err, "failed to update stream client");
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let stream = Guarded::new(UnboundedReceiverStream::new(rx), slot);
+        Ok(Response::new(Box::pin(stream) as Self::WatchManyStream))
+    }
+
+    type WatchTotalValueStream = Pin<Box<dyn Stream<Item = Result<TotalValue, Status>> + Send>>;
+
+    /// Streams the total inventory value, emitting when a mutation changes it
+    /// and at most once per configured interval.
+    async fn watch_total_value(
+        &self,
+        request: Request<TotalValueRequest>,
+    ) -> Result<Response<Self::WatchTotalValueStream>, Status> {
+        self.check_ready()?;
+        self.check_stream_rate(&request)?;
+        let slot = self.watch_slot()?;
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let inventory = self.clone();
+        let interval = std::time::Duration::from_millis(self.config.total_value_interval);
+        tokio::spawn(async move {
+            let mut seen_generation = None;
+            let mut last_total = None;
+
+            while !tx.is_closed() {
+                let generation = inventory.generation.load(AtomicOrdering::SeqCst);
+                let map = if seen_generation != Some(generation) {
+                    inventory.watch_lock("watch_total_value", "").await
+                } else {
+                    None
+                };
+                if let Some(map) = map {
+                    seen_generation = Some(generation);
+
+                    let total = total_value(&map);
+                    drop(map);
+                    if last_total != Some(total) {
+                        last_total = Some(total);
+                        if let Err(err) = tx.send(Ok(TotalValue { total })) {
+                            tracing::error!(error = ?err, "failed to update stream client");
+                            return;
+                        }
+                    }
+                }
+
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        let stream = Guarded::new(UnboundedReceiverStream::new(rx), slot);
+        Ok(Response::new(
+            Box::pin(stream) as Self::WatchTotalValueStream
+        ))
+    }
+
+    /// Holds stock against a SKU so it can't be taken by `decrease_quantity`.
+    /// A `ttl` of zero holds until released.
+    async fn reserve(
+        &self,
+        request: Request<ReserveRequest>,
+    ) -> Result<Response<ReserveResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        if request.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "reserve", &request.sku)
+            .await;
+        let item = match live(&map, &request.sku) {
+            Some(item) => self.with_stock(item)?,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+        check_lifecycle(&item, Operation::Sell)?;
+        if !item.bundle.is_empty() {
+            return self.reserve_bundle(&map, &item, &request).await;
+        }
+
+        let stock = item.stock.unwrap_or_default();
+        let on_hand = stock.quantity;
+
+        let quantity = requested_quantity(
+            &stock,
+            request.quantity,
+            request.measured_quantity,
+            &request.unit,
+        )?;
+        if quantity == 0 {
+            return Err(Status::invalid_argument(ZERO_QUANT_ERR));
+        }
+
+        let now = self.clock.now();
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "reserve", &request.sku)
+            .await;
+        reservations.expire(now);
+
+        if let Some(max) = self.config.max_reservations_per_sku {
+            if reservations.count(&request.sku) >= max {
+                return Err(Status::resource_exhausted(MAX_RESERVATIONS_ERR));
+            }
+        }
+
+        let available = on_hand.saturating_sub(self.held_back(&reservations, &request.sku));
+        if quantity > available {
+            return Err(Status::failed_precondition(INSUFFICIENT_STOCK_ERR));
+        }
+
+        let expires_at = self.reservation_expiry(request.ttl, now)?;
+        let reservation_id = reservations.insert(Reservation {
+            sku: request.sku.clone(),
+            quantity,
+            expires_at,
+        });
+
+        Ok(Response::new(ReserveResponse {
+            reservation_id,
+            available: on_hand.saturating_sub(reservations.reserved(&request.sku)),
+        }))
+    }
+
+    async fn release(
+        &self,
+        request: Request<ReleaseRequest>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        let mut reservations = self.locks.lock(&self.reservations, "release", "").await;
+        reservations.expire(self.clock.now());
+        if reservations.remove(&request.reservation_id).is_none() {
+            return Err(Status::not_found(NO_RESERVATION_ERR));
+        }
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    /// Renames an item and regenerates its slug.
+    async fn update_name(
+        &self,
+        request: Request<NameChangeRequest>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        if request.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        if request.name.trim().is_empty() {
+            return Err(Status::invalid_argument(EMPTY_NAME_ERR));
+        }
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "update_name", &request.sku)
+            .await;
+        let mut renamed = match live(&map, &request.sku) {
+            Some(item) => item.clone(),
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+        check_lifecycle(&renamed, Operation::Edit)?;
+        renamed
+            .information
+            .get_or_insert_with(Default::default)
+            .name = Some(request.name);
+
+        let mut indexes = self
+            .locks
+            .lock(&self.indexes, "update_name", &request.sku)
+            .await;
+        if !indexes.name_available(&map, &request.sku, &renamed) {
+            return Err(Status::already_exists(DUP_NAME_ERR));
+        }
+
+        let item = live_mut(&mut map, &request.sku).unwrap();
+        indexes.remove(&request.sku, item);
+        indexes.insert(&request.sku, &mut renamed);
+        renamed.updated_at = self.clock.now();
+        *item = renamed;
+        self.audit("update_name", &request.sku, String::new());
+        self.bump_generation();
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    async fn get_by_slug(&self, request: Request<SlugRequest>) -> Result<Response<Item>, Status> {
+        self.check_ready()?;
+        let request = request.into_inner();
+
+        if request.slug.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SLUG_ERR));
+        }
+
+        let map = self.locks.lock(&self.inventory, "get_by_slug", "").await;
+        let mut indexes = self.locks.lock(&self.indexes, "get_by_slug", "").await;
+        let item = match indexes
+            .resolve_slug(&map, &request.slug)
+            .and_then(|sku| live(&map, &sku))
+        {
+            Some(item) => item,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        Ok(self.signed(Response::new(self.with_stock(item)?)))
+    }
+
+    /// Adds each streamed item independently. Failures carry the zero-based
+    /// position of the item in the stream.
+    async fn add_batch(
+        &self,
+        request: Request<Streaming<Item>>,
+    ) -> Result<Response<BatchSummary>, Status> {
+        // An idempotency key covers a single add, so it isn't passed on to
+        // each item of the stream.
+        let mut metadata = request.metadata().clone();
+        metadata.remove(IDEMPOTENCY_KEY_HEADER);
+        let mut inbound = request.into_inner();
+
+        let mut summary = BatchSummary::default();
+        let mut index = 0;
+        while let Some(item) = inbound.next().await {
+            let item = item?;
+            let sku = item_sku(&item).to_owned();
+
+            // Items past the batch size fail rather than the whole stream,
+            // since those before them have already been added.
+            let mut add = Request::new(item);
+            *add.metadata_mut() = metadata.clone();
+            let added = match self.check_batch(index as usize + 1) {
+                Ok(()) => self.add(add).await,
+                Err(status) => Err(status),
+            };
+            match added {
+                Ok(_) => summary.added += 1,
+                Err(status) => summary.failures.push(BatchFailure {
+                    index,
+                    sku,
+                    error: status.message().into(),
+                }),
+            }
+            index += 1;
+        }
+
+        Ok(Response::new(summary))
+    }
+
+    /// Reconciles streamed stock counts, setting each item's quantity to what
+    /// was counted. Counts already waiting on the stream are applied together
+    /// under one inventory lock, up to the chunk or else the batch size at a
+    /// time. Only counts that differ from the stock on hand change anything;
+    /// those are audited with their variance and reach watches and long polls
+    /// like any other quantity change. Failures carry the zero-based position
+    /// of the count in the stream.
+    async fn stream_stocktake(
+        &self,
+        request: Request<Streaming<StockCount>>,
+    ) -> Result<Response<StocktakeSummary>, Status> {
+        let metadata = request.metadata().clone();
+        let mut check = Request::new(());
+        *check.metadata_mut() = metadata;
+        let mut inbound = request.into_inner();
+
+        let mut summary = StocktakeSummary::default();
+        let mut index = 0;
+        while let Some(count) = inbound.next().await {
+            let mut batch = vec![count?];
+            let size = self.chunk_size(self.config.max_batch_size);
+            while batch.len() < size {
+                match futures::FutureExt::now_or_never(inbound.next()) {
+                    Some(Some(count)) => batch.push(count?),
+                    _ => break,
+                }
+            }
+
+            let _permit = self.mutation_permit().await?;
+            let mut map = self
+                .locks
+                .lock(&self.inventory, "stream_stocktake", "")
+                .await;
+            let mut changed = false;
+            for count in batch {
+                match self.apply_count(&mut map, &count, &check) {
+                    Ok(correction) => {
+                        summary.counted += 1;
+                        changed |= correction.is_some();
+                        summary.corrections.extend(correction);
+                    }
+                    Err(status) => summary.failures.push(BatchFailure {
+                        index,
+                        sku: count.sku,
+                        error: status.message().into(),
+                    }),
+                }
+                index += 1;
+            }
+            if changed {
+                self.bump_generation();
+            }
+        }
+
+        Ok(Response::new(summary))
+    }
+
+    /// Reprices every item in a category, leaving out any whose lifecycle
+    /// state doesn't allow edits. A rejected line leaves the category
+    /// unchanged, unless `--batch-chunk-size` is set: then the lock is
+    /// released between chunks and the chunks before it stay applied.
+    async fn adjust_price_percent(
+        &self,
+        request: Request<PriceAdjustRequest>,
+    ) -> Result<Response<PriceAdjustResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let adjust = request.get_ref();
+
+        if adjust.category.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_CATEGORY_ERR));
+        }
+
+        if adjust.percent <= -100.0 {
+            return Err(Status::invalid_argument(BAD_PERCENT_ERR));
+        }
+
+        if adjust.floor.is_some_and(|floor| floor <= 0.0) {
+            return Err(Status::invalid_argument(BAD_PRICE_ERR));
+        }
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "adjust_price_percent", "")
+            .await;
+        let mut skus: Vec<String> = live_items(&map)
+            .filter(|item| item.category == adjust.category && item.stock.is_some())
+            .filter(|item| lifecycle::permits(item.state(), Operation::Edit))
+            .map(|item| item_sku(item).to_owned())
+            .collect();
+        skus.sort();
+
+        let size = self.chunk_size(skus.len());
+        let mut response = PriceAdjustResponse::default();
+        for (index, chunk) in skus.chunks(size).enumerate() {
+            if index > 0 {
+                drop(map);
+                tokio::task::yield_now().await;
+                map = self
+                    .locks
+                    .lock(&self.inventory, "adjust_price_percent", "")
+                    .await;
+            }
+
+            if let Err(status) = self.reprice(&mut map, chunk, &request, &mut response) {
+                return Err(chunk_failed(status, index * size, skus.len()));
+            }
+            if size < skus.len() {
+                chunk_applied(
+                    "adjust_price_percent",
+                    index * size + chunk.len(),
+                    skus.len(),
+                );
+            }
+        }
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_metrics(
+        &self,
+        _request: Request<MetricsRequest>,
+    ) -> Result<Response<Metrics>, Status> {
+        Ok(Response::new(Metrics {
+            slow_lock_acquisitions: self.locks.slow_acquisitions(),
+            active_watch_streams: self.watchers.active() as u64,
+            watch_rounds_skipped: self.locks.abandoned_acquisitions(),
+        }))
+    }
+
+    async fn get_with_status(
+        &self,
+        request: Request<ItemIdentifier>,
+    ) -> Result<Response<ItemStatus>, Status> {
+        let mut item = self.get(request).await?.into_inner();
+        let status = stock_status(self.stock_mut(&mut item)?).into();
+
+        Ok(self.signed(Response::new(ItemStatus {
+            item: Some(item),
+            status,
+        })))
+    }
+
+    /// Pages through a category in the requested order. The cursor is the
+    /// offset of the next page, so pages stay stable while the category is
+    /// unchanged; ties are broken by SKU.
+    async fn list_by_category(
+        &self,
+        request: Request<CategoryListRequest>,
+    ) -> Result<Response<CategoryPage>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.category.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_CATEGORY_ERR));
+        }
+
+        let sort = match CategorySort::from_i32(request.sort) {
+            Some(sort) => sort,
+            None => return Err(Status::invalid_argument(BAD_SORT_ERR)),
+        };
+
+        let offset = if request.cursor.is_empty() {
+            0
+        } else {
+            match request.cursor.parse::<usize>() {
+                Ok(offset) => offset,
+                Err(_) => return Err(Status::invalid_argument(BAD_CURSOR_ERR)),
+            }
+        };
+
+        let now = self.clock.now();
+        let map = self
+            .locks
+            .lock(&self.inventory, "list_by_category", "")
+            .await;
+        let mut items = live_items(&map)
+            .filter(|item| item.category == request.category)
+            .filter(|item| !request.active_only || !is_expired(item, now))
+            .filter(|item| !request.in_stock_only || feed::availability(item) == feed::IN_STOCK)
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(map);
+
+        let stock = |item: &Item| item.stock.clone().unwrap_or_default();
+        items.sort_by(|a, b| {
+            let order = match sort {
+                CategorySort::Sku => Ordering::Equal,
+                CategorySort::PriceAsc => stock(a).price.total_cmp(&stock(b).price),
+                CategorySort::PriceDesc => stock(b).price.total_cmp(&stock(a).price),
+                CategorySort::Name => item_name(a).cmp(item_name(b)),
+                CategorySort::Quantity => stock(a).quantity.cmp(&stock(b).quantity),
+            };
+            order.then_with(|| item_sku(a).cmp(item_sku(b)))
+        });
+
+        let total = items.len();
+        let page_size = match request.page_size {
+            0 => total,
+            size => size as usize,
+        };
+        let end = offset.saturating_add(page_size).min(total);
+        let next_cursor = if end < total {
+            end.to_string()
+        } else {
+            String::new()
+        };
+
+        Ok(Response::new(CategoryPage {
+            items: items.drain(offset.min(total)..end).collect(),
+            next_cursor,
+            total: total as u64,
+        }))
+    }
+
+    /// Reserves every line of a claim or none of them. Lines for the same SKU
+    /// are checked against its stock together.
+    async fn claim_items(
+        &self,
+        request: Request<ClaimRequest>,
+    ) -> Result<Response<ClaimResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        if request.lines.is_empty() {
+            return Err(Status::invalid_argument(NO_SKUS_ERR));
+        }
+
+        self.check_batch(request.lines.len())?;
+
+        let map = self.locks.lock(&self.inventory, "claim_items", "").await;
+        let now = self.clock.now();
+        let mut reservations = self.locks.lock(&self.reservations, "claim_items", "").await;
+        reservations.expire(now);
+
+        let expires_at = self.reservation_expiry(request.ttl, now)?;
+        let mut claimed: HashMap<&str, (u64, usize)> = HashMap::new();
+        let mut held = Vec::with_capacity(request.lines.len());
+        for line in &request.lines {
+            if line.sku.is_empty() {
+                return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+            }
+
+            let fail = |message: &str| format!("{}: {}", line.sku, message);
+            let stock = match live(&map, &line.sku) {
+                Some(item) => {
+                    check_lifecycle(item, Operation::Sell)
+                        .map_err(|err| Status::failed_precondition(fail(err.message())))?;
+                    self.with_stock(item)?.stock.unwrap_or_default()
+                }
+                None => return Err(Status::not_found(fail(NO_ITEM_ERR))),
+            };
+
+            let quantity =
+                requested_quantity(&stock, line.quantity, line.measured_quantity, &line.unit)?;
+            if quantity == 0 {
+                return Err(Status::invalid_argument(fail(ZERO_QUANT_ERR)));
+            }
+
+            let (total, lines) = claimed.entry(&line.sku).or_default();
+            *total += quantity;
+            *lines += 1;
+
+            if let Some(max) = self.config.max_reservations_per_sku {
+                if reservations.count(&line.sku) + *lines > max {
+                    return Err(Status::resource_exhausted(fail(MAX_RESERVATIONS_ERR)));
+                }
+            }
+
+            let available = stock
+                .quantity
+                .saturating_sub(self.held_back(&reservations, &line.sku));
+            if *total > available {
+                return Err(Status::failed_precondition(fail(INSUFFICIENT_STOCK_ERR)));
+            }
+
+            held.push(Reservation {
+                sku: line.sku.clone(),
+                quantity,
+                expires_at,
+            });
+        }
+
+        Ok(Response::new(ClaimResponse {
+            claim_id: reservations.insert_claim(held),
+        }))
+    }
+
+    async fn release_claim(
+        &self,
+        request: Request<ClaimIdRequest>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "release_claim", "")
+            .await;
+        reservations.expire(self.clock.now());
+        if reservations.remove_claim(&request.claim_id).is_none() {
+            return Err(Status::not_found(NO_CLAIM_ERR));
+        }
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    /// Takes a claim's reserved quantities out of stock and drops the claim.
+    async fn commit_claim(
+        &self,
+        request: Request<ClaimIdRequest>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        let mut map = self.locks.lock(&self.inventory, "commit_claim", "").await;
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "commit_claim", "")
+            .await;
+        let now = self.clock.now();
+        reservations.expire(now);
+        let held = match reservations.claim(&request.claim_id) {
+            Some(held) => held,
+            None => return Err(Status::not_found(NO_CLAIM_ERR)),
+        };
+
+        // Every line is checked first, so a bad one leaves the claim and the
+        // stock of the others as they were.
+        let mut totals: HashMap<&str, u64> = HashMap::new();
+        for reservation in &held {
+            let fail = |message: &str| format!("{}: {}", reservation.sku, message);
+            let stock = match live(&map, &reservation.sku) {
+                Some(item) => {
+                    check_lifecycle(item, Operation::Sell)
+                        .map_err(|err| Status::failed_precondition(fail(err.message())))?;
+                    self.with_stock(item)?.stock.unwrap_or_default()
+                }
+                None => return Err(Status::not_found(fail(NO_ITEM_ERR))),
+            };
+
+            let total = totals.entry(&reservation.sku).or_default();
+            *total += reservation.quantity;
+            if *total > stock.quantity {
+                return Err(Status::failed_precondition(fail(INSUFFICIENT_STOCK_ERR)));
+            }
+        }
+        reservations.remove_claim(&request.claim_id);
+
+        for reservation in held {
+            let item = live_mut(&mut map, &reservation.sku).unwrap();
+            item.updated_at = now;
+            let stock = self.stock_mut(item)?;
+            self.draw(stock, reservation.quantity, "", None)?;
+            self.audit(
+                "commit_claim",
+                &reservation.sku,
+                format!("by {}", reservation.quantity),
+            );
+        }
+        self.bump_generation();
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    /// Decreases by as much of the requested quantity as is unreserved,
+    /// and held by the warehouse when one is named, reporting what was taken
+    /// and what was short instead of failing.
+    async fn decrease_quantity_partial(
+        &self,
+        request: Request<QuantityChangeRequest>,
+    ) -> Result<Response<PartialDecreaseResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let region = region(&request);
+        let item = request.into_inner();
+
+        if item.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "decrease_quantity_partial", &item.sku)
+            .await;
+        let found = match live_mut(&mut map, &item.sku) {
+            Some(found) => found,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+        check_lifecycle(found, Operation::Sell)?;
+        let stock = self.stock_mut(found)?;
+
+        let change = requested_quantity(stock, item.quantity, item.measured_quantity, &item.unit)?;
+        if change == 0 {
+            return Ok(Response::new(PartialDecreaseResponse {
+                update: Some(self.zero_quantity_change(stock)?.into_inner()),
+                decreased: 0,
+                shortfall: 0,
+            }));
+        }
+
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "decrease_quantity_partial", &item.sku)
+            .await;
+        reservations.expire(self.clock.now());
+        let mut available = stock
+            .quantity
+            .saturating_sub(self.held_back(&reservations, &item.sku));
+        if !item.warehouse.is_empty() {
+            available = available.min(stock.warehouses.get(&item.warehouse).copied().unwrap_or(0));
+        }
+
+        let decreased = change.min(available);
+        let drawn = self.draw(stock, decreased, &item.warehouse, region.as_deref())?;
+        let update = InventoryUpdateResponse {
+            status: "success".into(),
+            price: stock.price,
+            quantity: stock.quantity,
+            scheduled_id: String::new(),
+            drawn,
+        };
+        if decreased > 0 {
+            found.updated_at = self.clock.now();
+            self.audit(
+                "decrease_quantity_partial",
+                &item.sku,
+                format!("by {}", decreased),
+            );
+            self.bump_generation();
+        }
+
+        Ok(Response::new(PartialDecreaseResponse {
+            update: Some(update),
+            decreased,
+            shortfall: change - decreased,
+        }))
+    }
+
+    /// Looks up several SKUs at once. By default found items and missing
+    /// SKUs come back in separate lists; with `preserve_order` every SKU gets
+    /// a result at its request position instead, marked missing if absent.
+    async fn get_many(
+        &self,
+        request: Request<GetManyRequest>,
+    ) -> Result<Response<GetManyResponse>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.skus.is_empty() {
+            return Err(Status::invalid_argument(NO_SKUS_ERR));
+        }
+
+        self.check_batch(request.skus.len())?;
+
+        let map = self.locks.lock(&self.inventory, "get_many", "").await;
+        let mut response = GetManyResponse::default();
+        for sku in request.skus {
+            let item = match live(&map, &sku) {
+                Some(item) => Some(self.read_item(&map, item, include_margin)?),
+                None => None,
+            };
+
+            if request.preserve_order {
+                response.results.push(GetManyResult {
+                    sku,
+                    missing: item.is_none(),
+                    item,
+                });
+                continue;
+            }
+
+            match item {
+                Some(item) => response.items.push(item),
+                None => response.missing.push(sku),
+            }
+        }
+
+        Ok(self.signed(Response::new(response)))
+    }
+
+    /// Rounds a price to a currency's minor unit with the configured rounding
+    /// mode, without storing anything.
+    async fn round_price(
+        &self,
+        request: Request<RoundPriceRequest>,
+    ) -> Result<Response<RoundPriceResponse>, Status> {
+        let request = request.into_inner();
+
+        if !request.price.is_finite() || request.price <= 0.0 {
+            return Err(Status::invalid_argument(BAD_PRICE_ERR));
+        }
+
+        let decimals = match pricing::minor_unit_decimals(&request.currency) {
+            Some(decimals) => decimals,
+            None => return Err(Status::invalid_argument(UNKNOWN_CURRENCY_ERR)),
+        };
+
+        Ok(Response::new(RoundPriceResponse {
+            price: pricing::round_to_decimals(request.price, decimals, self.config.price_rounding),
+            decimals,
+        }))
+    }
+
+    /// Reports slug and name index entries that disagree with the inventory
+    /// map, and items breaking an invariant the RPCs maintain. Nothing is
+    /// changed; see `repair_indexes`.
+    async fn verify_consistency(
+        &self,
+        request: Request<ConsistencyRequest>,
+    ) -> Result<Response<ConsistencyReport>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "verify_consistency", "")
+            .await;
+        let indexes = self
+            .locks
+            .lock(&self.indexes, "verify_consistency", "")
+            .await;
+        let mut reservations = self
+            .locks
+            .lock(&self.reservations, "verify_consistency", "")
+            .await;
+        reservations.expire(self.clock.now());
+
+        Ok(Response::new(ConsistencyReport {
+            issues: indexes.verify(&map),
+            repaired: 0,
+            violations: check_invariants(&map, &reservations, self.config.reservation_mode),
+        }))
+    }
+
+    /// Rebuilds the indexes from the inventory map and reports what is still
+    /// inconsistent afterwards, which is only ever duplicate names. Invariant
+    /// violations aren't checked; see `verify_consistency`.
+    async fn repair_indexes(
+        &self,
+        request: Request<ConsistencyRequest>,
+    ) -> Result<Response<ConsistencyReport>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+
+        let _permit = self.mutation_permit().await?;
+        let mut map = self.locks.lock(&self.inventory, "repair_indexes", "").await;
+        let mut indexes = self.locks.lock(&self.indexes, "repair_indexes", "").await;
+
+        let found = indexes.verify(&map).len();
+        for sku in indexes.rebuild(&mut map) {
+            self.audit("repair_indexes", &sku, format!("slug {}", map[&sku].slug));
+        }
+        let issues = indexes.verify(&map);
+        self.bump_generation();
+
+        Ok(Response::new(ConsistencyReport {
+            repaired: found.saturating_sub(issues.len()) as u32,
+            issues,
+            violations: Vec::new(),
+        }))
+    }
+
+    /// Pauses or resumes mutations. While paused they fail with
+    /// `unavailable` and a retry hint; reads keep working.
+    async fn set_maintenance(
+        &self,
+        request: Request<MaintenanceRequest>,
+    ) -> Result<Response<MaintenanceResponse>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+
+        let enabled = request.into_inner().enabled;
+        self.maintenance.store(enabled, AtomicOrdering::SeqCst);
+        tracing::info!(enabled, "maintenance mode changed");
+
+        Ok(Response::new(MaintenanceResponse { enabled }))
+    }
+
+    /// Creates missing items and replaces existing ones, each on its own so
+    /// an invalid item fails alone. Results follow the request order.
+    async fn upsert_many(
+        &self,
+        request: Request<UpsertRequest>,
+    ) -> Result<Response<UpsertResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        self.check_batch(request.get_ref().items.len())?;
+        let mut metadata = request.metadata().clone();
+        metadata.remove(IDEMPOTENCY_KEY_HEADER);
+
+        let mut response = UpsertResponse::default();
+        for item in request.into_inner().items {
+            let sku = item_sku(&item).to_owned();
+
+            let mut put = Request::new(item);
+            *put.metadata_mut() = metadata.clone();
+            let result = match self
+                .put_item(put, DuplicateAddPolicy::LastWins, "upsert_many")
+                .await
+            {
+                Ok(Put::Created) => UpsertResult {
+                    sku,
+                    outcome: UpsertOutcome::Created.into(),
+                    error: String::new(),
+                },
+                Ok(Put::Replaced | Put::Unchanged) => UpsertResult {
+                    sku,
+                    outcome: UpsertOutcome::Updated.into(),
+                    error: String::new(),
+                },
+                Err(status) => UpsertResult {
+                    sku,
+                    outcome: UpsertOutcome::Failed.into(),
+                    error: status.message().into(),
+                },
+            };
+            response.results.push(result);
+        }
+
+        Ok(Response::new(response))
+    }
+
+    /// Formats an amount for display in the request's locale, or the
+    /// configured one when unset.
+    async fn format_price(
+        &self,
+        request: Request<FormatPriceRequest>,
+    ) -> Result<Response<FormatPriceResponse>, Status> {
+        let request = request.into_inner();
+
+        if !request.amount.is_finite() {
+            return Err(Status::invalid_argument(BAD_PRICE_ERR));
+        }
+
+        if pricing::minor_unit_decimals(&request.currency).is_none() {
+            return Err(Status::invalid_argument(UNKNOWN_CURRENCY_ERR));
+        }
+
+        let locale = request
+            .locale
+            .as_deref()
+            .unwrap_or(&self.config.price_locale);
+        let formatted = match pricing::format_amount(
+            request.amount,
+            &request.currency,
+            locale,
+            self.config.price_rounding,
+        ) {
+            Some(formatted) => formatted,
+            None => return Err(Status::invalid_argument(UNKNOWN_LOCALE_ERR)),
+        };
+
+        Ok(Response::new(FormatPriceResponse { formatted }))
+    }
+
+    /// Counts live items per quantity bucket. `bounds` are inclusive upper
+    /// bounds, defaulting to the configured ones; items without stock count
+    /// as zero and bundles by the quantity their components make.
+    async fn get_quantity_histogram(
+        &self,
+        request: Request<HistogramRequest>,
+    ) -> Result<Response<QuantityHistogram>, Status> {
+        self.check_ready()?;
+        let mut bounds = request.into_inner().bounds;
+        if bounds.is_empty() {
+            bounds = self.config.histogram_buckets.clone();
+        }
+
+        if bounds.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(Status::invalid_argument(BAD_BUCKETS_ERR));
+        }
+
+        let mut buckets: Vec<HistogramBucket> = Vec::with_capacity(bounds.len() + 1);
+        let mut min = 0;
+        for &max in &bounds {
+            buckets.push(HistogramBucket {
+                min,
+                max: Some(max),
+                count: 0,
+            });
+            min = max.saturating_add(1);
+        }
+        if bounds.last() != Some(&u64::MAX) {
+            buckets.push(HistogramBucket {
+                min,
+                max: None,
+                count: 0,
+            });
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "get_quantity_histogram", "")
+            .await;
+        for item in live_items(&map) {
+            let quantity = match (item.bundle.is_empty(), item.stock.as_ref()) {
+                (false, _) => bundle_quantity(&map, &item.bundle, None),
+                (true, Some(stock)) => stock.quantity,
+                (true, None) => 0,
+            };
+            let bucket = bounds.partition_point(|&max| max < quantity);
+            buckets[bucket].count += 1;
+        }
+
+        Ok(Response::new(QuantityHistogram { buckets }))
+    }
+
+    /// Items carrying any or all of the given tags, each once and in SKU
+    /// order.
+    async fn get_by_tags(&self, request: Request<TagsRequest>) -> Result<Response<Items>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.tags.is_empty() {
+            return Err(Status::invalid_argument(NO_TAGS_ERR));
+        }
+
+        if request.tags.iter().any(String::is_empty) {
+            return Err(Status::invalid_argument(EMPTY_TAG_ERR));
+        }
+
+        let wanted: HashSet<&str> = request.tags.iter().map(String::as_str).collect();
+
+        let map = self.locks.lock(&self.inventory, "get_by_tags", "").await;
+        let mut items = live_items(&map)
+            .filter(|item| has_tags(item, &wanted, request.mode()))
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        Ok(Response::new(Items {
+            items,
+            etag: self.etag(),
+            not_modified: false,
+        }))
+    }
+
+    /// Lists an item's pending price changes in the order they'll apply.
+    async fn get_price_schedule(
+        &self,
+        request: Request<ItemIdentifier>,
+    ) -> Result<Response<PriceSchedule>, Status> {
+        let item = self.get(request).await?.into_inner();
+        let changes = item.stock.map(|stock| stock.schedule).unwrap_or_default();
+
+        Ok(Response::new(PriceSchedule { changes }))
+    }
+
+    async fn cancel_scheduled_price(
+        &self,
+        request: Request<CancelScheduleRequest>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        if request.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "cancel_scheduled_price", &request.sku)
+            .await;
+        let item = match live_mut(&mut map, &request.sku) {
+            Some(item) => item,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        let stock = self.stock_mut(item)?;
+        self.promote_due(&request.sku, stock, self.clock.now());
+        let at = match stock
+            .schedule
+            .iter()
+            .position(|change| change.id == request.id)
+        {
+            Some(at) => at,
+            None => return Err(Status::not_found(NO_SCHEDULE_ERR)),
+        };
+
+        stock.schedule.remove(at);
+        item.updated_at = self.clock.now();
+        self.audit(
+            "cancel_scheduled_price",
+            &request.sku,
+            format!("{} cancelled", request.id),
+        );
+        self.bump_generation();
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    type ExportChangesStream = Pin<Box<dyn Stream<Item = Result<ChangeEvent, Status>> + Send>>;
+
+    /// Replays the audit file from `offset`, its zero-based line number,
+    /// skipping entries before `since`. With `follow` the stream then keeps
+    /// tailing the file for live changes. The file is read as the client
+    /// consumes events, so memory stays bounded however long the log is.
+    ///
+    /// A replay longer or older than the configured limits fails with
+    /// `failed_precondition` naming the current end offset. The client must
+    /// then drop whatever it built from earlier events, reload with GetAll
+    /// and export from that offset.
+    async fn export_changes(
+        &self,
+        request: Request<ExportChangesRequest>,
+    ) -> Result<Response<Self::ExportChangesStream>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+        self.check_stream_rate(&request)?;
+        let request = request.into_inner();
+
+        let path = match self.config.audit_file.clone() {
+            Some(path) => path,
+            None => return Err(Status::failed_precondition(NO_CHANGE_LOG_ERR)),
+        };
+        let file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(err) => return Err(Status::internal(err.to_string())),
+        };
+
+        if self.config.max_replay_age.is_some() || self.config.max_replay_entries.is_some() {
+            let backlog_path = path.clone();
+            let (offset, since) = (request.offset, request.since);
+            let backlog =
+                tokio::task::spawn_blocking(move || replay_backlog(&backlog_path, offset, since))
+                    .await;
+            let backlog = match backlog {
+                Ok(Ok(backlog)) => backlog,
+                Ok(Err(err)) => return Err(Status::internal(err.to_string())),
+                Err(err) => return Err(Status::internal(err.to_string())),
+            };
+
+            let oldest = self
+                .config
+                .max_replay_age
+                .map(|age| self.clock.now().saturating_sub(age));
+            let too_old =
+                matches!((backlog.first_at, oldest), (Some(at), Some(oldest)) if at < oldest);
+            let too_long = self
+                .config
+                .max_replay_entries
+                .is_some_and(|max| backlog.entries > max);
+            if too_old || too_long {
+                let message = format!("{}; current offset is {}", RESYNC_ERR, backlog.end);
+                return Err(Status::failed_precondition(message));
+            }
+        }
+
+        let slot = self.watch_slot()?;
+        let (tx, rx) = mpsc::channel(EXPORT_BUFFER);
+
+        // Reads take a blocking thread only while there are lines to read;
+        // a follower waiting for more sleeps on the runtime instead.
+        tokio::spawn(async move {
+            let mut tail = AuditTail::new(file);
+            let mut offset = 0;
+            while !tx.is_closed() {
+                let read = tokio::task::spawn_blocking(move || {
+                    let lines = tail.read();
+                    (tail, lines)
+                })
+                .await;
+                let lines = match read {
+                    Ok((returned, Ok(lines))) => {
+                        tail = returned;
+                        lines
+                    }
+                    Ok((_, Err(err))) => {
+                        tracing::error!(error = ?err, "failed to read audit file");
+                        return;
+                    }
+                    Err(err) => {
+                        tracing::error!(error = ?err, "audit file reader panicked");
+                        return;
+                    }
+                };
+                let caught_up = lines.len() < EXPORT_BUFFER;
+
+                for line in lines {
+                    let entry = audit::RecordedEntry::parse(&line);
+                    offset += 1;
+
+                    let entry = match entry {
+                        Some(entry) => entry,
+                        None => {
+                            tracing::error!(line = offset - 1, "skipping malformed audit line");
+                            continue;
+                        }
+                    };
+                    if offset - 1 < request.offset || entry.at < request.since {
+                        continue;
+                    }
+
+                    let event = ChangeEvent {
+                        offset: offset - 1,
+                        at: entry.at,
+                        rpc: entry.rpc,
+                        sku: entry.sku,
+                        detail: entry.detail,
+                        reason: entry.reason,
+                    };
+                    if let Err(err) = tx.send(Ok(event)).await {
+                        tracing::error!(error = ?err, "failed to update stream client");
+                        return;
+                    }
+                }
+
+                if caught_up {
+                    if !request.follow {
+                        return;
+                    }
+                    tokio::time::sleep(EXPORT_POLL).await;
+                }
+            }
+        });
+
+        let stream = Guarded::new(ReceiverStream::new(rx), slot);
+        Ok(Response::new(Box::pin(stream) as Self::ExportChangesStream))
+    }
+
+    /// Purges tombstones past their retention and items whose TTL has
+    /// elapsed, then rebuilds the indexes. Reads wait only while the
+    /// inventory is locked. Bytes reclaimed are the encoded size of what was
+    /// purged. Runs during maintenance, when it's most useful.
+    async fn compact(
+        &self,
+        request: Request<CompactRequest>,
+    ) -> Result<Response<CompactReport>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+        self.check_ready()?;
+
+        let now = self.clock.now();
+        let retention = self.config.tombstone_retention;
+        let mut map = self.locks.lock(&self.inventory, "compact", "").await;
+        let mut indexes = self.locks.lock(&self.indexes, "compact", "").await;
+
+        let mut report = CompactReport::default();
+        let mut purged_skus = Vec::new();
+        map.retain(|sku, item| {
+            let purged = if item.deleted {
+                item.deleted_at.saturating_add(retention) <= now
+            } else {
+                is_expired(item, now)
+            };
+            if purged {
+                if item.deleted {
+                    report.tombstones_purged += 1;
+                } else {
+                    report.expired_purged += 1;
+                }
+                report.bytes_reclaimed += item.encoded_len() as u64;
+                purged_skus.push((sku.clone(), item.deleted));
+            }
+            !purged
+        });
+        map.shrink_to_fit();
+
+        for (sku, tombstone) in &purged_skus {
+            let detail = if *tombstone {
+                "tombstone purged"
+            } else {
+                "expired"
+            };
+            self.audit("compact", sku, detail.into());
+        }
+        report.index_repairs = indexes.verify(&map).len() as u64;
+        for sku in indexes.rebuild(&mut map) {
+            self.audit("compact", &sku, format!("slug {}", map[&sku].slug));
+        }
+        self.audit(
+            "compact",
+            "",
+            format!(
+                "{} tombstones, {} expired",
+                report.tombstones_purged, report.expired_purged
+            ),
+        );
+        self.bump_generation();
+
+        Ok(Response::new(report))
+    }
+
+    /// Items an earlier snapshot load set aside as invalid, with why.
+    async fn get_quarantine(
+        &self,
+        request: Request<QuarantineRequest>,
+    ) -> Result<Response<Quarantine>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+
+        let quarantine = self
+            .locks
+            .lock(&self.quarantine, "get_quarantine", "")
+            .await;
+        Ok(Response::new(Quarantine {
+            items: quarantine.clone(),
+        }))
+    }
+
+    /// Items in a tax category, in SKU order.
+    async fn list_by_tax_category(
+        &self,
+        request: Request<TaxCategoryRequest>,
+    ) -> Result<Response<Items>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.tax_category.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_TAX_CATEGORY_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "list_by_tax_category", "")
+            .await;
+        let mut items = live_items(&map)
+            .filter(|item| item.tax_category == request.tax_category)
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        Ok(Response::new(Items {
+            items,
+            etag: self.etag(),
+            not_modified: false,
+        }))
+    }
+
+    /// Changes an item's tax category, or clears it when empty, leaving
+    /// stock and price alone.
+    async fn set_tax_category(
+        &self,
+        request: Request<TaxCategoryChange>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        if request.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        self.check_tax_category(&request.tax_category)?;
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "set_tax_category", &request.sku)
+            .await;
+        let item = match live_mut(&mut map, &request.sku) {
+            Some(item) => item,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+        check_lifecycle(item, Operation::Edit)?;
+        item.tax_category = request.tax_category;
+        item.updated_at = self.clock.now();
+        self.audit("set_tax_category", &request.sku, item.tax_category.clone());
+        self.bump_generation();
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    /// Moves an item to another lifecycle state, if `--state-transitions`
+    /// allows going there from its current one.
+    async fn transition_state(
+        &self,
+        request: Request<StateChange>,
+    ) -> Result<Response<InventoryChangeResponse>, Status> {
+        let _permit = self.mutation_permit().await?;
+        let request = request.into_inner();
+
+        if request.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let to = match LifecycleState::from_i32(request.state) {
+            Some(state) => state,
+            None => return Err(Status::invalid_argument(BAD_STATE_ERR)),
+        };
+
+        let mut map = self
+            .locks
+            .lock(&self.inventory, "transition_state", &request.sku)
+            .await;
+        let item = match live_mut(&mut map, &request.sku) {
+            Some(item) => item,
+            None => return Err(Status::not_found(NO_ITEM_ERR)),
+        };
+
+        let from = item.state();
+        if from == to {
+            return Err(Status::invalid_argument(SAME_STATE_ERR));
+        }
+        if !self.config.state_transitions.contains(&(from, to)) {
+            let message = format!(
+                "{}: {} to {}",
+                ILLEGAL_TRANSITION_ERR,
+                lifecycle::name(from),
+                lifecycle::name(to)
+            );
+            return Err(Status::failed_precondition(message));
+        }
+
+        item.set_state(to);
+        item.updated_at = self.clock.now();
+        self.audit(
+            "transition_state",
+            &request.sku,
+            format!("{} to {}", lifecycle::name(from), lifecycle::name(to)),
+        );
+        self.bump_generation();
+
+        Ok(Response::new(InventoryChangeResponse {
+            status: "success".into(),
+        }))
+    }
+
+    type WatchPriceChangesStream = Pin<Box<dyn Stream<Item = Result<PriceChange, Status>> + Send>>;
+
+    /// Streams changes to the price of the given SKUs, or of every item when
+    /// none are given. Quantity changes, new items and removals aren't
+    /// reported, and changes made between polls are seen as one.
+    async fn watch_price_changes(
+        &self,
+        request: Request<PriceWatchRequest>,
+    ) -> Result<Response<Self::WatchPriceChangesStream>, Status> {
+        self.check_ready()?;
+        self.check_stream_rate(&request)?;
+        let skus: HashSet<String> = request.into_inner().skus.into_iter().collect();
+        if skus.iter().any(String::is_empty) {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        self.check_batch(skus.len())?;
+
+        let prices = move |map: &HashMap<String, Item>| -> HashMap<String, f32> {
+            map.iter()
+                .filter(|(sku, item)| !item.deleted && (skus.is_empty() || skus.contains(*sku)))
+                .filter_map(|(sku, item)| Some((sku.clone(), item.stock.as_ref()?.price)))
+                .collect()
+        };
+        let mut seen = prices(
+            &*self
+                .locks
+                .lock(&self.inventory, "watch_price_changes", "")
+                .await,
+        );
+
+        let slot = self.watch_slot()?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        let inventory = self.clone();
+        tokio::spawn(async move {
+            let mut seen_generation = inventory.generation.load(AtomicOrdering::SeqCst);
+            while !tx.is_closed() {
+                tokio::time::sleep(WATCH_POLL).await;
+
+                let generation = inventory.generation.load(AtomicOrdering::SeqCst);
+                if generation == seen_generation {
+                    continue;
+                }
+                let current = match inventory.watch_lock("watch_price_changes", "").await {
+                    Some(map) => prices(&map),
+                    None => continue,
+                };
+                seen_generation = generation;
+                let mut changes: Vec<PriceChange> = current
+                    .iter()
+                    .filter_map(|(sku, &new_price)| {
+                        let old_price = *seen.get(sku)?;
+                        (old_price != new_price).then(|| PriceChange {
+                            sku: sku.clone(),
+                            old_price,
+                            new_price,
+                        })
+                    })
+                    .collect();
+                changes.sort_by(|a, b| a.sku.cmp(&b.sku));
+                seen = current;
+
+                for change in changes {
+                    if let Err(err) = tx.send(Ok(change)) {
+                        tracing::error!(error = ?err, "failed to update stream client");
+                        return;
+                    }
+                }
+            }
+        });
+
+        let stream = Guarded::new(UnboundedReceiverStream::new(rx), slot);
+        Ok(Response::new(
+            Box::pin(stream) as Self::WatchPriceChangesStream
+        ))
+    }
+
+    /// Pages through audit entries recorded at or after `start` and before
+    /// `end`, or without an upper bound when `end` is zero, in the order they
+    /// were recorded. The cursor is the audit file offset the next page
+    /// starts from.
+    async fn get_audit_log(
+        &self,
+        request: Request<AuditLogRequest>,
+    ) -> Result<Response<AuditLogPage>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+        let request = request.into_inner();
+
+        let path = match self.config.audit_file.clone() {
+            Some(path) => path,
+            None => return Err(Status::failed_precondition(NO_CHANGE_LOG_ERR)),
+        };
+        let from = if request.cursor.is_empty() {
+            0
+        } else {
+            match request.cursor.parse::<u64>() {
+                Ok(offset) => offset,
+                Err(_) => return Err(Status::invalid_argument(BAD_CURSOR_ERR)),
+            }
+        };
+        let end = match request.end {
+            0 => u64::MAX,
+            end => end,
+        };
+        let page_size = match request.page_size {
+            0 => usize::MAX,
+            size => size as usize,
+        };
+
+        let read = tokio::task::spawn_blocking(move || -> std::io::Result<AuditLogPage> {
+            let file = std::fs::File::open(path)?;
+            let mut page = AuditLogPage::default();
+            let lines = std::io::BufRead::lines(std::io::BufReader::new(file));
+            for (offset, line) in (0u64..).zip(lines).skip(from as usize) {
+                let entry = match audit::RecordedEntry::parse(&line?) {
+                    Some(entry) if entry.at >= request.start && entry.at < end => entry,
+                    Some(_) => continue,
+                    None => {
+                        tracing::error!(line = offset, "skipping malformed audit line");
+                        continue;
+                    }
+                };
+                if page.entries.len() == page_size {
+                    page.next_cursor = offset.to_string();
+                    break;
+                }
+                page.entries.push(ChangeEvent {
+                    offset,
+                    at: entry.at,
+                    rpc: entry.rpc,
+                    sku: entry.sku,
+                    detail: entry.detail,
+                    reason: entry.reason,
+                });
+            }
+            Ok(page)
+        })
+        .await;
+
+        match read {
+            Ok(Ok(page)) => Ok(Response::new(page)),
+            Ok(Err(err)) => Err(Status::internal(err.to_string())),
+            Err(err) => Err(Status::internal(err.to_string())),
+        }
+    }
+
+    /// A parent's variants in SKU order. The parent itself isn't included.
+    async fn list_variants(
+        &self,
+        request: Request<ItemIdentifier>,
+    ) -> Result<Response<Items>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "list_variants", &request.sku)
+            .await;
+        if live(&map, &request.sku).is_none() {
+            return Err(Status::not_found(NO_ITEM_ERR));
+        }
+        let mut items = variants(&map, &request.sku)
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        Ok(Response::new(Items {
+            items,
+            etag: self.etag(),
+            not_modified: false,
+        }))
+    }
+
+    /// Waits up to `timeout_ms` for the catalog to move past `since_version`,
+    /// then returns the items changed since then and the SKUs removed. On
+    /// timeout it returns nothing at the same version. When the change
+    /// history doesn't reach back far enough, or the version predates a
+    /// restart, every item is returned with `reset` set, and the client
+    /// should replace what it holds.
+    async fn poll_changes(
+        &self,
+        request: Request<PollRequest>,
+    ) -> Result<Response<PollResponse>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+        let wait = Duration::from_millis(request.timeout_ms).min(MAX_POLL_WAIT);
+        let deadline = tokio::time::Instant::now() + wait;
+
+        loop {
+            let notified = self.changes.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            // A version ahead of the server's comes from before a restart.
+            let version = self.generation.load(AtomicOrdering::SeqCst);
+            if version != request.since_version {
+                break;
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return Ok(Response::new(PollResponse {
+                    version,
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let map = self.locks.lock(&self.inventory, "poll_changes", "").await;
+        let mut response = PollResponse {
+            version: self.generation.load(AtomicOrdering::SeqCst),
+            ..Default::default()
+        };
+        let changed = if request.since_version > response.version {
+            None
+        } else {
+            self.changes.since(request.since_version)
+        };
+        match changed {
+            Some(skus) => {
+                for sku in skus {
+                    match live(&map, &sku) {
+                        Some(item) => {
+                            response
+                                .items
+                                .push(self.read_item(&map, item, include_margin)?)
+                        }
+                        None => response.removed.push(sku),
+                    }
+                }
+            }
+            None => {
+                response.reset = true;
+                response.items = live_items(&map)
+                    .map(|item| self.read_item(&map, item, include_margin))
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+
+        Ok(Response::new(response))
+    }
+
+    type StreamAllStream = Pin<Box<dyn Stream<Item = Result<Items, Status>> + Send>>;
+
+    /// The catalog as get_all returns it, in SKU order and split into messages
+    /// under the response size limit. Every message carries the same etag, and
+    /// all of them come from one snapshot taken under the inventory lock, so
+    /// the items passing `filter` are exported as of a single moment however
+    /// writes interleave. An item too large to fit the limit on its own is
+    /// still sent, alone.
+    async fn stream_all(
+        &self,
+        request: Request<ItemAll>,
+    ) -> Result<Response<Self::StreamAllStream>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let filter = request.into_inner().filter.unwrap_or_default();
+        let passes = export_filter(&filter)?;
+
+        let map = self.locks.lock(&self.inventory, "stream_all", "").await;
+        let etag = self.etag();
+        let mut items = live_items(&map)
+            .filter(|item| passes(item))
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(map);
+        items.sort_by(|a, b| item_sku(a).cmp(item_sku(b)));
+
+        let empty = Items {
+            items: Vec::new(),
+            etag,
+            not_modified: false,
+        };
+        let mut chunks = vec![empty.clone()];
+        let mut size = empty.encoded_len();
+        for item in items {
+            let item_size = prost::encoding::message::encoded_len(1, &item);
+            if size + item_size > self.config.max_response_size
+                && !chunks.last().unwrap().items.is_empty()
+            {
+                chunks.push(empty.clone());
+                size = empty.encoded_len();
+            }
+            size += item_size;
+            chunks.last_mut().unwrap().items.push(item);
+        }
+
+        let stream = tokio_stream::iter(chunks.into_iter().map(Ok));
+        Ok(Response::new(Box::pin(stream) as Self::StreamAllStream))
+    }
+
+    /// Items created between `start` and `end` seconds since the epoch,
+    /// both inclusive, oldest first. Items created in the same second come
+    /// in SKU order.
+    async fn get_created_between(
+        &self,
+        request: Request<CreatedRangeRequest>,
+    ) -> Result<Response<Items>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.start > request.end {
+            return Err(Status::invalid_argument(INVERTED_RANGE_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "get_created_between", "")
+            .await;
+        let mut items = live_items(&map)
+            .filter(|item| (request.start..=request.end).contains(&item.created_at))
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| item_sku(a).cmp(item_sku(b)))
+        });
+
+        Ok(Response::new(Items {
+            items,
+            etag: self.etag(),
+            not_modified: false,
+        }))
+    }
+
+    /// Items not changed in the last `older_than` seconds, least recently
+    /// changed first. Items changed in the same second come in SKU order.
+    async fn get_stale(&self, request: Request<StaleRequest>) -> Result<Response<Items>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let cutoff = self
+            .clock
+            .now()
+            .saturating_sub(request.get_ref().older_than);
+
+        let map = self.locks.lock(&self.inventory, "get_stale", "").await;
+        let mut items = live_items(&map)
+            .filter(|item| last_updated(item) < cutoff)
+            .map(|item| self.read_item(&map, item, include_margin))
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| {
+            last_updated(a)
+                .cmp(&last_updated(b))
+                .then_with(|| item_sku(a).cmp(item_sku(b)))
+        });
+
+        Ok(Response::new(Items {
+            items,
+            etag: self.etag(),
+            not_modified: false,
+        }))
+    }
+
+    /// Finds items by SKU or name. Substring mode matches either one containing
+    /// the query; fuzzy mode matches within `max_distance` edits of the SKU,
+    /// the name or a word of it, closest first. Both ignore case unless
+    /// `case_sensitive` is set, and scan the whole catalog under its lock, so
+    /// fuzzy searches cost roughly the catalog size times the query length
+    /// times the name length. Ties and substring matches come in SKU order.
+    async fn search(
+        &self,
+        request: Request<SearchRequest>,
+    ) -> Result<Response<SearchResults>, Status> {
+        self.check_ready()?;
+        let include_margin = self.include_margin(&request);
+        let request = request.into_inner();
+
+        if request.query.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_QUERY_ERR));
+        }
+
+        let max_distance = match request.max_distance {
+            0 => self.config.search_max_distance,
+            distance => distance.min(self.config.search_max_distance),
+        } as usize;
+        let limit = match request.limit {
+            0 => self.config.search_max_results,
+            limit => limit.min(self.config.search_max_results),
+        } as usize;
+        let case_sensitive = request.case_sensitive;
+        let query = search::fold_case(&request.query, case_sensitive);
+        let closeness = |item: &Item| match request.mode() {
+            SearchMode::Substring => {
+                let found = search::fold_case(item_sku(item), case_sensitive).contains(&*query)
+                    || search::fold_case(item_name(item), case_sensitive).contains(&*query);
+                found.then_some(0)
+            }
+            SearchMode::Fuzzy => search::closeness(
+                &query,
+                item_sku(item),
+                item_name(item),
+                max_distance,
+                case_sensitive,
+            ),
+        };
+
+        let map = self.locks.lock(&self.inventory, "search", "").await;
+        let mut found: Vec<(usize, &Item)> = live_items(&map)
+            .filter_map(|item| closeness(item).map(|distance| (distance, item)))
+            .collect();
+        found.sort_by(|(a_distance, a), (b_distance, b)| {
+            a_distance
+                .cmp(b_distance)
+                .then_with(|| item_sku(a).cmp(item_sku(b)))
+        });
+        let matches = found
+            .into_iter()
+            .take(limit)
+            .map(|(distance, item)| {
+                Ok(SearchMatch {
+                    item: Some(self.read_item(&map, item, include_margin)?),
+                    distance: distance as u32,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        Ok(Response::new(SearchResults { matches }))
+    }
+
+    /// Prices a cart without touching stock or reservations. Lines are
+    /// priced in the feed currency, and each requested currency from the
+    /// price list named by its code, which every line's item must have.
+    /// Totals are summed in the currency's minor units, so they're exact;
+    /// the discount is rounded to a minor unit with the price rounding.
+    async fn quote_cart(&self, request: Request<QuoteRequest>) -> Result<Response<Quote>, Status> {
+        self.check_ready()?;
+        let request = request.into_inner();
+
+        if request.lines.is_empty() {
+            return Err(Status::invalid_argument(NO_SKUS_ERR));
+        }
+
+        self.check_batch(request.lines.len())?;
+
+        if !(0.0..=100.0).contains(&request.discount_percent) {
+            return Err(Status::invalid_argument(BAD_DISCOUNT_ERR));
+        }
+
+        let mut currencies = Vec::with_capacity(request.currencies.len());
+        for currency in &request.currencies {
+            match pricing::minor_unit_decimals(currency) {
+                Some(decimals) => currencies.push((currency.to_ascii_uppercase(), decimals)),
+                None => return Err(Status::invalid_argument(UNKNOWN_CURRENCY_ERR)),
+            }
+        }
+
+        let map = self.locks.lock(&self.inventory, "quote_cart", "").await;
+        let base = &self.config.feed.currency;
+        let base_decimals = pricing::minor_unit_decimals(base).unwrap_or(2);
+        let mut quote = Quote::default();
+        let mut subtotals = vec![0i128; currencies.len() + 1];
+        for line in &request.lines {
+            if line.sku.is_empty() {
+                return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+            }
+
+            let fail = |message: &str| format!("{}: {}", line.sku, message);
+            if line.quantity == 0 {
+                return Err(Status::invalid_argument(fail(ZERO_QUANT_ERR)));
+            }
+            let stock = match live(&map, &line.sku) {
+                Some(item) => item.stock.as_ref(),
+                None => return Err(Status::not_found(fail(NO_ITEM_ERR))),
+            };
+            let stock = match stock {
+                Some(stock) => stock,
+                None => return Err(Status::failed_precondition(fail(NO_PRICE_ERR))),
+            };
+
+            let subtotal = pricing::line_minor(stock.price, line.quantity, base_decimals);
+            subtotals[0] += subtotal;
+            for ((currency, decimals), total) in currencies.iter().zip(&mut subtotals[1..]) {
+                let price = match stock.prices.get(currency) {
+                    Some(price) => *price,
+                    None => {
+                        let message = format!("{} in {}", fail(NO_PRICE_ERR), currency);
+                        return Err(Status::failed_precondition(message));
+                    }
+                };
+                *total += pricing::line_minor(price, line.quantity, *decimals);
+            }
+
+            quote.lines.push(QuotedLine {
+                sku: line.sku.clone(),
+                quantity: line.quantity,
+                unit_price: pricing::minor_to_amount(
+                    pricing::line_minor(stock.price, 1, base_decimals),
+                    base_decimals,
+                ),
+                subtotal: pricing::minor_to_amount(subtotal, base_decimals),
+            });
+        }
+
+        let base = std::iter::once((base.to_ascii_uppercase(), base_decimals));
+        for ((currency, decimals), subtotal) in base.chain(currencies).zip(subtotals) {
+            let discount = pricing::percent_of_minor(
+                subtotal,
+                request.discount_percent,
+                self.config.price_rounding,
+            );
+            quote.totals.push(CurrencyTotal {
+                currency,
+                subtotal: pricing::minor_to_amount(subtotal, decimals),
+                discount: pricing::minor_to_amount(discount, decimals),
+                total: pricing::minor_to_amount(subtotal - discount, decimals),
+            });
+        }
+
+        Ok(Response::new(quote))
+    }
+
+    /// What restocking every item at or below its reorder point would cost,
+    /// per item in SKU order and in total. Each is reordered by its reorder
+    /// quantity, or when that's unset by enough to lift it just above the
+    /// reorder point, at its cost, or its price when the cost is unknown.
+    /// Items without a reorder point and bundles, whose stock is their
+    /// components', are never reordered.
+    async fn get_reorder_cost(
+        &self,
+        request: Request<ReorderCostRequest>,
+    ) -> Result<Response<ReorderCost>, Status> {
+        self.check_ready()?;
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+
+        let map = self
+            .locks
+            .lock(&self.inventory, "get_reorder_cost", "")
+            .await;
+        let mut low: Vec<(&str, &ItemStock)> = live_items(&map)
+            .filter(|item| item.bundle.is_empty())
+            .filter_map(|item| item.stock.as_ref().map(|stock| (item_sku(item), stock)))
+            .filter(|(_, stock)| stock.reorder_point > 0 && stock.quantity <= stock.reorder_point)
+            .collect();
+        low.sort_by_key(|(sku, _)| *sku);
+
+        let mut report = ReorderCost::default();
+        let mut total = 0;
+        for (sku, stock) in low {
+            let reorder = match stock.reorder_quantity {
+                0 => stock.reorder_point - stock.quantity + 1,
+                reorder => reorder,
+            };
+            let unit_cost = stock.cost.unwrap_or(stock.price);
+            let cents = pricing::line_cents(unit_cost, reorder);
+            total += cents;
+            report.lines.push(ReorderLine {
+                sku: sku.into(),
+                quantity: stock.quantity,
+                reorder,
+                unit_cost,
+                at_price: stock.cost.is_none(),
+                cost: pricing::cents_to_amount(cents),
+            });
+        }
+        report.total = pricing::cents_to_amount(total);
+
+        Ok(Response::new(report))
+    }
+
+    /// An item's prices from the audit log, oldest first: the price it was
+    /// added or upserted with, direct changes, schedulings, scheduled
+    /// changes as they applied and category adjustments, each with the
+    /// reason it was made for, if one was given.
+    async fn get_price_history(
+        &self,
+        request: Request<ItemIdentifier>,
+    ) -> Result<Response<PriceHistory>, Status> {
+        if !self.is_admin(&request) {
+            return Err(Status::permission_denied(NOT_ADMIN_ERR));
+        }
+        let sku = request.into_inner().sku;
+        if sku.is_empty() {
+            return Err(Status::invalid_argument(EMPTY_SKU_ERR));
+        }
+
+        let path = match self.config.audit_file.clone() {
+            Some(path) => path,
+            None => return Err(Status::failed_precondition(NO_CHANGE_LOG_ERR)),
+        };
+
+        let read = tokio::task::spawn_blocking(move || -> std::io::Result<PriceHistory> {
+            let file = std::fs::File::open(path)?;
+            let mut history = PriceHistory::default();
+            let lines = std::io::BufRead::lines(std::io::BufReader::new(file));
+            for (offset, line) in (0u64..).zip(lines) {
+                let entry = match audit::RecordedEntry::parse(&line?) {
+                    Some(entry) => entry,
+                    None => {
+                        tracing::error!(line = offset, "skipping malformed audit line");
+                        continue;
+                    }
+                };
+                if !PRICE_SETTING_RPCS.contains(&entry.rpc.as_str()) || entry.sku != sku {
+                    continue;
+                }
+                history.changes.push(ChangeEvent {
+                    offset,
+                    at: entry.at,
+                    rpc: entry.rpc,
+                    sku: entry.sku,
+                    detail: entry.detail,
+                    reason: entry.reason,
+                });
+            }
+            Ok(history)
+        })
+        .await;
+
+        match read {
+            Ok(Ok(history)) => Ok(Response::new(history)),
+            Ok(Err(err)) => Err(Status::internal(err.to_string())),
+            Err(err) => Err(Status::internal(err.to_string())),
+        }
+    }
 }
+
+#[cfg(test)]
+mod tests;
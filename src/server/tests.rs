@@ -0,0 +1,4321 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use clap::Parser;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Request, Status};
+
+use super::StoreInventory;
+use crate::audit::{AuditLog, FileSink, RecordedEntry};
+use crate::backend::{Backend, BackendError, Journaled, SnapshotFile};
+use crate::clock::Clock;
+use crate::config::Config;
+use crate::deadlines::DeadlineLayer;
+use crate::signing;
+use crate::store::inventory_client::InventoryClient;
+use crate::store::inventory_server::InventoryServer;
+use crate::store::session_request::Command;
+use crate::store::session_response::Outcome;
+use crate::store::{
+    AuditLogRequest, Availability, BundleLine, CancelScheduleRequest, CategoryListRequest,
+    CategoryRepriceRequest, CategorySort, ChangeEvent, ClaimIdRequest, ClaimLine, ClaimRequest,
+    CompactRequest, ConsistencyRequest, CreatedRangeRequest, DescribeRequest, ExportChangesRequest,
+    ExportFilter, FeedRequest, FieldDescription, FormatPriceRequest, FormatPriceResponse,
+    GetManyRequest, HistogramRequest, IndexIssue, InventoryUpdateResponse, Item, ItemAll,
+    ItemIdentifier, ItemInformation, ItemStock, Items, LifecycleState, MaintenanceRequest,
+    MetricsRequest, NameChangeRequest, PingRequest, PollRequest, PollResponse, PriceAdjustRequest,
+    PriceChange, PriceChangeRequest, PriceWatchRequest, QuantityChangeRequest, QuantityHistogram,
+    QuarantineRequest, Quote, QuoteLine, QuoteRequest, ReleaseRequest, ReorderCostRequest,
+    ReserveRequest, RoundPriceRequest, SearchMatch, SearchMode, SearchRequest, SessionRequest,
+    SlugRequest, Snapshot, StaleRequest, StateChange, StockCount, TagMatch, TagRequest,
+    TagsRequest, TaxCategoryChange, TaxCategoryRequest, TopRequest, TotalValueRequest, Unit,
+    UpsertOutcome, UpsertRequest, UpsertResponse, WatchManyRequest, WeightedRandomRequest,
+};
+use crate::wal::Wal;
+use crate::webhooks::{DeadLetters, WebhookConfig, Webhooks, SIGNATURE_HEADER};
+
+const ADMIN_TOKEN: &str = "secret";
+const START: u64 = 1_700_000_000;
+
+/// A clock tests move by hand.
+#[derive(Debug)]
+struct TestClock(AtomicU64);
+
+impl TestClock {
+    fn advance(&self, secs: u64) {
+        self.0.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// A server on a free local port, served the way main serves it, with a
+/// client connected to it and a handle on its inventory and clock.
+struct Fixture {
+    client: InventoryClient<Channel>,
+    inventory: StoreInventory,
+    clock: Arc<TestClock>,
+}
+
+/// Starts a server configured by `args`, as if passed on the command line.
+/// The admin token is always `ADMIN_TOKEN`.
+async fn start(args: &[&str]) -> Fixture {
+    let config = Config::parse_from(
+        ["server", "--admin-token", ADMIN_TOKEN]
+            .into_iter()
+            .chain(args.iter().copied()),
+    );
+    let clock = Arc::new(TestClock(AtomicU64::new(START)));
+    let deadlines = DeadlineLayer::new(&config.method_deadlines);
+    let audit = match config.audit_file.as_ref() {
+        Some(path) => AuditLog::spawn(FileSink::open(path).unwrap(), config.audit_queue_capacity),
+        None => AuditLog::default(),
+    };
+    let webhooks = if config.webhook_urls.is_empty() {
+        Webhooks::default()
+    } else {
+        let webhook_config = WebhookConfig {
+            urls: config.webhook_urls.clone(),
+            secret: config.webhook_secret.clone(),
+            timeout: std::time::Duration::from_millis(config.webhook_timeout),
+            retries: config.webhook_retries,
+            events: config.webhook_events.clone(),
+        };
+        let dead_letters = config
+            .webhook_dead_letter_file
+            .as_ref()
+            .map(|path| DeadLetters::open(path).unwrap());
+        Webhooks::spawn(webhook_config, config.webhook_queue_capacity, dead_letters)
+    };
+    let inventory = StoreInventory::new(config, clock.clone())
+        .with_audit(audit)
+        .with_webhooks(webhooks);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .layer(deadlines)
+            .add_service(InventoryServer::new(inventory.clone()))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let client = InventoryClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    Fixture {
+        client,
+        inventory,
+        clock,
+    }
+}
+
+fn item(sku: &str, price: f32, quantity: u64) -> Item {
+    Item {
+        identifier: Some(id(sku)),
+        stock: Some(ItemStock {
+            price,
+            quantity,
+            ..Default::default()
+        }),
+        information: Some(ItemInformation {
+            name: Some(sku.to_uppercase()),
+            description: None,
+        }),
+        ..Default::default()
+    }
+}
+
+fn change(sku: &str, quantity: u64) -> QuantityChangeRequest {
+    QuantityChangeRequest {
+        sku: sku.into(),
+        quantity,
+        ..Default::default()
+    }
+}
+
+fn id(sku: &str) -> ItemIdentifier {
+    ItemIdentifier { sku: sku.into() }
+}
+
+/// A request carrying the admin token.
+fn admin<T>(message: T) -> Request<T> {
+    with_header(message, "x-admin-token", ADMIN_TOKEN)
+}
+
+fn with_header<T>(message: T, key: &'static str, value: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert(key, value.parse().unwrap());
+    request
+}
+
+fn code<T: std::fmt::Debug>(result: Result<T, Status>) -> Code {
+    result.unwrap_err().code()
+}
+
+impl Fixture {
+    async fn add(&mut self, item: Item) {
+        self.client.add(item).await.unwrap();
+    }
+
+    async fn get(&mut self, sku: &str) -> Result<Item, Status> {
+        self.client.get(id(sku)).await.map(|r| r.into_inner())
+    }
+
+    async fn stock(&mut self, sku: &str) -> ItemStock {
+        self.get(sku).await.unwrap().stock.unwrap()
+    }
+}
+
+// synth-153: expired items are evicted by the sweeper.
+
+#[tokio::test]
+async fn sweep_evicts_items_past_their_ttl() {
+    let mut fx = start(&[]).await;
+    fx.add(Item {
+        ttl: 60,
+        ..item("short", 1.0, 1)
+    })
+    .await;
+    fx.add(item("forever", 1.0, 1)).await;
+
+    fx.clock.advance(59);
+    fx.inventory.sweep().await;
+    assert!(fx.get("short").await.is_ok());
+
+    fx.clock.advance(1);
+    fx.inventory.sweep().await;
+    assert_eq!(code(fx.get("short").await), Code::NotFound);
+    assert!(fx.get("forever").await.is_ok());
+}
+
+#[tokio::test]
+async fn swept_items_reach_their_watchers_as_deletions() {
+    let mut fx = start(&[]).await;
+    fx.add(Item {
+        ttl: 60,
+        ..item("short", 1.0, 1)
+    })
+    .await;
+    fx.add(item("forever", 1.0, 1)).await;
+
+    let mut watch = fx.client.watch(id("short")).await.unwrap().into_inner();
+    let many = tokio_stream::iter([watch_request(&["short", "forever"], &[])]);
+    let mut events = fx.client.watch_many(many).await.unwrap().into_inner();
+
+    fx.clock.advance(60);
+    fx.inventory.sweep().await;
+
+    let evicted = loop {
+        match watch.message().await {
+            Ok(Some(_)) => continue,
+            result => break result,
+        }
+    };
+    assert_eq!(code(evicted), Code::NotFound);
+    let event = events.message().await.unwrap().unwrap();
+    assert_eq!(event.sku, "short");
+    assert!(event.removed);
+    assert!(event.item.is_none());
+}
+
+// synth-153~2: quantities above --max-quantity are refused unless an admin
+// overrides the limit.
+
+#[tokio::test]
+async fn increase_past_max_quantity_is_out_of_range() {
+    let mut fx = start(&["--max-quantity", "100"]).await;
+    fx.add(item("a", 1.0, 90)).await;
+
+    let result = fx.client.increase_quantity(change("a", 11)).await;
+    assert_eq!(code(result), Code::OutOfRange);
+    assert_eq!(fx.stock("a").await.quantity, 90);
+
+    fx.client.increase_quantity(change("a", 10)).await.unwrap();
+    assert_eq!(fx.stock("a").await.quantity, 100);
+}
+
+#[tokio::test]
+async fn admin_can_override_max_quantity() {
+    let mut fx = start(&["--max-quantity", "100"]).await;
+    fx.add(item("a", 1.0, 90)).await;
+
+    let plain = with_header(change("a", 50), "x-override-max-quantity", "1");
+    assert_eq!(
+        code(fx.client.increase_quantity(plain).await),
+        Code::OutOfRange
+    );
+
+    let mut overridden = admin(change("a", 50));
+    overridden
+        .metadata_mut()
+        .insert("x-override-max-quantity", "1".parse().unwrap());
+    fx.client.increase_quantity(overridden).await.unwrap();
+    assert_eq!(fx.stock("a").await.quantity, 140);
+}
+
+#[tokio::test]
+async fn unconfigured_max_only_guards_overflow() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, u64::MAX - 1)).await;
+
+    let result = fx.client.increase_quantity(change("a", 2)).await;
+    assert_eq!(code(result), Code::OutOfRange);
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+}
+
+// synth-154: the JSON feed derives availability from stock.
+
+#[tokio::test]
+async fn feed_renders_each_availability() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.5, 3)).await;
+    fx.add(item("b", 2.0, 0)).await;
+    let mut backordered = item("c", 3.0, 0);
+    backordered.stock.as_mut().unwrap().backorder = true;
+    fx.add(backordered).await;
+
+    let feed = fx.client.export_feed(FeedRequest::default()).await.unwrap();
+    assert_eq!(
+        feed.into_inner().json,
+        concat!(
+            r#"[{"id":"a","title":"A","price":1.5,"availability":"in stock","currency":"USD"},"#,
+            r#"{"id":"b","title":"B","price":2,"availability":"out of stock","currency":"USD"},"#,
+            r#"{"id":"c","title":"C","price":3,"availability":"backorder","currency":"USD"}]"#,
+        )
+    );
+}
+
+#[tokio::test]
+async fn feed_filters_to_in_stock_items() {
+    let mut fx = start(&["--feed-id-key", "sku"]).await;
+    fx.add(item("a", 1.0, 3)).await;
+    fx.add(item("b", 1.0, 0)).await;
+
+    let request = FeedRequest {
+        in_stock_only: true,
+        ..Default::default()
+    };
+    let json = fx
+        .client
+        .export_feed(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .json;
+    assert!(json.starts_with(r#"[{"sku":"a","#));
+    assert!(!json.contains(r#""b""#));
+}
+
+// synth-154~2: an interactive session answers commands in order.
+
+#[tokio::test]
+async fn interactive_session_answers_in_order() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    let commands = vec![
+        SessionRequest {
+            id: 1,
+            command: Some(Command::Get(id("a"))),
+        },
+        SessionRequest {
+            id: 2,
+            command: Some(Command::Decrease(change("a", 2))),
+        },
+        SessionRequest {
+            id: 3,
+            command: Some(Command::Get(id("missing"))),
+        },
+    ];
+    let mut responses = fx
+        .client
+        .interactive(tokio_stream::iter(commands))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let first = responses.message().await.unwrap().unwrap();
+    assert_eq!(first.id, 1);
+    assert!(
+        matches!(first.outcome, Some(Outcome::Item(item)) if item.stock.as_ref().unwrap().quantity == 5)
+    );
+
+    let second = responses.message().await.unwrap().unwrap();
+    assert_eq!(second.id, 2);
+    assert!(matches!(second.outcome, Some(Outcome::Update(update)) if update.quantity == 3));
+
+    let third = responses.message().await.unwrap().unwrap();
+    assert_eq!(third.id, 3);
+    assert!(
+        matches!(third.outcome, Some(Outcome::Error(error)) if error.code == Code::NotFound as i32)
+    );
+}
+
+// synth-155: loading retries transient backend errors.
+
+/// Times out `blips` times, then loads `items`.
+#[derive(Debug)]
+struct FlakyBackend {
+    blips: AtomicU64,
+    items: Vec<Item>,
+}
+
+impl Backend for FlakyBackend {
+    fn load(&self) -> Result<Snapshot, BackendError> {
+        let left = self.blips.load(Ordering::SeqCst);
+        if left > 0 {
+            self.blips.store(left - 1, Ordering::SeqCst);
+            return Err(BackendError::Transient("timed out".into()));
+        }
+        Ok(Snapshot {
+            items: self.items.clone(),
+            ..Default::default()
+        })
+    }
+}
+
+impl Fixture {
+    /// Waits for a background load to finish.
+    async fn ready(&mut self) -> bool {
+        for _ in 0..100 {
+            let ping = self.client.ping(PingRequest::default()).await.unwrap();
+            if ping.into_inner().ready {
+                return true;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        false
+    }
+}
+
+#[tokio::test]
+async fn load_succeeds_after_transient_backend_errors() {
+    let mut fx = start(&["--backend-retry-backoff", "1"]).await;
+    fx.inventory.spawn_load(Arc::new(FlakyBackend {
+        blips: AtomicU64::new(2),
+        items: vec![item("a", 1.0, 1)],
+    }));
+
+    assert!(fx.ready().await);
+    assert!(fx.get("a").await.is_ok());
+}
+
+#[tokio::test]
+async fn load_gives_up_once_retries_are_exhausted() {
+    let mut fx = start(&[
+        "--backend-retry-backoff",
+        "1",
+        "--backend-retry-attempts",
+        "2",
+    ])
+    .await;
+    let backend = Arc::new(FlakyBackend {
+        blips: AtomicU64::new(2),
+        items: vec![item("a", 1.0, 1)],
+    });
+    fx.inventory.spawn_load(backend.clone());
+
+    assert!(!fx.ready().await);
+    assert_eq!(backend.blips.load(Ordering::SeqCst), 0);
+    assert_eq!(code(fx.get("a").await), Code::Unavailable);
+}
+
+// synth-155~2: weighted random picks favour heavy items and skip weightless
+// ones.
+
+#[tokio::test]
+async fn weighted_random_favours_heavy_items() {
+    let mut fx = start(&[]).await;
+    for (sku, weight) in [("heavy", 50), ("light", 1), ("hidden", 0)] {
+        fx.add(Item {
+            featured_weight: weight,
+            ..item(sku, 1.0, 1)
+        })
+        .await;
+    }
+
+    let mut heavy = 0;
+    for seed in 0..100 {
+        let request = WeightedRandomRequest {
+            count: 1,
+            seed: Some(seed),
+        };
+        let picked = fx.client.get_weighted_random(request).await.unwrap();
+        let picked = picked.into_inner().items;
+        assert_eq!(picked.len(), 1);
+        match picked[0].identifier.as_ref().unwrap().sku.as_str() {
+            "heavy" => heavy += 1,
+            "light" => {}
+            sku => panic!("picked {}", sku),
+        }
+    }
+    assert!(heavy > 90, "heavy picked {} times", heavy);
+}
+
+#[tokio::test]
+async fn weighted_random_is_deterministic_for_a_seed() {
+    let mut fx = start(&[]).await;
+    for (i, sku) in ["a", "b", "c", "d"].into_iter().enumerate() {
+        fx.add(Item {
+            featured_weight: i as u32 + 1,
+            ..item(sku, 1.0, 1)
+        })
+        .await;
+    }
+
+    let request = WeightedRandomRequest {
+        count: 4,
+        seed: Some(7),
+    };
+    let first = fx
+        .client
+        .get_weighted_random(request.clone())
+        .await
+        .unwrap();
+    let second = fx.client.get_weighted_random(request).await.unwrap();
+    assert_eq!(first.get_ref().items.len(), 4);
+    assert_eq!(first.into_inner(), second.into_inner());
+}
+
+// synth-156: the reject policy keeps prices at or above cost.
+
+fn costed(sku: &str, price: f32, cost: f32) -> Item {
+    let mut item = item(sku, price, 1);
+    item.stock.as_mut().unwrap().cost = Some(cost);
+    item
+}
+
+fn price(sku: &str, price: f32) -> PriceChangeRequest {
+    PriceChangeRequest {
+        sku: sku.into(),
+        price,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn price_below_cost_is_rejected() {
+    let mut fx = start(&["--below-cost-policy", "reject"]).await;
+    fx.add(costed("a", 10.0, 5.0)).await;
+
+    let status = fx.client.update_price(price("a", 4.0)).await.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert!(status.message().contains("cost 5 attempted price 4"));
+    assert_eq!(fx.stock("a").await.price, 10.0);
+
+    fx.client.update_price(price("a", 6.0)).await.unwrap();
+    assert_eq!(fx.stock("a").await.price, 6.0);
+}
+
+#[tokio::test]
+async fn admin_can_price_below_cost_deliberately() {
+    let mut fx = start(&["--below-cost-policy", "reject"]).await;
+    fx.add(costed("a", 10.0, 5.0)).await;
+
+    let plain = with_header(price("a", 4.0), "x-allow-below-cost", "1");
+    assert_eq!(
+        code(fx.client.update_price(plain).await),
+        Code::FailedPrecondition
+    );
+
+    let mut overridden = admin(price("a", 4.0));
+    overridden
+        .metadata_mut()
+        .insert("x-allow-below-cost", "1".parse().unwrap());
+    fx.client.update_price(overridden).await.unwrap();
+    assert_eq!(fx.stock("a").await.price, 4.0);
+}
+
+#[tokio::test]
+async fn percent_adjustment_below_cost_is_rejected() {
+    let mut fx = start(&["--below-cost-policy", "reject"]).await;
+    fx.add(Item {
+        category: "tools".into(),
+        ..costed("a", 10.0, 9.0)
+    })
+    .await;
+    fx.add(Item {
+        category: "tools".into(),
+        ..item("uncosted", 10.0, 1)
+    })
+    .await;
+
+    let request = PriceAdjustRequest {
+        category: "tools".into(),
+        percent: -20.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        code(fx.client.adjust_price_percent(request).await),
+        Code::FailedPrecondition
+    );
+    assert_eq!(fx.stock("a").await.price, 10.0);
+    assert_eq!(fx.stock("uncosted").await.price, 10.0);
+}
+
+// synth-156~2: watching an empty SKU fails up front.
+
+impl Fixture {
+    async fn active_watches(&mut self) -> u64 {
+        let metrics = self.client.get_metrics(MetricsRequest {}).await.unwrap();
+        metrics.into_inner().active_watch_streams
+    }
+}
+
+#[tokio::test]
+async fn watch_of_empty_sku_is_invalid_and_starts_nothing() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 1)).await;
+
+    let status = fx.client.watch(id("")).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), "provided SKU was empty");
+    assert_eq!(fx.active_watches().await, 0);
+
+    let _stream = fx.client.watch(id("a")).await.unwrap();
+    assert_eq!(fx.active_watches().await, 1);
+}
+
+// synth-157: top items by value, for any k.
+
+fn skus(items: &[Item]) -> Vec<&str> {
+    items
+        .iter()
+        .map(|item| item.identifier.as_ref().unwrap().sku.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn top_by_value_for_each_k() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    fx.add(item("b", 5.0, 10)).await;
+    fx.add(item("c", 2.0, 25)).await;
+    fx.add(item("d", 3.0, 1)).await;
+
+    let top = |k| {
+        let mut client = fx.client.clone();
+        async move {
+            let items = client.get_top_by_value(TopRequest { k }).await.unwrap();
+            items.into_inner().items
+        }
+    };
+    assert_eq!(skus(&top(2).await), ["b", "c"]);
+    assert_eq!(skus(&top(4).await), ["b", "c", "a", "d"]);
+    assert_eq!(skus(&top(10).await), ["b", "c", "a", "d"]);
+    assert!(top(0).await.is_empty());
+    assert_eq!(top(u32::MAX).await.len(), 4);
+}
+
+// synth-157~2: get_all answers not_modified only for the same request shape.
+
+async fn get_all(fx: &mut Fixture, request: Request<ItemAll>) -> Items {
+    fx.client.get_all(request).await.unwrap().into_inner()
+}
+
+#[tokio::test]
+async fn get_all_etag_covers_filter_headers_and_due_schedules() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    fx.add(item("b", 5.0, 10)).await;
+
+    let first = get_all(&mut fx, Request::new(ItemAll::default())).await;
+    let etag = first.etag.clone();
+    let again = ItemAll {
+        etag: etag.clone(),
+        filter: None,
+    };
+    assert!(
+        get_all(&mut fx, Request::new(again.clone()))
+            .await
+            .not_modified
+    );
+
+    let filtered = ItemAll {
+        etag: etag.clone(),
+        filter: Some(ExportFilter {
+            min_price: Some(2.0),
+            ..Default::default()
+        }),
+    };
+    let response = get_all(&mut fx, Request::new(filtered)).await;
+    assert!(!response.not_modified);
+    assert_eq!(skus(&response.items), ["b"]);
+
+    let rollup = with_header(again.clone(), "x-rollup-variants", "true");
+    assert!(!get_all(&mut fx, rollup).await.not_modified);
+
+    let mut scheduled = price("a", 3.0);
+    scheduled.effective_at = START + 60;
+    fx.client.update_price(scheduled).await.unwrap();
+    let etag = get_all(&mut fx, Request::new(ItemAll::default()))
+        .await
+        .etag;
+    let unchanged = ItemAll { etag, filter: None };
+    assert!(
+        get_all(&mut fx, Request::new(unchanged.clone()))
+            .await
+            .not_modified
+    );
+
+    // Due but not yet swept: reads apply it, so the old etag is stale.
+    fx.clock.advance(60);
+    let response = get_all(&mut fx, Request::new(unchanged)).await;
+    assert!(!response.not_modified);
+    assert_eq!(response.items.len(), 2);
+}
+
+// synth-158: previewing a category reprice.
+
+fn in_category(category: &str, item: Item) -> Item {
+    Item {
+        category: category.into(),
+        ..item
+    }
+}
+
+#[tokio::test]
+async fn reprice_preview_matches_the_reprice_and_changes_nothing() {
+    let mut fx = start(&[]).await;
+    fx.add(in_category("tools", costed("a", 10.0, 6.0))).await;
+    fx.add(in_category("tools", item("b", 3.99, 2))).await;
+    fx.add(in_category("toys", item("c", 7.0, 1))).await;
+
+    let request = CategoryRepriceRequest {
+        category: "tools".into(),
+        percent: -15.0,
+    };
+    let preview = fx
+        .client
+        .preview_category_reprice(request)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(fx.stock("a").await.price, 10.0);
+    assert_eq!(fx.stock("b").await.price, 3.99);
+
+    let reprice = PriceAdjustRequest {
+        category: "tools".into(),
+        percent: -15.0,
+        ..Default::default()
+    };
+    let applied = fx.client.adjust_price_percent(reprice).await.unwrap();
+    assert_eq!(preview.lines, applied.into_inner().lines);
+    assert_eq!(preview.lines.len(), 2);
+    for line in &preview.lines {
+        assert_eq!(fx.stock(&line.sku).await.price, line.new_price);
+    }
+    assert_eq!(fx.stock("c").await.price, 7.0);
+
+    let quantity = fx.stock("a").await.quantity as f64;
+    let a = &preview.lines[0];
+    assert_eq!(preview.margin_before, (10.0 - 6.0) * quantity);
+    assert_eq!(preview.margin_after, (a.new_price as f64 - 6.0) * quantity);
+}
+
+// synth-158~2: one policy for items without stock.
+
+impl Fixture {
+    /// Puts an item straight into the map, skipping add's validation.
+    async fn insert(&self, item: Item) {
+        let sku = item.identifier.as_ref().unwrap().sku.clone();
+        self.inventory.inventory.lock().await.insert(sku, item);
+    }
+}
+
+fn stockless(sku: &str) -> Item {
+    Item {
+        stock: None,
+        ..item(sku, 1.0, 1)
+    }
+}
+
+#[tokio::test]
+async fn strict_policy_fails_every_rpc_on_missing_stock() {
+    let mut fx = start(&["--missing-stock-policy", "strict"]).await;
+    fx.insert(stockless("a")).await;
+
+    assert_eq!(code(fx.get("a").await), Code::Internal);
+    let all = fx.client.get_all(ItemAll::default()).await;
+    assert_eq!(code(all), Code::Internal);
+    let decrease = fx.client.decrease_quantity(change("a", 1)).await;
+    assert_eq!(code(decrease), Code::Internal);
+    let update = fx.client.update_price(price("a", 2.0)).await;
+    assert_eq!(code(update), Code::Internal);
+}
+
+#[tokio::test]
+async fn lenient_policy_treats_missing_stock_as_empty() {
+    let mut fx = start(&["--missing-stock-policy", "lenient"]).await;
+    fx.insert(stockless("a")).await;
+
+    assert_eq!(fx.stock("a").await, ItemStock::default());
+    let all = fx.client.get_all(ItemAll::default()).await.unwrap();
+    assert_eq!(all.into_inner().items[0].stock, Some(ItemStock::default()));
+    let decrease = fx.client.decrease_quantity(change("a", 1)).await;
+    assert_eq!(code(decrease), Code::InvalidArgument);
+    fx.client.update_price(price("a", 2.0)).await.unwrap();
+    assert_eq!(fx.stock("a").await.price, 2.0);
+}
+
+// synth-159: ping echoes the nonce.
+
+#[tokio::test]
+async fn ping_echoes_nonce_and_server_time() {
+    let mut fx = start(&[]).await;
+    let response = fx.client.ping(PingRequest { nonce: 42 }).await.unwrap();
+    let response = response.into_inner();
+    assert_eq!(response.nonce, 42);
+    assert_eq!(response.server_time, START);
+}
+
+// synth-159~2: a redelivered increase is applied once.
+
+fn operation(sku: &str, quantity: u64, id: &str) -> QuantityChangeRequest {
+    QuantityChangeRequest {
+        operation_id: id.into(),
+        ..change(sku, quantity)
+    }
+}
+
+#[tokio::test]
+async fn repeated_operation_id_increases_once() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    let first = fx
+        .client
+        .increase_quantity(operation("a", 5, "receipt-1"))
+        .await;
+    let again = fx
+        .client
+        .increase_quantity(operation("a", 5, "receipt-1"))
+        .await;
+    assert_eq!(first.unwrap().into_inner(), again.unwrap().into_inner());
+    assert_eq!(fx.stock("a").await.quantity, 15);
+
+    fx.client
+        .increase_quantity(operation("a", 5, "receipt-2"))
+        .await
+        .unwrap();
+    assert_eq!(fx.stock("a").await.quantity, 20);
+}
+
+#[tokio::test]
+async fn operation_ids_are_forgotten_after_their_ttl() {
+    let mut fx = start(&["--operation-id-ttl", "60"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    fx.client
+        .increase_quantity(operation("a", 5, "receipt-1"))
+        .await
+        .unwrap();
+    fx.clock.advance(60);
+    fx.client
+        .increase_quantity(operation("a", 5, "receipt-1"))
+        .await
+        .unwrap();
+    assert_eq!(fx.stock("a").await.quantity, 20);
+}
+
+// synth-160: tombstones are hidden from get unless an admin asks.
+
+#[tokio::test]
+async fn removed_item_is_only_visible_to_admins() {
+    let mut fx = start(&["--soft-delete"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    fx.client.remove(id("a")).await.unwrap();
+
+    assert_eq!(code(fx.get("a").await), Code::NotFound);
+    let unauthorized = with_header(id("a"), "x-include-deleted", "true");
+    assert_eq!(code(fx.client.get(unauthorized).await), Code::NotFound);
+
+    let mut request = admin(id("a"));
+    request
+        .metadata_mut()
+        .insert("x-include-deleted", "true".parse().unwrap());
+    let item = fx.client.get(request).await.unwrap().into_inner();
+    assert!(item.deleted);
+    assert_eq!(item.stock.unwrap().quantity, 10);
+}
+
+// synth-160~2: mutations beyond the permit count are shed or queued.
+
+impl Fixture {
+    /// Takes one of the mutation permits, until the guard is dropped.
+    async fn hold_permit(&self) -> tokio::sync::OwnedSemaphorePermit {
+        let mutations = self.inventory.mutations.clone().unwrap();
+        mutations.acquire_owned().await.unwrap()
+    }
+}
+
+#[tokio::test]
+async fn saturated_mutations_are_shed() {
+    let mut fx = start(&["--max-concurrent-mutations", "1"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    let permit = fx.hold_permit().await;
+    let shed = fx.client.increase_quantity(change("a", 1)).await;
+    assert_eq!(code(shed), Code::ResourceExhausted);
+    assert_eq!(fx.stock("a").await.quantity, 10);
+
+    drop(permit);
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    assert_eq!(fx.stock("a").await.quantity, 11);
+}
+
+#[tokio::test]
+async fn queued_mutations_wait_for_a_permit() {
+    let fx = start(&[
+        "--max-concurrent-mutations",
+        "1",
+        "--mutation-overload",
+        "queue",
+        "--mutation-queue-timeout",
+        "5000",
+    ])
+    .await;
+    let mut client = fx.client.clone();
+    client.add(item("a", 1.0, 10)).await.unwrap();
+
+    let permit = fx.hold_permit().await;
+    let queued = tokio::spawn(async move { client.increase_quantity(change("a", 1)).await });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    assert!(!queued.is_finished());
+
+    drop(permit);
+    queued.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn queued_mutations_are_shed_after_the_timeout() {
+    let mut fx = start(&[
+        "--max-concurrent-mutations",
+        "1",
+        "--mutation-overload",
+        "queue",
+        "--mutation-queue-timeout",
+        "20",
+    ])
+    .await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    let _permit = fx.hold_permit().await;
+    let shed = fx.client.increase_quantity(change("a", 1)).await;
+    assert_eq!(code(shed), Code::ResourceExhausted);
+}
+
+// synth-161: tagging and untagging items in bulk.
+
+fn tag(tag: &str, skus: &[&str]) -> TagRequest {
+    TagRequest {
+        tag: tag.into(),
+        skus: skus.iter().map(|&sku| sku.into()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn bulk_tagging_dedupes_and_reports_each_sku() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.add(Item {
+        tags: vec!["sale".into()],
+        ..item("b", 1.0, 1)
+    })
+    .await;
+
+    let added = fx
+        .client
+        .add_tag_to_items(tag("sale", &["a", "b", "missing"]))
+        .await
+        .unwrap();
+    let success: Vec<_> = added
+        .into_inner()
+        .results
+        .iter()
+        .map(|r| r.success)
+        .collect();
+    assert_eq!(success, [true, true, false]);
+    assert_eq!(fx.get("a").await.unwrap().tags, ["sale"]);
+    assert_eq!(fx.get("b").await.unwrap().tags, ["sale"]);
+
+    fx.client
+        .remove_tag_from_items(tag("sale", &["a", "b"]))
+        .await
+        .unwrap();
+    assert!(fx.get("a").await.unwrap().tags.is_empty());
+    assert!(fx.get("b").await.unwrap().tags.is_empty());
+}
+
+// synth-161~2: one stream watching several SKUs.
+
+fn watch_request(skus: &[&str], unsubscribe: &[&str]) -> WatchManyRequest {
+    WatchManyRequest {
+        skus: skus.iter().map(|&sku| sku.into()).collect(),
+        unsubscribe: unsubscribe.iter().map(|&sku| sku.into()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn watch_many_streams_events_for_each_sku() {
+    let mut fx = start(&[]).await;
+    for sku in ["a", "b", "c"] {
+        fx.add(item(sku, 1.0, 10)).await;
+    }
+
+    let (requests, rx) = mpsc::channel(4);
+    requests
+        .send(watch_request(&["a", "b", "c"], &[]))
+        .await
+        .unwrap();
+    let mut events = fx
+        .client
+        .watch_many(ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+
+    for sku in ["a", "b", "c"] {
+        fx.client.increase_quantity(change(sku, 1)).await.unwrap();
+    }
+    let mut seen = Vec::new();
+    while seen.len() < 3 {
+        let event = events.message().await.unwrap().unwrap();
+        assert_eq!(event.item.unwrap().stock.unwrap().quantity, 11);
+        seen.push(event.sku);
+    }
+    seen.sort();
+    assert_eq!(seen, ["a", "b", "c"]);
+
+    requests
+        .send(watch_request(&[], &["a", "b"]))
+        .await
+        .unwrap();
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    fx.client.increase_quantity(change("c", 1)).await.unwrap();
+    let event = events.message().await.unwrap().unwrap();
+    assert_eq!(event.sku, "c");
+}
+
+// synth-162: zero quantity changes error or do nothing, by policy.
+
+#[tokio::test]
+async fn zero_changes_are_invalid_by_default() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    let decrease = fx.client.decrease_quantity(change("a", 0)).await;
+    let status = decrease.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), super::ZERO_QUANT_ERR);
+    let increase = fx.client.increase_quantity(change("a", 0)).await;
+    assert_eq!(code(increase), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn zero_changes_succeed_as_noops_under_the_noop_policy() {
+    let mut fx = start(&["--zero-quantity-policy", "noop"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    let generation = fx.inventory.generation.load(Ordering::SeqCst);
+
+    for response in [
+        fx.client.decrease_quantity(change("a", 0)).await,
+        fx.client.increase_quantity(change("a", 0)).await,
+    ] {
+        assert_eq!(response.unwrap().into_inner().quantity, 10);
+    }
+    assert_eq!(fx.inventory.generation.load(Ordering::SeqCst), generation);
+}
+
+// synth-162~2: total value updates, debounced.
+
+#[tokio::test]
+async fn total_value_updates_are_debounced() {
+    let mut fx = start(&["--total-value-interval", "300"]).await;
+    fx.add(item("a", 2.5, 10)).await;
+
+    let mut totals = fx
+        .client
+        .watch_total_value(TotalValueRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(totals.message().await.unwrap().unwrap().total, 25.0);
+
+    let started = std::time::Instant::now();
+    for _ in 0..3 {
+        fx.client.increase_quantity(change("a", 2)).await.unwrap();
+    }
+    assert_eq!(totals.message().await.unwrap().unwrap().total, 40.0);
+    assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+}
+
+// synth-163: a cap on active reservations per SKU.
+
+fn reserve(sku: &str, quantity: u64, ttl: u64) -> ReserveRequest {
+    ReserveRequest {
+        sku: sku.into(),
+        quantity,
+        ttl,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn reservations_past_the_cap_are_rejected_until_one_frees() {
+    let mut fx = start(&["--max-reservations-per-sku", "2"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    fx.add(item("b", 1.0, 10)).await;
+
+    let first = fx.client.reserve(reserve("a", 1, 0)).await.unwrap();
+    fx.client.reserve(reserve("a", 1, 60)).await.unwrap();
+    let over = fx.client.reserve(reserve("a", 1, 0)).await;
+    assert_eq!(code(over), Code::ResourceExhausted);
+    fx.client.reserve(reserve("b", 1, 0)).await.unwrap();
+
+    let release = ReleaseRequest {
+        reservation_id: first.into_inner().reservation_id,
+    };
+    fx.client.release(release).await.unwrap();
+    fx.client.reserve(reserve("a", 1, 0)).await.unwrap();
+
+    fx.clock.advance(60);
+    fx.client.reserve(reserve("a", 1, 0)).await.unwrap();
+}
+
+// synth-163~2: unique slugs, looked up with get_by_slug.
+
+impl Fixture {
+    async fn by_slug(&mut self, slug: &str) -> Result<Item, Status> {
+        let request = SlugRequest { slug: slug.into() };
+        self.client
+            .get_by_slug(request)
+            .await
+            .map(|r| r.into_inner())
+    }
+}
+
+fn named(sku: &str, name: &str) -> Item {
+    let mut item = item(sku, 1.0, 1);
+    item.information.as_mut().unwrap().name = Some(name.into());
+    item
+}
+
+#[tokio::test]
+async fn slugs_are_unique_and_follow_renames() {
+    let mut fx = start(&[]).await;
+    fx.add(named("a", "Red Widget")).await;
+    fx.add(named("b", "red widget!")).await;
+
+    assert_eq!(fx.get("a").await.unwrap().slug, "red-widget");
+    assert_eq!(fx.get("b").await.unwrap().slug, "red-widget-2");
+    let found = fx.by_slug("red-widget-2").await.unwrap();
+    assert_eq!(found.identifier, Some(id("b")));
+
+    let rename = NameChangeRequest {
+        sku: "a".into(),
+        name: "Blue Widget".into(),
+    };
+    fx.client.update_name(rename).await.unwrap();
+    assert_eq!(
+        fx.by_slug("blue-widget").await.unwrap().identifier,
+        Some(id("a"))
+    );
+    assert_eq!(code(fx.by_slug("red-widget").await), Code::NotFound);
+}
+
+// synth-164: a snapshot and a WAL are reconciled at startup.
+
+/// A fresh directory for one test's files.
+fn scratch(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("{}-{}", test, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes a WAL holding `entries` after `sequence`.
+fn write_wal(path: &std::path::Path, sequence: u64, entries: &[(&str, Option<Item>)]) {
+    let mut wal = Wal::open(path, sequence).unwrap();
+    for (sku, item) in entries {
+        wal.append(sku, item.clone()).unwrap();
+    }
+    wal.sync().unwrap();
+}
+
+#[tokio::test]
+async fn overlapping_wal_replays_after_the_snapshot() {
+    let dir = scratch("overlapping-wal");
+    let (snapshot, wal) = (dir.join("snapshot"), dir.join("wal"));
+    let saved = Snapshot {
+        items: vec![item("a", 1.0, 10), item("b", 1.0, 3)],
+        version: 2,
+        taken_at: START,
+    };
+    SnapshotFile::new(snapshot.clone()).save(&saved).unwrap();
+    write_wal(
+        &wal,
+        0,
+        &[
+            ("a", Some(item("a", 1.0, 5))),
+            ("a", Some(item("a", 1.0, 10))),
+            ("b", None),
+            ("c", Some(item("c", 1.0, 7))),
+        ],
+    );
+
+    let mut fx = start(&[]).await;
+    let backend = Journaled::new(Some(snapshot.clone()), &wal);
+    let version = fx.inventory.spawn_load(Arc::new(backend)).await.unwrap();
+    assert_eq!(version.unwrap(), 4);
+    assert_eq!(fx.stock("a").await.quantity, 10);
+    assert_eq!(code(fx.get("b").await), Code::NotFound);
+    assert_eq!(fx.stock("c").await.quantity, 7);
+
+    // Changes after startup are journaled, and a snapshot takes them in.
+    let file = SnapshotFile::new(snapshot.clone());
+    fx.inventory
+        .start_journal(Wal::open(&wal, 4).unwrap())
+        .await;
+    fx.client.increase_quantity(change("c", 1)).await.unwrap();
+    fx.client.remove(id("a")).await.unwrap();
+    fx.inventory.save_snapshot(&file).await.unwrap();
+    fx.client.increase_quantity(change("c", 1)).await.unwrap();
+    fx.inventory.save_snapshot(&file).await.unwrap();
+    assert_eq!(std::fs::metadata(&wal).unwrap().len(), 0);
+
+    let mut restarted = start(&[]).await;
+    let backend = Journaled::new(Some(snapshot), &wal);
+    let version = restarted.inventory.spawn_load(Arc::new(backend)).await;
+    assert_eq!(version.unwrap().unwrap(), 7);
+    assert_eq!(code(restarted.get("a").await), Code::NotFound);
+    assert_eq!(restarted.stock("c").await.quantity, 9);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn incompatible_snapshot_and_wal_fail_the_load() {
+    let dir = scratch("incompatible-wal");
+    let (snapshot, wal) = (dir.join("snapshot"), dir.join("wal"));
+    let saved = Snapshot {
+        items: vec![item("a", 1.0, 10)],
+        version: 2,
+        taken_at: START,
+    };
+    SnapshotFile::new(snapshot.clone()).save(&saved).unwrap();
+    write_wal(&wal, 5, &[("a", Some(item("a", 1.0, 1)))]);
+
+    let mut fx = start(&[]).await;
+    let backend = Journaled::new(Some(snapshot), &wal);
+    let status = fx.inventory.spawn_load(Arc::new(backend)).await.unwrap();
+    let status = status.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert!(status.message().contains("WAL starts at 6"));
+    assert!(!fx.ready().await);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// synth-164~2: streamed imports report failures by stream index.
+
+#[tokio::test]
+async fn add_batch_reports_failures_by_index() {
+    let mut fx = start(&[]).await;
+    let items: Vec<Item> = (0..8)
+        .map(|i| match i {
+            2 => item("", 1.0, 1),
+            6 => item("item-1", 1.0, 1),
+            _ => item(&format!("item-{}", i), 1.0, 1),
+        })
+        .collect();
+
+    let summary = fx
+        .client
+        .add_batch(tokio_stream::iter(items))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(summary.added, 6);
+    let failed: Vec<_> = summary
+        .failures
+        .iter()
+        .map(|failure| (failure.index, failure.sku.as_str()))
+        .collect();
+    assert_eq!(failed, [(2, ""), (6, "item-1")]);
+}
+
+// synth-165: names unique under a policy.
+
+fn rename(sku: &str, name: &str) -> NameChangeRequest {
+    NameChangeRequest {
+        sku: sku.into(),
+        name: name.into(),
+    }
+}
+
+#[tokio::test]
+async fn duplicate_names_are_rejected_under_normalized_policy() {
+    let mut fx = start(&["--unique-names", "normalized"]).await;
+    fx.add(named("a", "Red Widget")).await;
+
+    let dup = fx.client.add(named("b", "  red widget ")).await;
+    assert_eq!(code(dup), Code::AlreadyExists);
+    let upsert = UpsertRequest {
+        items: vec![named("b", "RED WIDGET")],
+    };
+    let results = fx.client.upsert_many(upsert).await.unwrap().into_inner();
+    assert_eq!(results.results[0].error, super::DUP_NAME_ERR);
+
+    fx.add(named("b", "Blue Widget")).await;
+    assert_eq!(
+        code(fx.client.update_name(rename("b", "red widget")).await),
+        Code::AlreadyExists
+    );
+
+    // Renames and removals free the old name.
+    fx.client
+        .update_name(rename("a", "Green Widget"))
+        .await
+        .unwrap();
+    fx.client
+        .update_name(rename("b", "Red Widget"))
+        .await
+        .unwrap();
+    fx.client.remove(id("b")).await.unwrap();
+    fx.add(named("c", "red widget")).await;
+}
+
+#[tokio::test]
+async fn exact_policy_tells_case_apart_and_off_allows_duplicates() {
+    let mut fx = start(&["--unique-names", "exact"]).await;
+    fx.add(named("a", "Widget")).await;
+    fx.add(named("b", "widget")).await;
+    assert_eq!(
+        code(fx.client.add(named("c", "Widget")).await),
+        Code::AlreadyExists
+    );
+
+    let mut fx = start(&[]).await;
+    fx.add(named("a", "Widget")).await;
+    fx.add(named("b", "Widget")).await;
+}
+
+// synth-165~2: percentage adjustments clamp at a floor.
+
+fn adjust(category: &str, percent: f64, floor: Option<f32>) -> PriceAdjustRequest {
+    PriceAdjustRequest {
+        category: category.into(),
+        percent,
+        floor,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn adjustment_clamps_at_the_floor() {
+    let mut fx = start(&[]).await;
+    fx.add(in_category("tools", item("cheap", 4.0, 1))).await;
+    fx.add(in_category("tools", item("dear", 20.0, 1))).await;
+
+    let response = fx
+        .client
+        .adjust_price_percent(adjust("tools", -50.0, Some(3.0)))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.clamped, ["cheap"]);
+    assert_eq!(fx.stock("cheap").await.price, 3.0);
+    assert_eq!(fx.stock("dear").await.price, 10.0);
+}
+
+#[tokio::test]
+async fn adjustment_clamped_below_cost_is_still_rejected() {
+    let mut fx = start(&["--below-cost-policy", "reject"]).await;
+    fx.add(in_category("tools", costed("a", 10.0, 6.0))).await;
+
+    let clamped = fx
+        .client
+        .adjust_price_percent(adjust("tools", -50.0, Some(5.0)))
+        .await;
+    assert_eq!(code(clamped), Code::FailedPrecondition);
+    assert_eq!(fx.stock("a").await.price, 10.0);
+
+    let mut at_cost = adjust("tools", -50.0, Some(5.0));
+    at_cost.floor_at_cost = true;
+    let response = fx.client.adjust_price_percent(at_cost).await.unwrap();
+    assert_eq!(response.into_inner().clamped, ["a"]);
+    assert_eq!(fx.stock("a").await.price, 6.0);
+}
+
+// synth-166: slow lock acquisitions are counted.
+
+#[tokio::test]
+async fn contended_lock_counts_as_slow() {
+    let mut fx = start(&["--slow-lock-threshold", "20"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    let metrics = fx.client.get_metrics(MetricsRequest {}).await.unwrap();
+    assert_eq!(metrics.into_inner().slow_lock_acquisitions, 0);
+
+    let held = fx.inventory.inventory.lock().await;
+    let mut client = fx.client.clone();
+    let contending = tokio::spawn(async move { client.increase_quantity(change("a", 1)).await });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    drop(held);
+    contending.await.unwrap().unwrap();
+
+    let metrics = fx.client.get_metrics(MetricsRequest {}).await.unwrap();
+    assert_eq!(metrics.into_inner().slow_lock_acquisitions, 1);
+}
+
+// synth-166~2: an item's availability status from quantity, reorder point
+// and backorder flag.
+
+#[tokio::test]
+async fn status_for_each_stock_level() {
+    let mut fx = start(&[]).await;
+    let cases = [
+        (0, 0, false, "out_of_stock"),
+        (0, 5, false, "out_of_stock"),
+        (0, 5, true, "backorder"),
+        (3, 5, false, "low_stock"),
+        (5, 5, true, "low_stock"),
+        (6, 5, false, "in_stock"),
+        (1, 0, false, "in_stock"),
+    ];
+    for (i, (quantity, reorder_point, backorder, expected)) in cases.into_iter().enumerate() {
+        let sku = format!("item-{}", i);
+        let mut item = item(&sku, 1.0, quantity);
+        let stock = item.stock.as_mut().unwrap();
+        stock.reorder_point = reorder_point;
+        stock.backorder = backorder;
+        fx.insert(item).await;
+
+        let status = fx.client.get_with_status(id(&sku)).await.unwrap();
+        assert_eq!(status.into_inner().status, expected, "{:?}", cases[i]);
+    }
+}
+
+// synth-167: paging through a category in a sort order.
+
+#[tokio::test]
+async fn category_pages_are_sorted_and_stable() {
+    let mut fx = start(&[]).await;
+    let prices = [("a", 3.0), ("b", 1.0), ("c", 3.0), ("d", 2.0), ("e", 5.0)];
+    for (sku, price) in prices {
+        fx.add(in_category("tools", item(sku, price, 1))).await;
+    }
+    fx.add(in_category("tools", item("empty", 4.0, 0))).await;
+    fx.add(in_category("toys", item("x", 1.0, 1))).await;
+
+    let mut seen = Vec::new();
+    let mut cursor = String::new();
+    loop {
+        let request = CategoryListRequest {
+            category: "tools".into(),
+            page_size: 2,
+            cursor,
+            sort: CategorySort::PriceDesc as i32,
+            in_stock_only: true,
+            ..Default::default()
+        };
+        let page = fx.client.list_by_category(request).await.unwrap();
+        let page = page.into_inner();
+        assert_eq!(page.total, 5);
+        seen.extend(skus(&page.items).into_iter().map(String::from));
+        if page.next_cursor.is_empty() {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    assert_eq!(seen, ["e", "a", "c", "d", "b"]);
+}
+
+// synth-167~2: racing adds of one SKU, under each duplicate add policy.
+
+impl Fixture {
+    /// Runs `first` then `second` add of the same SKU queued behind a held
+    /// inventory lock, so they race but reach the map in a known order.
+    async fn race_adds(&self, first: Item, second: Item) -> (Code, Code) {
+        let held = self.inventory.inventory.lock().await;
+        let queue = |item: Item| {
+            let mut client = self.client.clone();
+            tokio::spawn(async move { client.add(item).await })
+        };
+        let first = queue(first);
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        let second = queue(second);
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        drop(held);
+
+        let outcome = |result: Result<_, Status>| result.map_or_else(|s| s.code(), |_| Code::Ok);
+        (
+            outcome(first.await.unwrap()),
+            outcome(second.await.unwrap()),
+        )
+    }
+}
+
+#[tokio::test]
+async fn racing_adds_keep_the_first_under_reject() {
+    let mut fx = start(&[]).await;
+    let codes = fx.race_adds(item("a", 1.0, 1), item("a", 2.0, 2)).await;
+    assert_eq!(codes, (Code::Ok, Code::AlreadyExists));
+    assert_eq!(fx.stock("a").await.price, 1.0);
+}
+
+#[tokio::test]
+async fn racing_adds_keep_the_last_under_last_wins() {
+    let mut fx = start(&["--duplicate-add-policy", "last-wins"]).await;
+    let codes = fx.race_adds(item("a", 1.0, 1), item("a", 2.0, 2)).await;
+    assert_eq!(codes, (Code::Ok, Code::Ok));
+    assert_eq!(fx.stock("a").await.price, 2.0);
+}
+
+// synth-168: per-method default deadlines.
+
+#[tokio::test]
+async fn slow_request_past_its_method_deadline_is_aborted() {
+    let mut fx = start(&["--method-deadline", "Get=30"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+
+    let inventory = fx.inventory.clone();
+    let held = inventory.inventory.lock().await;
+    assert_eq!(code(fx.get("a").await), Code::DeadlineExceeded);
+    drop(held);
+    assert!(fx.get("a").await.is_ok());
+}
+
+// synth-168~2: describe_item reports the rules add enforces.
+
+impl Fixture {
+    async fn describe(&mut self, name: &str) -> FieldDescription {
+        let description = self.client.describe_item(DescribeRequest {}).await.unwrap();
+        let mut fields = description.into_inner().fields.into_iter();
+        fields.find(|field| field.name == name).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn description_includes_the_price_constraint() {
+    let mut fx = start(&[]).await;
+    let price = fx.describe("stock.price").await;
+    assert_eq!(price.r#type, "float");
+    assert!(price
+        .constraints
+        .iter()
+        .any(|c| c == "must be greater than 0"));
+    assert!(fx.describe("identifier.sku").await.required);
+
+    let mut fx = start(&["--zero-price-policy", "allow"]).await;
+    let price = fx.describe("stock.price").await;
+    assert!(price
+        .constraints
+        .iter()
+        .any(|c| c == "must not be negative"));
+}
+
+// synth-169: totals are summed in cents.
+
+#[tokio::test]
+async fn total_value_is_exact_in_cents() {
+    let mut fx = start(&[]).await;
+    for i in 0..10 {
+        fx.add(item(&format!("dime-{}", i), 0.1, 1)).await;
+    }
+    fx.add(item("pricey", 19.99, 7)).await;
+
+    let mut totals = fx
+        .client
+        .watch_total_value(TotalValueRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+    let total = totals.message().await.unwrap().unwrap().total;
+    assert_eq!(total, 140.93);
+    let naive: f64 = (0..10).map(|_| 0.1f32 as f64).sum::<f64>() + 19.99f32 as f64 * 7.0;
+    assert_ne!(naive, 140.93);
+}
+
+// synth-169~2: measured goods take fractional quantities.
+
+fn by_weight(sku: &str, grams: u64) -> Item {
+    let mut item = item(sku, 4.0, grams);
+    item.stock.as_mut().unwrap().unit = Unit::Kg as i32;
+    item
+}
+
+fn measured(sku: &str, amount: f64) -> QuantityChangeRequest {
+    QuantityChangeRequest {
+        measured_quantity: amount,
+        ..change(sku, 0)
+    }
+}
+
+#[tokio::test]
+async fn measured_item_decreases_by_a_fraction() {
+    let mut fx = start(&[]).await;
+    fx.add(by_weight("flour", 2_500)).await;
+
+    let response = fx.client.decrease_quantity(measured("flour", 0.75)).await;
+    assert_eq!(response.unwrap().into_inner().quantity, 1_750);
+    fx.client
+        .decrease_quantity(measured("flour", 0.0004))
+        .await
+        .unwrap_err();
+    assert_eq!(fx.stock("flour").await.quantity, 1_750);
+
+    let whole = fx.client.decrease_quantity(change("flour", 1)).await;
+    assert_eq!(code(whole), Code::InvalidArgument);
+    let too_much = fx.client.decrease_quantity(measured("flour", 1.7505)).await;
+    assert_eq!(code(too_much), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn items_sold_each_reject_fractions() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 3)).await;
+    let fraction = fx.client.decrease_quantity(measured("a", 0.5)).await;
+    assert_eq!(code(fraction), Code::InvalidArgument);
+    assert_eq!(fx.stock("a").await.quantity, 3);
+}
+
+// synth-170: audit entries reach the file sink.
+
+#[tokio::test]
+async fn audit_entries_are_written_to_the_file() {
+    let dir = scratch("audit-file");
+    let path = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", path.to_str().unwrap()]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    fx.client.increase_quantity(change("a", 5)).await.unwrap();
+    fx.client.remove(id("a")).await.unwrap();
+
+    let mut entries = Vec::new();
+    for _ in 0..100 {
+        let written = std::fs::read_to_string(&path).unwrap();
+        entries = written.lines().filter_map(RecordedEntry::parse).collect();
+        if entries.len() == 3 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let rpcs: Vec<_> = entries.iter().map(|entry| entry.rpc.as_str()).collect();
+    assert_eq!(rpcs, ["add", "increase_quantity", "remove"]);
+    assert!(entries
+        .iter()
+        .all(|entry| entry.sku == "a" && entry.at == START));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// synth-170~2: all-or-nothing claims, committed or released by ID.
+
+fn claim(lines: &[(&str, u64)]) -> ClaimRequest {
+    ClaimRequest {
+        lines: lines
+            .iter()
+            .map(|&(sku, quantity)| ClaimLine {
+                sku: sku.into(),
+                quantity,
+                ..Default::default()
+            })
+            .collect(),
+        ttl: 0,
+    }
+}
+
+fn claim_id(id: &str) -> ClaimIdRequest {
+    ClaimIdRequest {
+        claim_id: id.into(),
+    }
+}
+
+#[tokio::test]
+async fn claims_hold_stock_until_committed_or_released() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    fx.add(item("b", 1.0, 2)).await;
+
+    let short = fx.client.claim_items(claim(&[("a", 2), ("b", 3)])).await;
+    assert_eq!(code(short), Code::FailedPrecondition);
+    // Nothing was held by the failed claim.
+    fx.client.decrease_quantity(change("a", 5)).await.unwrap();
+    fx.client.increase_quantity(change("a", 5)).await.unwrap();
+
+    let claimed = fx.client.claim_items(claim(&[("a", 2), ("b", 2)])).await;
+    let claimed_id = claimed.unwrap().into_inner().claim_id;
+    let taken = fx.client.decrease_quantity(change("b", 1)).await;
+    assert_eq!(code(taken), Code::InvalidArgument);
+
+    fx.client.commit_claim(claim_id(&claimed_id)).await.unwrap();
+    assert_eq!(fx.stock("a").await.quantity, 3);
+    assert_eq!(fx.stock("b").await.quantity, 0);
+    let again = fx.client.commit_claim(claim_id(&claimed_id)).await;
+    assert_eq!(code(again), Code::NotFound);
+
+    let claimed = fx.client.claim_items(claim(&[("a", 3)])).await;
+    let claimed_id = claimed.unwrap().into_inner().claim_id;
+    fx.client
+        .release_claim(claim_id(&claimed_id))
+        .await
+        .unwrap();
+    fx.client.decrease_quantity(change("a", 3)).await.unwrap();
+}
+
+#[tokio::test]
+async fn failed_commit_changes_nothing() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    fx.add(item("b", 1.0, 5)).await;
+
+    let claimed = fx.client.claim_items(claim(&[("a", 2), ("b", 2)])).await;
+    let claimed_id = claimed.unwrap().into_inner().claim_id;
+    fx.client.remove(id("b")).await.unwrap();
+
+    let commit = fx.client.commit_claim(claim_id(&claimed_id)).await;
+    assert_eq!(code(commit), Code::NotFound);
+    assert_eq!(fx.stock("a").await.quantity, 5);
+    fx.client
+        .release_claim(claim_id(&claimed_id))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn expired_claims_are_forgotten() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    let mut request = claim(&[("a", 2)]);
+    request.ttl = 60;
+    let claimed_id = fx
+        .client
+        .claim_items(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .claim_id;
+    fx.clock.advance(60);
+    fx.client.decrease_quantity(change("a", 5)).await.unwrap();
+
+    assert!(fx
+        .inventory
+        .reservations
+        .lock()
+        .await
+        .claim(&claimed_id)
+        .is_none());
+    let commit = fx.client.commit_claim(claim_id(&claimed_id)).await;
+    assert_eq!(code(commit), Code::NotFound);
+}
+
+// synth-171: admins can ask for margins on reads.
+
+/// A get with `key` set, made as an admin.
+fn admin_with(sku: &str, key: &'static str) -> Request<ItemIdentifier> {
+    let mut request = admin(id(sku));
+    request.metadata_mut().insert(key, "true".parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn margin_is_computed_from_cost_for_admins() {
+    let mut fx = start(&[]).await;
+    fx.add(costed("a", 10.0, 6.5)).await;
+    fx.add(item("uncosted", 10.0, 1)).await;
+
+    let a = fx
+        .client
+        .get(admin_with("a", "x-include-margin"))
+        .await
+        .unwrap();
+    let margin = a.into_inner().margin.unwrap();
+    assert_eq!((margin.amount, margin.percent), (3.5, 35.0));
+
+    let uncosted = fx
+        .client
+        .get(admin_with("uncosted", "x-include-margin"))
+        .await;
+    assert_eq!(uncosted.unwrap().into_inner().margin, None);
+
+    let anonymous = with_header(id("a"), "x-include-margin", "true");
+    let a = fx.client.get(anonymous).await.unwrap();
+    assert_eq!(a.into_inner().margin, None);
+}
+
+// synth-172: partial decreases report the shortfall.
+
+impl Fixture {
+    async fn decrease_partial(&mut self, sku: &str, quantity: u64) -> (u64, u64, u64) {
+        let response = self
+            .client
+            .decrease_quantity_partial(change(sku, quantity))
+            .await;
+        let response = response.unwrap().into_inner();
+        let left = response.update.unwrap().quantity;
+        (response.decreased, response.shortfall, left)
+    }
+}
+
+#[tokio::test]
+async fn partial_decrease_takes_what_is_available() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    assert_eq!(fx.decrease_partial("a", 4).await, (4, 0, 6));
+    assert_eq!(fx.decrease_partial("a", 9).await, (6, 3, 0));
+    assert_eq!(fx.decrease_partial("a", 5).await, (0, 5, 0));
+    assert_eq!(fx.stock("a").await.quantity, 0);
+}
+
+// synth-172~2: re-adding an identical item is a no-op under the idempotent
+// policy.
+
+#[tokio::test]
+async fn identical_readd_succeeds_and_conflicting_one_fails() {
+    let mut fx = start(&["--duplicate-add-policy", "idempotent"]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    let generation = fx.inventory.generation.load(Ordering::SeqCst);
+
+    fx.add(item("a", 1.0, 10)).await;
+    assert_eq!(fx.inventory.generation.load(Ordering::SeqCst), generation);
+    let conflicting = fx.client.add(item("a", 2.0, 10)).await;
+    assert_eq!(code(conflicting), Code::AlreadyExists);
+    assert_eq!(fx.stock("a").await.price, 1.0);
+
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+    assert_eq!(
+        code(fx.client.add(item("a", 1.0, 10)).await),
+        Code::AlreadyExists
+    );
+}
+
+// synth-173: get_many results aligned with the request.
+
+fn get_many(skus: &[&str], preserve_order: bool) -> GetManyRequest {
+    GetManyRequest {
+        skus: skus.iter().map(|&sku| sku.into()).collect(),
+        preserve_order,
+    }
+}
+
+#[tokio::test]
+async fn preserved_order_marks_missing_skus_in_place() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.add(item("c", 1.0, 1)).await;
+
+    let request = get_many(&["c", "x", "a", "y"], true);
+    let response = fx.client.get_many(request).await.unwrap().into_inner();
+    let results: Vec<_> = response
+        .results
+        .iter()
+        .map(|result| (result.sku.as_str(), result.missing, result.item.is_some()))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            ("c", false, true),
+            ("x", true, false),
+            ("a", false, true),
+            ("y", true, false)
+        ]
+    );
+
+    let request = get_many(&["c", "x", "a", "y"], false);
+    let response = fx.client.get_many(request).await.unwrap().into_inner();
+    assert!(response.results.is_empty());
+    assert_eq!(response.items.len(), 2);
+    assert_eq!(response.missing, ["x", "y"]);
+}
+
+// synth-173~2: rounding previews in a currency's minor units.
+
+fn round_price(price: f64, currency: &str) -> RoundPriceRequest {
+    RoundPriceRequest {
+        price,
+        currency: currency.into(),
+    }
+}
+
+#[tokio::test]
+async fn prices_round_to_the_currency_minor_unit() {
+    let mut fx = start(&["--price-rounding", "up"]).await;
+    for (currency, price, decimals) in [
+        ("jpy", 1001.0, 0),
+        ("USD", 1000.01, 2),
+        ("KWD", 1000.001, 3),
+    ] {
+        let request = round_price(1000.0001, currency);
+        let response = fx.client.round_price(request).await.unwrap().into_inner();
+        assert_eq!(
+            (response.price, response.decimals),
+            (price, decimals),
+            "{}",
+            currency
+        );
+    }
+}
+
+#[tokio::test]
+async fn unknown_currencies_and_bad_prices_are_rejected() {
+    let mut fx = start(&[]).await;
+    let unknown = fx.client.round_price(round_price(1.0, "XXX")).await;
+    assert_eq!(code(unknown), Code::InvalidArgument);
+    let negative = fx.client.round_price(round_price(-1.0, "USD")).await;
+    assert_eq!(code(negative), Code::InvalidArgument);
+}
+
+// synth-174: throttled watches send the latest state once per window.
+
+#[tokio::test]
+async fn throttled_watch_emits_the_latest_state_per_window() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 0)).await;
+
+    let mut request = with_header(id("a"), "x-watch-mode", "throttle");
+    request
+        .metadata_mut()
+        .insert("x-watch-window-ms", "200".parse().unwrap());
+    let mut events = fx.client.watch(request).await.unwrap().into_inner();
+
+    let mut writer = fx.client.clone();
+    let updates = tokio::spawn(async move {
+        for _ in 0..50 {
+            writer.increase_quantity(change("a", 1)).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    });
+
+    let mut quantities = Vec::new();
+    let mut emitted_at = Vec::new();
+    while quantities.last() != Some(&50) {
+        let event = events.message().await.unwrap().unwrap();
+        quantities.push(event.stock.unwrap().quantity);
+        emitted_at.push(std::time::Instant::now());
+    }
+    updates.await.unwrap();
+
+    // A second of changes every 20ms comes through as a handful of events.
+    assert!((3..=8).contains(&quantities.len()), "{:?}", quantities);
+    assert!(quantities.windows(2).all(|pair| pair[0] < pair[1]));
+    for pair in emitted_at.windows(2) {
+        assert!(pair[1] - pair[0] >= std::time::Duration::from_millis(150));
+    }
+}
+
+// synth-174~2: change webhooks, and admin-only readers of the change log.
+
+/// A local endpoint that accepts every POST, passing each request's head
+/// and body on as they arrive.
+fn webhook_receiver() -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0; 1024];
+            let (head, body) = loop {
+                let read = stream.read(&mut chunk).unwrap();
+                request.extend_from_slice(&chunk[..read]);
+                let text = String::from_utf8_lossy(&request).into_owned();
+                let (head, body) = match text.split_once("\r\n\r\n") {
+                    Some(parts) => parts,
+                    None => continue,
+                };
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(str::to_owned)
+                    })
+                    .map_or(0, |length| length.trim().parse().unwrap());
+                if body.len() >= length {
+                    break (head.to_owned(), body.to_owned());
+                }
+            };
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .unwrap();
+            if tx.send((head, body)).is_err() {
+                return;
+            }
+        }
+    });
+    (url, rx)
+}
+
+#[tokio::test]
+async fn adds_are_posted_to_webhooks_signed() {
+    let (url, mut posts) = webhook_receiver();
+    let mut fx = start(&["--webhook-url", &url, "--webhook-secret", "shh"]).await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    let (head, body) = posts.recv().await.unwrap();
+    assert!(head.starts_with("POST /hook "), "{}", head);
+    let entry = RecordedEntry::parse(&body).unwrap();
+    assert_eq!((entry.rpc.as_str(), entry.sku.as_str()), ("add", "a"));
+    assert_eq!(entry.at, START);
+
+    let mac = signing::hmac_sha256(b"shh", body.as_bytes());
+    let signature = format!("{}: sha256={}", SIGNATURE_HEADER, signing::hex(&mac));
+    assert!(
+        head.lines()
+            .any(|line| line.eq_ignore_ascii_case(&signature)),
+        "{}",
+        head
+    );
+}
+
+#[tokio::test]
+async fn change_log_readers_are_admin_only() {
+    let dir = scratch("change_log_readers_are_admin_only");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+
+    let changes = ExportChangesRequest::default();
+    let export = fx.client.export_changes(changes.clone()).await;
+    assert_eq!(code(export), Code::PermissionDenied);
+    let log = fx.client.get_audit_log(AuditLogRequest::default()).await;
+    assert_eq!(code(log), Code::PermissionDenied);
+    let history = fx.client.get_price_history(id("a")).await;
+    assert_eq!(code(history), Code::PermissionDenied);
+
+    assert!(fx.client.export_changes(admin(changes)).await.is_ok());
+    let log = admin(AuditLogRequest::default());
+    assert!(fx.client.get_audit_log(log).await.is_ok());
+    assert!(fx.client.get_price_history(admin(id("a"))).await.is_ok());
+}
+
+#[tokio::test]
+async fn following_exports_receive_later_changes() {
+    let dir = scratch("following_exports_receive_later_changes");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    let follow = admin(ExportChangesRequest {
+        follow: true,
+        ..Default::default()
+    });
+    let mut events = fx.client.export_changes(follow).await.unwrap().into_inner();
+    let first = events.message().await.unwrap().unwrap();
+    assert_eq!((first.offset, first.sku.as_str()), (0, "a"));
+
+    fx.add(item("b", 1.0, 5)).await;
+    let later = events.message().await.unwrap().unwrap();
+    assert_eq!(
+        (later.offset, later.rpc.as_str(), later.sku.as_str()),
+        (1, "add", "b")
+    );
+}
+
+// synth-175: reads wait for the catalog to load.
+
+/// Loads `items` once released, standing in for a slow backend.
+#[derive(Debug)]
+struct GatedBackend {
+    release: std::sync::Mutex<std::sync::mpsc::Receiver<()>>,
+    items: Vec<Item>,
+}
+
+impl Backend for GatedBackend {
+    fn load(&self) -> Result<Snapshot, BackendError> {
+        self.release.lock().unwrap().recv().unwrap();
+        Ok(Snapshot {
+            items: self.items.clone(),
+            ..Default::default()
+        })
+    }
+}
+
+#[tokio::test]
+async fn reads_are_unavailable_until_the_load_completes() {
+    let mut fx = start(&[]).await;
+    let (release, gate) = std::sync::mpsc::channel();
+    let load = fx.inventory.spawn_load(Arc::new(GatedBackend {
+        release: std::sync::Mutex::new(gate),
+        items: vec![item("a", 1.0, 1)],
+    }));
+
+    let loading = fx.client.get_all(ItemAll::default()).await;
+    assert_eq!(code(loading), Code::Unavailable);
+    let ping = fx.client.ping(PingRequest::default()).await.unwrap();
+    assert!(!ping.into_inner().ready);
+
+    release.send(()).unwrap();
+    load.await.unwrap().unwrap();
+    let loaded = get_all(&mut fx, Request::new(ItemAll::default())).await;
+    assert_eq!(skus(&loaded.items), ["a"]);
+}
+
+// synth-175~2: index drift found by verify_consistency, fixed by repair_indexes.
+
+fn issue(kind: &str, index: &str, key: &str, sku: &str) -> IndexIssue {
+    IndexIssue {
+        kind: kind.into(),
+        index: index.into(),
+        key: key.into(),
+        sku: sku.into(),
+    }
+}
+
+#[tokio::test]
+async fn drifted_indexes_are_reported_then_repaired() {
+    let mut fx = start(&[]).await;
+    fx.add(named("a", "Red Mug")).await;
+    fx.add(named("b", "Blue Mug")).await;
+    {
+        let mut indexes = fx.inventory.indexes.lock().await;
+        indexes.slugs.remove("red-mug");
+        indexes.slugs.insert("green-mug".into(), "gone".into());
+    }
+
+    let plain = fx.client.verify_consistency(ConsistencyRequest {}).await;
+    assert_eq!(code(plain), Code::PermissionDenied);
+    let report = fx
+        .client
+        .verify_consistency(admin(ConsistencyRequest {}))
+        .await;
+    assert_eq!(
+        report.unwrap().into_inner().issues,
+        [
+            issue("missing", "slug", "red-mug", "a"),
+            issue("orphaned", "slug", "green-mug", "gone"),
+        ]
+    );
+
+    let repair = fx.client.repair_indexes(admin(ConsistencyRequest {})).await;
+    let repair = repair.unwrap().into_inner();
+    assert_eq!((repair.repaired, repair.issues.len()), (2, 0));
+    let report = fx
+        .client
+        .verify_consistency(admin(ConsistencyRequest {}))
+        .await;
+    assert!(report.unwrap().into_inner().issues.is_empty());
+    assert_eq!(
+        fx.by_slug("red-mug").await.unwrap(),
+        fx.get("a").await.unwrap()
+    );
+}
+
+// synth-176: a server-wide cap on open watch streams.
+
+#[tokio::test]
+async fn watch_streams_past_the_cap_are_rejected_until_one_closes() {
+    let mut fx = start(&["--max-watch-streams", "2"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+
+    let first = fx.client.watch(id("a")).await.unwrap();
+    let total = fx.client.watch_total_value(TotalValueRequest {}).await;
+    let _second = total.unwrap();
+    assert_eq!(fx.active_watches().await, 2);
+    let status = fx.client.watch(id("a")).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+
+    drop(first);
+    for _ in 0..100 {
+        if fx.active_watches().await < 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(fx.active_watches().await, 1);
+    assert!(fx.client.watch(id("a")).await.is_ok());
+}
+
+// synth-176~2: named price lists beside the default price.
+
+#[tokio::test]
+async fn price_lists_are_set_and_read_by_name_with_a_default() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 10.0, 1)).await;
+
+    let wholesale = with_header(price("a", 7.5), "x-price-list", "wholesale");
+    fx.client.update_price(wholesale).await.unwrap();
+
+    let get = |list: &str| with_header(id("a"), "x-price-list", list);
+    let listed = fx.client.get(get("wholesale")).await.unwrap().into_inner();
+    assert_eq!(listed.stock.unwrap().price, 7.5);
+    let unknown = fx.client.get(get("staff")).await.unwrap().into_inner();
+    assert_eq!(unknown.stock.unwrap().price, 10.0);
+
+    let stock = fx.stock("a").await;
+    assert_eq!(stock.price, 10.0);
+    assert_eq!(stock.prices.get("wholesale"), Some(&7.5));
+}
+
+// synth-177: unavailable errors say how long to wait.
+
+#[tokio::test]
+async fn maintenance_errors_carry_the_configured_retry_delay() {
+    let mut fx = start(&["--maintenance-retry-delay", "2500"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    let maintenance = admin(MaintenanceRequest { enabled: true });
+    fx.client.set_maintenance(maintenance).await.unwrap();
+
+    let status = fx
+        .client
+        .increase_quantity(change("a", 1))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unavailable);
+    let delay = crate::retry::retry_delay(&status);
+    assert_eq!(delay, Some(std::time::Duration::from_millis(2500)));
+    assert!(fx.get("a").await.is_ok());
+}
+
+// synth-177~2: bundles sell from their components' stock.
+
+fn bundle(sku: &str, lines: &[(&str, u64)]) -> Item {
+    let mut bundle = item(sku, 20.0, 0);
+    bundle.bundle = lines
+        .iter()
+        .map(|&(sku, quantity)| BundleLine {
+            sku: sku.into(),
+            quantity,
+        })
+        .collect();
+    bundle
+}
+
+#[tokio::test]
+async fn bundles_sell_all_components_or_none() {
+    let mut fx = start(&[]).await;
+    fx.add(item("x", 1.0, 10)).await;
+    fx.add(item("y", 1.0, 3)).await;
+    fx.add(bundle("kit", &[("x", 2), ("y", 1)])).await;
+    assert_eq!(fx.stock("kit").await.quantity, 3);
+
+    let sold = fx.client.decrease_quantity(change("kit", 2)).await.unwrap();
+    assert_eq!(sold.into_inner().quantity, 1);
+    assert_eq!(fx.stock("x").await.quantity, 6);
+    assert_eq!(fx.stock("y").await.quantity, 1);
+
+    let short = fx.client.decrease_quantity(change("kit", 2)).await;
+    assert_eq!(code(short), Code::InvalidArgument);
+    assert_eq!(fx.stock("x").await.quantity, 6);
+    assert_eq!(fx.stock("y").await.quantity, 1);
+}
+
+#[tokio::test]
+async fn bundle_reservations_hold_every_component() {
+    let mut fx = start(&[]).await;
+    fx.add(item("x", 1.0, 4)).await;
+    fx.add(item("y", 1.0, 2)).await;
+    fx.add(bundle("kit", &[("x", 2), ("y", 1)])).await;
+
+    let held = fx.client.reserve(reserve("kit", 1, 60)).await.unwrap();
+    assert_eq!(held.into_inner().available, 1);
+    let short = fx.client.reserve(reserve("kit", 2, 60)).await;
+    assert_eq!(code(short), Code::FailedPrecondition);
+
+    let direct = fx.client.reserve(reserve("x", 3, 60)).await;
+    assert_eq!(code(direct), Code::FailedPrecondition);
+    assert!(fx.client.reserve(reserve("x", 2, 60)).await.is_ok());
+}
+
+// synth-178: upserts create, replace or fail each item on its own.
+
+fn upsert_outcomes(response: UpsertResponse) -> Vec<(String, UpsertOutcome)> {
+    response
+        .results
+        .into_iter()
+        .map(|result| (result.sku.clone(), result.outcome()))
+        .collect()
+}
+
+#[tokio::test]
+async fn upserts_report_created_updated_and_failed_items() {
+    let mut fx = start(&[]).await;
+    fx.add(item("old", 1.0, 1)).await;
+
+    let items = vec![
+        item("new", 2.0, 2),
+        item("old", 3.0, 3),
+        item("bad", -1.0, 1),
+    ];
+    let response = fx
+        .client
+        .upsert_many(UpsertRequest { items })
+        .await
+        .unwrap();
+    let response = response.into_inner();
+    assert_eq!(response.results[2].error, super::NEGATIVE_PRICE_ERR);
+    assert_eq!(
+        upsert_outcomes(response),
+        [
+            ("new".into(), UpsertOutcome::Created),
+            ("old".into(), UpsertOutcome::Updated),
+            ("bad".into(), UpsertOutcome::Failed),
+        ]
+    );
+    assert_eq!(fx.stock("old").await.price, 3.0);
+    assert!(fx.get("bad").await.is_err());
+}
+
+#[tokio::test]
+async fn replacing_upserts_keep_the_creation_time() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.clock.advance(60);
+
+    let items = vec![item("a", 2.0, 1), item("b", 1.0, 1)];
+    fx.client
+        .upsert_many(UpsertRequest { items })
+        .await
+        .unwrap();
+    let replaced = fx.get("a").await.unwrap();
+    assert_eq!(
+        (replaced.created_at, replaced.updated_at),
+        (START, START + 60)
+    );
+    let created = fx.get("b").await.unwrap();
+    assert_eq!(
+        (created.created_at, created.updated_at),
+        (START + 60, START + 60)
+    );
+}
+
+// synth-178~2: lookups fall back to the map past a stale index entry.
+
+#[tokio::test]
+async fn stale_slug_entries_fall_back_and_heal_by_policy() {
+    for (policy, healed) in [("heal", "a"), ("skip", "b")] {
+        let mut fx = start(&["--stale-index-policy", policy]).await;
+        fx.add(named("a", "Red Mug")).await;
+        fx.add(named("b", "Blue Mug")).await;
+        let entry = fx
+            .inventory
+            .indexes
+            .lock()
+            .await
+            .slugs
+            .insert("red-mug".into(), "b".into());
+        assert_eq!(entry.as_deref(), Some("a"));
+
+        let found = fx.by_slug("red-mug").await.unwrap();
+        assert_eq!(found.identifier.unwrap().sku, "a", "{}", policy);
+        let entry = fx
+            .inventory
+            .indexes
+            .lock()
+            .await
+            .slugs
+            .get("red-mug")
+            .cloned();
+        assert_eq!(entry.as_deref(), Some(healed), "{}", policy);
+    }
+}
+
+// synth-179: prices formatted for display.
+
+fn format_price(amount: f64, currency: &str, locale: Option<&str>) -> FormatPriceRequest {
+    FormatPriceRequest {
+        amount,
+        currency: currency.into(),
+        locale: locale.map(str::to_owned),
+    }
+}
+
+#[tokio::test]
+async fn prices_format_in_the_requested_or_configured_locale() {
+    let mut fx = start(&["--price-locale", "de-DE"]).await;
+    let formatted = |response: Result<tonic::Response<FormatPriceResponse>, Status>| {
+        response.unwrap().into_inner().formatted
+    };
+
+    let default = fx
+        .client
+        .format_price(format_price(9.99, "EUR", None))
+        .await;
+    assert_eq!(formatted(default), "9,99\u{a0}€");
+    let request = format_price(9.99, "EUR", Some("en-GB"));
+    assert_eq!(formatted(fx.client.format_price(request).await), "€9.99");
+
+    let locale = format_price(1.0, "EUR", Some("xx-XX"));
+    assert_eq!(
+        code(fx.client.format_price(locale).await),
+        Code::InvalidArgument
+    );
+    let currency = format_price(1.0, "XXX", None);
+    assert_eq!(
+        code(fx.client.format_price(currency).await),
+        Code::InvalidArgument
+    );
+}
+
+// synth-179~2: a histogram of stock levels.
+
+fn buckets(histogram: QuantityHistogram) -> Vec<(u64, Option<u64>, u64)> {
+    histogram
+        .buckets
+        .into_iter()
+        .map(|bucket| (bucket.min, bucket.max, bucket.count))
+        .collect()
+}
+
+#[tokio::test]
+async fn stock_levels_count_into_inclusive_buckets() {
+    let mut fx = start(&[]).await;
+    for (sku, quantity) in [("a", 0), ("b", 5), ("c", 10), ("d", 11), ("e", 500)] {
+        fx.add(item(sku, 1.0, quantity)).await;
+    }
+
+    let default = HistogramRequest::default();
+    let histogram = fx.client.get_quantity_histogram(default).await.unwrap();
+    assert_eq!(
+        buckets(histogram.into_inner()),
+        [
+            (0, Some(0), 1),
+            (1, Some(10), 2),
+            (11, Some(100), 1),
+            (101, None, 1)
+        ]
+    );
+
+    let custom = HistogramRequest { bounds: vec![5] };
+    let histogram = fx.client.get_quantity_histogram(custom).await.unwrap();
+    assert_eq!(
+        buckets(histogram.into_inner()),
+        [(0, Some(5), 2), (6, None, 3)]
+    );
+
+    let unordered = HistogramRequest {
+        bounds: vec![10, 5],
+    };
+    let status = fx.client.get_quantity_histogram(unordered).await;
+    assert_eq!(code(status), Code::InvalidArgument);
+}
+
+// synth-180: a per-client rate on opening streams.
+
+#[tokio::test]
+async fn streams_opened_past_the_burst_are_rejected_with_a_retry_hint() {
+    let mut fx = start(&["--stream-open-rate", "0.5", "--stream-open-burst", "2"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+
+    let _first = fx.client.watch(id("a")).await.unwrap();
+    let _second = fx.client.watch(id("a")).await.unwrap();
+    let status = fx.client.watch(id("a")).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    let delay = crate::retry::retry_delay(&status).unwrap();
+    assert!(delay > std::time::Duration::from_secs(1), "{:?}", delay);
+
+    // Unary calls don't spend stream opens.
+    assert!(fx.get("a").await.is_ok());
+}
+
+// synth-180~2: strict input rejects server-assigned fields.
+
+#[tokio::test]
+async fn strict_input_rejects_server_fields_that_lenient_input_ignores() {
+    let mut slugged = item("a", 1.0, 1);
+    slugged.slug = "chosen".into();
+
+    let mut strict = start(&["--strict-input"]).await;
+    let status = strict.client.add(slugged.clone()).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(
+        status.message(),
+        format!("slug: {}", super::SERVER_FIELD_ERR)
+    );
+    let mut kit = bundle("kit", &[("a", 1)]);
+    kit.stock.as_mut().unwrap().quantity = 3;
+    let status = strict.client.add(kit).await.unwrap_err();
+    assert_eq!(status.message(), super::BUNDLE_QUANT_ERR);
+
+    let mut lenient = start(&[]).await;
+    lenient.client.add(slugged).await.unwrap();
+    // The slug comes from the name instead.
+    assert_eq!(lenient.get("a").await.unwrap().slug, "a");
+}
+
+// synth-181: items matching any or all of a set of tags.
+
+fn tagged(sku: &str, tags: &[&str]) -> Item {
+    let mut item = item(sku, 1.0, 1);
+    item.tags = tags.iter().map(|&tag| tag.into()).collect();
+    item
+}
+
+fn tags_request(tags: &[&str], mode: TagMatch) -> TagsRequest {
+    TagsRequest {
+        tags: tags.iter().map(|&tag| tag.into()).collect(),
+        mode: mode.into(),
+    }
+}
+
+#[tokio::test]
+async fn tag_queries_match_any_or_all_tags() {
+    let mut fx = start(&[]).await;
+    fx.add(tagged("c", &["sale", "summer"])).await;
+    fx.add(tagged("a", &["sale"])).await;
+    fx.add(tagged("b", &["summer"])).await;
+    fx.add(tagged("d", &[])).await;
+
+    let any = tags_request(&["sale", "summer"], TagMatch::Any);
+    let any = fx.client.get_by_tags(any).await.unwrap().into_inner();
+    assert_eq!(skus(&any.items), ["a", "b", "c"]);
+    let all = tags_request(&["summer", "sale"], TagMatch::All);
+    let all = fx.client.get_by_tags(all).await.unwrap().into_inner();
+    assert_eq!(skus(&all.items), ["c"]);
+
+    let none = fx
+        .client
+        .get_by_tags(tags_request(&[], TagMatch::Any))
+        .await;
+    assert_eq!(code(none), Code::InvalidArgument);
+    let empty = fx
+        .client
+        .get_by_tags(tags_request(&[""], TagMatch::Any))
+        .await;
+    assert_eq!(code(empty), Code::InvalidArgument);
+}
+
+// synth-181~2: scheduled prices take effect at their time.
+
+fn scheduled(sku: &str, price: f32, effective_at: u64) -> PriceChangeRequest {
+    PriceChangeRequest {
+        sku: sku.into(),
+        price,
+        effective_at,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn scheduled_prices_apply_when_due_unless_cancelled() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 10.0, 1)).await;
+
+    let later = fx
+        .client
+        .update_price(scheduled("a", 6.0, START + 120))
+        .await;
+    let later = later.unwrap().into_inner().scheduled_id;
+    let sooner = fx
+        .client
+        .update_price(scheduled("a", 8.0, START + 60))
+        .await;
+    let sooner = sooner.unwrap().into_inner().scheduled_id;
+    assert_eq!(fx.stock("a").await.price, 10.0);
+    let schedule = fx.client.get_price_schedule(id("a")).await.unwrap();
+    let ids: Vec<_> = schedule
+        .into_inner()
+        .changes
+        .into_iter()
+        .map(|change| change.id)
+        .collect();
+    assert_eq!(ids, [sooner, later.clone()]);
+
+    fx.clock.advance(60);
+    assert_eq!(fx.stock("a").await.price, 8.0);
+    let cancel = CancelScheduleRequest {
+        sku: "a".into(),
+        id: later,
+    };
+    fx.client.cancel_scheduled_price(cancel).await.unwrap();
+    fx.clock.advance(60);
+    assert_eq!(fx.stock("a").await.price, 8.0);
+    let schedule = fx.client.get_price_schedule(id("a")).await.unwrap();
+    assert!(schedule.into_inner().changes.is_empty());
+}
+
+// synth-182: negative prices always fail; zero prices fail unless allowed.
+
+#[tokio::test]
+async fn negative_and_zero_prices_fail_with_their_own_errors() {
+    let mut fx = start(&[]).await;
+    let status = fx.client.add(item("a", -1.0, 1)).await.unwrap_err();
+    assert_eq!(status.message(), super::NEGATIVE_PRICE_ERR);
+    let status = fx.client.add(item("a", 0.0, 1)).await.unwrap_err();
+    assert_eq!(status.message(), super::ZERO_PRICE_ERR);
+    let status = fx.client.add(item("a", f32::NAN, 1)).await.unwrap_err();
+    assert_eq!(status.message(), super::BAD_PRICE_ERR);
+
+    fx.add(item("a", 1.0, 1)).await;
+    let status = fx.client.update_price(price("a", -1.0)).await.unwrap_err();
+    assert_eq!(status.message(), super::NEGATIVE_PRICE_ERR);
+}
+
+#[tokio::test]
+async fn zero_prices_are_accepted_when_allowed() {
+    let mut fx = start(&["--zero-price-policy", "allow"]).await;
+    fx.add(costed("free", 0.0, 0.5)).await;
+    let free = fx.client.get(admin_with("free", "x-include-margin")).await;
+    let margin = free.unwrap().into_inner().margin.unwrap();
+    assert_eq!((margin.amount, margin.percent), (-0.5, 0.0));
+
+    let status = fx.client.add(item("b", -1.0, 1)).await.unwrap_err();
+    assert_eq!(status.message(), super::NEGATIVE_PRICE_ERR);
+}
+
+// synth-182~2: client timestamps within the skew tolerance are overwritten.
+
+#[tokio::test]
+async fn timestamps_past_the_skew_tolerance_are_rejected() {
+    let mut fx = start(&["--clock-skew-tolerance", "30"]).await;
+
+    let mut close = item("a", 1.0, 1);
+    close.created_at = START + 30;
+    fx.add(close).await;
+    assert_eq!(fx.get("a").await.unwrap().created_at, START);
+
+    let mut skewed = item("b", 1.0, 1);
+    skewed.updated_at = START + 31;
+    let status = fx.client.add(skewed).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    let expected = format!("updated_at: {}", super::FUTURE_TIMESTAMP_ERR);
+    assert_eq!(status.message(), expected);
+}
+
+// synth-183: replaying the change log from an offset or a time.
+
+/// Waits until the audit file at `path` holds `lines` entries.
+async fn audited(path: &std::path::Path, lines: usize) {
+    for _ in 0..100 {
+        let written = std::fs::read_to_string(path).unwrap_or_default();
+        if written.lines().count() >= lines {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("audit file never reached {} lines", lines);
+}
+
+impl Fixture {
+    async fn export(&mut self, request: ExportChangesRequest) -> Result<Vec<ChangeEvent>, Status> {
+        let mut events = self
+            .client
+            .export_changes(admin(request))
+            .await?
+            .into_inner();
+        let mut exported = Vec::new();
+        while let Some(event) = events.message().await? {
+            exported.push(event);
+        }
+        Ok(exported)
+    }
+}
+
+fn offsets(events: &[ChangeEvent]) -> Vec<(u64, &str)> {
+    events
+        .iter()
+        .map(|event| (event.offset, event.sku.as_str()))
+        .collect()
+}
+
+#[tokio::test]
+async fn exports_replay_from_an_offset_or_time_within_the_limits() {
+    let dir = scratch("exports_replay_from_an_offset_or_time_within_the_limits");
+    let audit = dir.join("audit.jsonl");
+    let path = audit.to_str().unwrap();
+    let mut fx = start(&["--audit-file", path, "--max-replay-entries", "3"]).await;
+    for sku in ["a", "b", "c"] {
+        fx.add(item(sku, 1.0, 1)).await;
+    }
+    fx.clock.advance(10);
+    fx.add(item("d", 1.0, 1)).await;
+    audited(&audit, 4).await;
+
+    let from_offset = ExportChangesRequest {
+        offset: 1,
+        ..Default::default()
+    };
+    let events = fx.export(from_offset).await.unwrap();
+    assert_eq!(offsets(&events), [(1, "b"), (2, "c"), (3, "d")]);
+    let since = ExportChangesRequest {
+        since: START + 10,
+        ..Default::default()
+    };
+    let events = fx.export(since).await.unwrap();
+    assert_eq!(offsets(&events), [(3, "d")]);
+    assert_eq!(events[0].at, START + 10);
+
+    let status = fx
+        .export(ExportChangesRequest::default())
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    let expected = format!("{}; current offset is 4", super::RESYNC_ERR);
+    assert_eq!(status.message(), expected);
+}
+
+// synth-183~2: compaction purges old tombstones and expired items.
+
+#[tokio::test]
+async fn compaction_purges_tombstones_past_retention_and_expired_items() {
+    let mut fx = start(&["--soft-delete", "--tombstone-retention", "60"]).await;
+    for sku in ["old", "recent", "kept"] {
+        fx.add(item(sku, 1.0, 1)).await;
+    }
+    let mut expiring = item("expiring", 1.0, 1);
+    expiring.ttl = 30;
+    fx.add(expiring).await;
+
+    fx.client.remove(id("old")).await.unwrap();
+    fx.clock.advance(30);
+    fx.client.remove(id("recent")).await.unwrap();
+    fx.clock.advance(30);
+
+    let plain = fx.client.compact(CompactRequest {}).await;
+    assert_eq!(code(plain), Code::PermissionDenied);
+    let report = fx.client.compact(admin(CompactRequest {})).await;
+    let report = report.unwrap().into_inner();
+    assert_eq!((report.tombstones_purged, report.expired_purged), (1, 1));
+    assert!(report.bytes_reclaimed > 0);
+
+    let deleted = |sku| admin_with(sku, "x-include-deleted");
+    assert_eq!(code(fx.client.get(deleted("old")).await), Code::NotFound);
+    assert!(
+        fx.client
+            .get(deleted("recent"))
+            .await
+            .unwrap()
+            .into_inner()
+            .deleted
+    );
+    assert_eq!(
+        code(fx.client.get(deleted("expiring")).await),
+        Code::NotFound
+    );
+    assert!(fx.get("kept").await.is_ok());
+}
+
+// synth-184: snapshot items are validated on load.
+
+/// A snapshot holding a good item, a repeat of it and a negative price.
+fn flawed_snapshot() -> Arc<FlakyBackend> {
+    Arc::new(FlakyBackend {
+        blips: AtomicU64::new(0),
+        items: vec![item("a", 1.0, 1), item("a", 2.0, 2), item("b", -1.0, 1)],
+    })
+}
+
+#[tokio::test]
+async fn invalid_snapshot_items_are_skipped_or_quarantined() {
+    for validation in ["skip", "quarantine"] {
+        let mut fx = start(&["--snapshot-validation", validation]).await;
+        fx.inventory
+            .spawn_load(flawed_snapshot())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(fx.stock("a").await.price, 1.0, "{}", validation);
+        assert_eq!(code(fx.get("b").await), Code::NotFound);
+        let quarantine = fx.client.get_quarantine(admin(QuarantineRequest {})).await;
+        let reasons: Vec<_> = quarantine
+            .unwrap()
+            .into_inner()
+            .items
+            .into_iter()
+            .map(|quarantined| quarantined.reason)
+            .collect();
+        match validation {
+            "skip" => assert!(reasons.is_empty()),
+            _ => assert_eq!(reasons, [super::DUP_ITEM_ERR, super::NEGATIVE_PRICE_ERR]),
+        }
+    }
+}
+
+#[tokio::test]
+async fn snapshots_with_invalid_items_are_rejected_under_reject() {
+    let mut fx = start(&["--snapshot-validation", "reject"]).await;
+    let status = fx
+        .inventory
+        .spawn_load(flawed_snapshot())
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    let expected = format!("{}: 2 invalid items", super::SNAPSHOT_REJECTED_ERR);
+    assert_eq!(status.message(), expected);
+    assert!(!fx.ready().await);
+}
+
+// synth-184~2: tax categories checked against the configured codes.
+
+fn taxed(sku: &str, tax_category: &str) -> Item {
+    let mut item = item(sku, 1.0, 1);
+    item.tax_category = tax_category.into();
+    item
+}
+
+fn set_tax(sku: &str, tax_category: &str) -> TaxCategoryChange {
+    TaxCategoryChange {
+        sku: sku.into(),
+        tax_category: tax_category.into(),
+    }
+}
+
+#[tokio::test]
+async fn tax_categories_are_checked_set_and_listed() {
+    let mut fx = start(&["--tax-categories", "standard,zero"]).await;
+    fx.add(taxed("a", "standard")).await;
+    fx.add(taxed("b", "")).await;
+    let status = fx.client.add(taxed("c", "luxury")).await.unwrap_err();
+    let expected = format!("luxury: {}", super::UNKNOWN_TAX_CATEGORY_ERR);
+    assert_eq!(status.message(), expected);
+
+    fx.client
+        .set_tax_category(set_tax("b", "zero"))
+        .await
+        .unwrap();
+    let unknown = fx.client.set_tax_category(set_tax("a", "luxury")).await;
+    assert_eq!(code(unknown), Code::InvalidArgument);
+    assert_eq!(fx.stock("b").await.quantity, 1);
+
+    let list = |tax_category: &str| TaxCategoryRequest {
+        tax_category: tax_category.into(),
+    };
+    let zero = fx.client.list_by_tax_category(list("zero")).await.unwrap();
+    assert_eq!(skus(&zero.into_inner().items), ["b"]);
+    let standard = fx
+        .client
+        .list_by_tax_category(list("standard"))
+        .await
+        .unwrap();
+    assert_eq!(skus(&standard.into_inner().items), ["a"]);
+    let empty = fx.client.list_by_tax_category(list("")).await;
+    assert_eq!(code(empty), Code::InvalidArgument);
+}
+
+// synth-185: reads fall back to a cached copy under lock pressure.
+
+impl Fixture {
+    /// Holds the inventory lock for `millis` from a background task.
+    async fn hold_inventory(&self, millis: u64) -> tokio::task::JoinHandle<()> {
+        let inventory = self.inventory.clone();
+        let held = tokio::spawn(async move {
+            let _map = inventory.inventory.lock().await;
+            tokio::time::sleep(std::time::Duration::from_millis(millis)).await;
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        held
+    }
+}
+
+#[tokio::test]
+async fn reads_under_lock_pressure_are_served_stale_and_flagged() {
+    let mut fx = start(&["--stale-read-threshold", "20"]).await;
+    fx.inventory.spawn_read_cache();
+    fx.add(item("a", 1.0, 1)).await;
+    tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+
+    let fresh = fx.client.get(id("a")).await.unwrap();
+    assert!(fresh.metadata().get("x-stale-read").is_none());
+
+    // A long wait behind a writer pushes the recent wait over the threshold.
+    let held = fx.hold_inventory(300).await;
+    let waited = fx.client.get(id("a")).await.unwrap();
+    assert!(waited.metadata().get("x-stale-read").is_none());
+    held.await.unwrap();
+
+    let held = fx.hold_inventory(300).await;
+    let read = tokio::time::timeout(
+        std::time::Duration::from_millis(100),
+        fx.client.get(id("a")),
+    );
+    let stale = read.await.expect("stale read waited for the lock").unwrap();
+    assert_eq!(stale.metadata().get("x-stale-read").unwrap(), "true");
+    assert_eq!(stale.into_inner().stock.unwrap().quantity, 1);
+    held.await.unwrap();
+}
+
+// synth-185~2: stock kept in one unit and sold in another.
+
+fn cased(sku: &str, cases: u64) -> Item {
+    let mut item = item(sku, 12.0, cases);
+    let stock = item.stock.as_mut().unwrap();
+    stock.stock_unit = "case".into();
+    stock.sale_unit = "each".into();
+    stock.sale_units_per = 12;
+    item
+}
+
+fn in_unit(sku: &str, quantity: u64, unit: &str) -> QuantityChangeRequest {
+    QuantityChangeRequest {
+        unit: unit.into(),
+        ..change(sku, quantity)
+    }
+}
+
+#[tokio::test]
+async fn sale_unit_changes_convert_to_whole_stock_units() {
+    let mut fx = start(&[]).await;
+    fx.add(cased("soda", 5)).await;
+    assert_eq!(fx.stock("soda").await.sale_quantity, 60);
+
+    let sold = fx
+        .client
+        .decrease_quantity(in_unit("soda", 24, "each"))
+        .await;
+    assert_eq!(sold.unwrap().into_inner().quantity, 3);
+    fx.client
+        .increase_quantity(in_unit("soda", 1, "case"))
+        .await
+        .unwrap();
+    let stock = fx.stock("soda").await;
+    assert_eq!((stock.quantity, stock.sale_quantity), (4, 48));
+
+    let partial = fx
+        .client
+        .decrease_quantity(in_unit("soda", 6, "each"))
+        .await;
+    assert_eq!(partial.unwrap_err().message(), super::PARTIAL_UNIT_ERR);
+    let unknown = fx
+        .client
+        .decrease_quantity(in_unit("soda", 1, "pallet"))
+        .await;
+    assert_eq!(code(unknown), Code::InvalidArgument);
+    assert_eq!(fx.stock("soda").await.quantity, 4);
+}
+
+// synth-186: webhooks filtered by RPC, with dead letters for failures.
+
+#[tokio::test]
+async fn webhooks_only_post_the_configured_events() {
+    let (url, mut posts) = webhook_receiver();
+    let mut fx = start(&["--webhook-url", &url, "--webhook-event", "add"]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    fx.add(item("b", 1.0, 5)).await;
+
+    for sku in ["a", "b"] {
+        let (_, body) = posts.recv().await.unwrap();
+        let entry = RecordedEntry::parse(&body).unwrap();
+        assert_eq!((entry.rpc.as_str(), entry.sku.as_str()), ("add", sku));
+    }
+}
+
+#[tokio::test]
+async fn undeliverable_webhooks_are_dead_lettered() {
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", closed.local_addr().unwrap());
+    drop(closed);
+    let dir = scratch("undeliverable_webhooks_are_dead_lettered");
+    let dead = dir.join("dead.jsonl");
+    let mut fx = start(&[
+        "--webhook-url",
+        &url,
+        "--webhook-retries",
+        "0",
+        "--webhook-dead-letter-file",
+        dead.to_str().unwrap(),
+    ])
+    .await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    audited(&dead, 1).await;
+    let line = std::fs::read_to_string(&dead).unwrap();
+    let expected = format!(
+        "{{\"url\":\"{}\",\"event\":{{\"at\":{},\"rpc\":\"add\"",
+        url, START
+    );
+    assert!(line.starts_with(&expected), "{}", line);
+}
+
+// synth-186~2: a stream of price changes only.
+
+#[tokio::test]
+async fn price_watches_report_only_price_changes_of_watched_skus() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    fx.add(item("b", 1.0, 5)).await;
+
+    let request = PriceWatchRequest {
+        skus: vec!["a".into()],
+    };
+    let mut changes = fx
+        .client
+        .watch_price_changes(request)
+        .await
+        .unwrap()
+        .into_inner();
+    let next = |change: PriceChange| (change.sku, change.old_price, change.new_price);
+
+    fx.client.update_price(price("a", 2.0)).await.unwrap();
+    let first = changes.message().await.unwrap().unwrap();
+    assert_eq!(next(first), ("a".into(), 1.0, 2.0));
+
+    fx.client.update_price(price("b", 2.0)).await.unwrap();
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    fx.client.update_price(price("a", 3.0)).await.unwrap();
+    let second = changes.message().await.unwrap().unwrap();
+    assert_eq!(next(second), ("a".into(), 2.0, 3.0));
+}
+
+// synth-187: watch refreshes give up on a busy lock and try next round.
+
+#[tokio::test]
+async fn watch_rounds_behind_a_held_lock_are_skipped_then_resume() {
+    let mut fx = start(&["--watch-lock-timeout", "50"]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    let mut events = fx.client.watch(id("a")).await.unwrap().into_inner();
+
+    let held = fx.hold_inventory(1_200).await;
+    held.await.unwrap();
+    let metrics = fx.client.get_metrics(MetricsRequest {}).await.unwrap();
+    assert!(metrics.into_inner().watch_rounds_skipped >= 1);
+
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    let event = events.message().await.unwrap().unwrap();
+    assert_eq!(event.stock.unwrap().quantity, 6);
+}
+
+// synth-187~2: reads compute availability on request, net of reservations.
+
+#[tokio::test]
+async fn requested_availability_counts_reservations() {
+    let mut fx = start(&[]).await;
+    let mut stocked = item("a", 1.0, 5);
+    stocked.stock.as_mut().unwrap().reorder_point = 2;
+    fx.add(stocked).await;
+
+    let plain = fx.get("a").await.unwrap();
+    assert_eq!(plain.availability(), Availability::Unset);
+    let with_availability = || with_header(id("a"), "x-include-availability", "true");
+    let read = fx
+        .client
+        .get(with_availability())
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(read.availability(), Availability::InStock);
+
+    fx.client.reserve(reserve("a", 3, 60)).await.unwrap();
+    let read = fx
+        .client
+        .get(with_availability())
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(read.availability(), Availability::LowStock);
+    let all = with_header(ItemAll::default(), "x-include-availability", "true");
+    let all = get_all(&mut fx, all).await;
+    assert_eq!(all.items[0].availability(), Availability::LowStock);
+
+    fx.client.reserve(reserve("a", 2, 60)).await.unwrap();
+    let read = fx
+        .client
+        .get(with_availability())
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(read.availability(), Availability::OutOfStock);
+}
+
+// synth-188: snapshot prices rounded to the currency's minor unit.
+
+fn snapshot_of(items: Vec<Item>) -> Arc<FlakyBackend> {
+    Arc::new(FlakyBackend {
+        blips: AtomicU64::new(0),
+        items,
+    })
+}
+
+#[tokio::test]
+async fn loaded_prices_are_normalized_only_when_asked() {
+    let loaded = || snapshot_of(vec![costed("a", 2.499, 1.2345)]);
+
+    let mut fx = start(&[]).await;
+    fx.inventory.spawn_load(loaded()).await.unwrap().unwrap();
+    assert_eq!(fx.stock("a").await.price, 2.499);
+
+    let mut fx = start(&["--normalize-snapshot-prices"]).await;
+    fx.inventory.spawn_load(loaded()).await.unwrap().unwrap();
+    let stock = fx.stock("a").await;
+    assert_eq!((stock.price, stock.cost), (2.5, Some(1.23)));
+
+    let args = ["--normalize-snapshot-prices", "--feed-currency", "JPY"];
+    let mut fx = start(&args).await;
+    fx.inventory.spawn_load(loaded()).await.unwrap().unwrap();
+    assert_eq!(fx.stock("a").await.price, 2.0);
+}
+
+// synth-188~2: paging through audit entries in a time range.
+
+#[tokio::test]
+async fn audit_pages_cover_the_time_range_in_order() {
+    let dir = scratch("audit_pages_cover_the_time_range_in_order");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+    for sku in ["a", "b", "c", "d"] {
+        fx.add(item(sku, 1.0, 1)).await;
+        fx.clock.advance(10);
+    }
+    audited(&audit, 4).await;
+
+    let mut request = AuditLogRequest {
+        start: START + 10,
+        end: START + 30,
+        page_size: 1,
+        cursor: String::new(),
+    };
+    let mut pages = Vec::new();
+    loop {
+        let page = fx.client.get_audit_log(admin(request.clone())).await;
+        let page = page.unwrap().into_inner();
+        pages.push(
+            offsets(&page.entries)
+                .first()
+                .copied()
+                .map(|(offset, _)| offset),
+        );
+        if page.next_cursor.is_empty() {
+            break;
+        }
+        request.cursor = page.next_cursor;
+    }
+    // Offsets 1 and 2 are b and c; a is too early and d too late.
+    assert_eq!(pages, [Some(1), Some(2)]);
+
+    request.cursor = "later".into();
+    let bad = fx.client.get_audit_log(admin(request)).await;
+    assert_eq!(code(bad), Code::InvalidArgument);
+}
+
+// synth-189: replays starting too far back are told to resync.
+
+#[tokio::test]
+async fn replays_older_than_the_max_age_must_resync() {
+    let dir = scratch("replays_older_than_the_max_age_must_resync");
+    let audit = dir.join("audit.jsonl");
+    let path = audit.to_str().unwrap();
+    let mut fx = start(&["--audit-file", path, "--max-replay-age", "60"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.clock.advance(100);
+    fx.add(item("b", 1.0, 1)).await;
+    audited(&audit, 2).await;
+
+    let status = fx
+        .export(ExportChangesRequest::default())
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert!(
+        status.message().ends_with("current offset is 2"),
+        "{}",
+        status.message()
+    );
+
+    let recent = ExportChangesRequest {
+        offset: 1,
+        ..Default::default()
+    };
+    assert_eq!(offsets(&fx.export(recent).await.unwrap()), [(1, "b")]);
+    let since = ExportChangesRequest {
+        since: START + 50,
+        ..Default::default()
+    };
+    assert_eq!(offsets(&fx.export(since).await.unwrap()), [(1, "b")]);
+}
+
+// synth-189~2: bulk requests past the batch size fail whole.
+
+#[tokio::test]
+async fn bulk_requests_past_the_batch_size_are_rejected_untouched() {
+    let mut fx = start(&["--max-batch-size", "2"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    let expected = format!("{} 2", super::BATCH_SIZE_ERR);
+
+    let many = fx.client.get_many(get_many(&["a", "b", "c"], false)).await;
+    assert_eq!(many.unwrap_err().message(), expected);
+    assert!(fx
+        .client
+        .get_many(get_many(&["a", "b"], false))
+        .await
+        .is_ok());
+
+    let items = vec![item("x", 1.0, 1), item("y", 1.0, 1), item("z", 1.0, 1)];
+    let upsert = fx.client.upsert_many(UpsertRequest { items }).await;
+    assert_eq!(upsert.unwrap_err().message(), expected);
+    assert_eq!(code(fx.get("x").await), Code::NotFound);
+
+    let skus = vec!["a".into(), "b".into(), "c".into()];
+    let watch = fx
+        .client
+        .watch_price_changes(PriceWatchRequest { skus })
+        .await;
+    assert_eq!(code(watch), Code::InvalidArgument);
+}
+
+// synth-190: variants grouped under a parent SKU, with rolled-up stock.
+
+fn variant(sku: &str, parent: &str, quantity: u64) -> Item {
+    Item {
+        parent_sku: parent.into(),
+        ..item(sku, 1.0, quantity)
+    }
+}
+
+#[tokio::test]
+async fn variants_list_under_a_parent_and_roll_up_its_stock() {
+    let mut fx = start(&[]).await;
+    fx.add(item("shirt", 1.0, 0)).await;
+    fx.add(variant("shirt-m", "shirt", 4)).await;
+    fx.add(variant("shirt-l", "shirt", 6)).await;
+
+    let listed = fx.client.list_variants(id("shirt")).await.unwrap();
+    let skus: Vec<_> = listed.get_ref().items.iter().map(super::item_sku).collect();
+    assert_eq!(skus, ["shirt-l", "shirt-m"]);
+
+    let rolled = || with_header(id("shirt"), "x-rollup-variants", "1");
+    let parent = fx.client.get(rolled()).await.unwrap().into_inner();
+    assert_eq!(parent.stock.unwrap().quantity, 10);
+    assert_eq!(fx.stock("shirt").await.quantity, 0);
+
+    fx.client
+        .decrease_quantity(change("shirt-m", 1))
+        .await
+        .unwrap();
+    assert_eq!(fx.stock("shirt-m").await.quantity, 3);
+    let parent = fx.client.get(rolled()).await.unwrap().into_inner();
+    assert_eq!(parent.stock.unwrap().quantity, 9);
+
+    let nested = fx.client.add(variant("shirt-xl", "shirt-m", 1)).await;
+    assert_eq!(nested.unwrap_err().message(), super::BAD_PARENT_ERR);
+}
+
+// synth-190~2: long polls for changes, including tag changes and expiries.
+
+impl Fixture {
+    async fn poll(&mut self, since_version: u64, timeout_ms: u64) -> PollResponse {
+        let request = PollRequest {
+            since_version,
+            timeout_ms,
+        };
+        self.client
+            .poll_changes(request)
+            .await
+            .unwrap()
+            .into_inner()
+    }
+}
+
+fn polled(response: &PollResponse) -> Vec<&str> {
+    response.items.iter().map(super::item_sku).collect()
+}
+
+#[tokio::test]
+async fn polls_return_changes_at_once_and_time_out_without() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 1)).await;
+
+    let changed = fx.poll(0, 5_000).await;
+    assert_eq!(polled(&changed), ["a"]);
+    let version = changed.version;
+
+    let idle = fx.poll(version, 50).await;
+    assert_eq!(idle.version, version);
+    assert!(idle.items.is_empty() && idle.removed.is_empty());
+
+    let mut poller = fx.client.clone();
+    let waiting = tokio::spawn(async move {
+        let request = PollRequest {
+            since_version: version,
+            timeout_ms: 5_000,
+        };
+        poller.poll_changes(request).await.unwrap().into_inner()
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    let woken = waiting.await.unwrap();
+    assert_eq!(polled(&woken), ["a"]);
+    assert!(woken.version > version);
+}
+
+#[tokio::test]
+async fn retags_and_expiries_reach_polls_and_the_audit_log() {
+    let dir = scratch("retags_and_expiries_reach_polls_and_the_audit_log");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.add(Item {
+        ttl: 60,
+        ..item("short", 1.0, 1)
+    })
+    .await;
+    let version = fx.poll(0, 0).await.version;
+
+    fx.client
+        .add_tag_to_items(tag("sale", &["a"]))
+        .await
+        .unwrap();
+    let tagged = fx.poll(version, 0).await;
+    assert_eq!(polled(&tagged), ["a"]);
+    assert_eq!(tagged.items[0].tags, ["sale"]);
+
+    fx.clock.advance(60);
+    fx.inventory.sweep().await;
+    let swept = fx.poll(tagged.version, 0).await;
+    assert!(swept.items.is_empty());
+    assert_eq!(swept.removed, ["short"]);
+
+    audited(&audit, 4).await;
+    let written = std::fs::read_to_string(&audit).unwrap();
+    let rpcs: Vec<_> = written.lines().skip(2).collect();
+    assert!(rpcs[0].contains("add_tag_to_items") && rpcs[0].contains("\"a\""));
+    assert!(rpcs[1].contains("sweeper") && rpcs[1].contains("\"short\""));
+}
+
+// synth-191: invariant violations reported by verify_consistency.
+
+#[tokio::test]
+async fn injected_invariant_violations_are_reported_untouched() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.add(item("b", 1.0, 5)).await;
+    fx.client.reserve(reserve("b", 2, 60)).await.unwrap();
+
+    let clean = fx
+        .client
+        .verify_consistency(admin(ConsistencyRequest {}))
+        .await;
+    assert!(clean.unwrap().into_inner().violations.is_empty());
+
+    fx.insert(stockless("a")).await;
+    fx.insert(item("b", 1.0, 1)).await;
+    let report = fx
+        .client
+        .verify_consistency(admin(ConsistencyRequest {}))
+        .await;
+    let rules: Vec<_> = report
+        .unwrap()
+        .into_inner()
+        .violations
+        .into_iter()
+        .map(|violation| (violation.sku, violation.rule, violation.detail))
+        .collect();
+    assert_eq!(
+        rules,
+        [
+            ("a".into(), "stock".into(), "no stock".into()),
+            ("b".into(), "reserved".into(), "2 reserved of 1".into()),
+        ] as [(String, String, String); 2]
+    );
+    assert_eq!(fx.stock("b").await.quantity, 1);
+}
+
+// synth-191~2: zero initial quantity allowed or rejected on add.
+
+#[tokio::test]
+async fn zero_initial_quantity_follows_the_policy() {
+    let mut allowing = start(&[]).await;
+    allowing.add(item("a", 1.0, 0)).await;
+    assert_eq!(allowing.stock("a").await.quantity, 0);
+
+    let mut rejecting = start(&["--zero-initial-quantity", "reject"]).await;
+    let empty = rejecting.client.add(item("a", 1.0, 0)).await;
+    assert_eq!(empty.unwrap_err().message(), super::ZERO_INITIAL_ERR);
+    rejecting.add(item("b", 1.0, 1)).await;
+    // A bundle's quantity comes from its components, so it's exempt.
+    rejecting.add(bundle("kit", &[("b", 1)])).await;
+}
+
+// synth-192: oversized get_all responses point at StreamAll, which chunks.
+
+#[tokio::test]
+async fn oversized_catalogs_are_refused_by_get_all_and_streamed_in_chunks() {
+    let mut fx = start(&["--max-response-size", "200"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    assert!(fx.client.get_all(ItemAll::default()).await.is_ok());
+    for n in 0..9 {
+        fx.add(item(&format!("sku-{}", n), 1.0, 1)).await;
+    }
+
+    let status = fx.client.get_all(ItemAll::default()).await.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert!(status.message().starts_with(super::RESPONSE_SIZE_ERR));
+    assert!(status.message().contains("10 items"));
+
+    let mut stream = fx
+        .client
+        .stream_all(ItemAll::default())
+        .await
+        .unwrap()
+        .into_inner();
+    let mut chunks = Vec::new();
+    while let Some(chunk) = stream.message().await.unwrap() {
+        assert!(prost::Message::encoded_len(&chunk) <= 200);
+        chunks.push(chunk);
+    }
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|chunk| chunk.etag == chunks[0].etag));
+    let skus: Vec<_> = chunks
+        .iter()
+        .flat_map(|chunk| chunk.items.iter().map(super::item_sku))
+        .collect();
+    assert_eq!(skus.len(), 10);
+    assert!(skus.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+// synth-192~2: items created within a time range.
+
+impl Fixture {
+    async fn created_between(&mut self, start: u64, end: u64) -> Result<Vec<String>, Status> {
+        let request = CreatedRangeRequest { start, end };
+        let items = self.client.get_created_between(request).await?.into_inner();
+        Ok(items
+            .items
+            .iter()
+            .map(|item| super::item_sku(item).to_owned())
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn created_ranges_select_inclusively_oldest_first() {
+    let mut fx = start(&[]).await;
+    for sku in ["d", "c", "b", "a"] {
+        fx.add(item(sku, 1.0, 1)).await;
+        fx.clock.advance(10);
+    }
+
+    let middle = fx.created_between(START + 10, START + 20).await.unwrap();
+    assert_eq!(middle, ["c", "b"]);
+    assert!(fx
+        .created_between(START + 1, START + 9)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let inverted = fx.created_between(START + 20, START + 10).await;
+    assert_eq!(inverted.unwrap_err().message(), super::INVERTED_RANGE_ERR);
+}
+
+// synth-193: fuzzy search within an edit distance, closest first.
+
+impl Fixture {
+    async fn search(&mut self, query: &str, max_distance: u32) -> Vec<(String, u32)> {
+        let request = SearchRequest {
+            query: query.into(),
+            mode: SearchMode::Fuzzy as i32,
+            max_distance,
+            ..Default::default()
+        };
+        let results = self.client.search(request).await.unwrap().into_inner();
+        results
+            .matches
+            .into_iter()
+            .map(|found| {
+                (
+                    super::item_sku(&found.item.unwrap()).to_owned(),
+                    found.distance,
+                )
+            })
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn misspelled_searches_match_within_the_distance() {
+    let mut fx = start(&["--search-max-distance", "2"]).await;
+    fx.add(named("a", "Ceramic Mug")).await;
+    fx.add(named("b", "Ceramic Bowl")).await;
+    fx.add(named("c", "Teapot")).await;
+
+    assert_eq!(
+        fx.search("ceramc", 1).await,
+        [("a".into(), 1), ("b".into(), 1)]
+    );
+    assert_eq!(fx.search("bowel", 1).await, [("b".into(), 1)]);
+    assert!(fx.search("tapet", 1).await.is_empty());
+    assert_eq!(fx.search("tapet", 2).await, [("c".into(), 2)]);
+    // Requests past --search-max-distance are capped to it.
+    assert!(fx.search("tpt", 5).await.is_empty());
+}
+
+// synth-193~2: each method's deadline is enforced on its own.
+
+#[tokio::test]
+async fn method_deadlines_are_enforced_independently() {
+    let mut fx = start(&[
+        "--method-deadline",
+        "Get=30",
+        "--method-deadline",
+        "StreamAll=5000",
+    ])
+    .await;
+    fx.add(item("a", 1.0, 1)).await;
+
+    let held = fx.hold_inventory(200).await;
+    let mut streamer = fx.client.clone();
+    let streamed = tokio::spawn(async move {
+        let mut stream = streamer.stream_all(ItemAll::default()).await?.into_inner();
+        stream.message().await
+    });
+    assert_eq!(code(fx.get("a").await), Code::DeadlineExceeded);
+    let streamed = streamed.await.unwrap().unwrap().unwrap();
+    assert_eq!(streamed.items.len(), 1);
+    held.await.unwrap();
+
+    let mut tight = start(&["--method-deadline", "StreamAll=30"]).await;
+    tight.add(item("a", 1.0, 1)).await;
+    let held = tight.hold_inventory(200).await;
+    let mut getter = tight.client.clone();
+    let got = tokio::spawn(async move { getter.get(id("a")).await });
+    let streamed = tight.client.stream_all(ItemAll::default()).await;
+    assert_eq!(code(streamed), Code::DeadlineExceeded);
+    assert!(got.await.unwrap().is_ok());
+    held.await.unwrap();
+}
+
+// synth-194: soft reservations record intent without holding stock back.
+
+#[tokio::test]
+async fn soft_reservations_oversell_and_report_it() {
+    let mut hard = start(&[]).await;
+    hard.add(item("a", 1.0, 5)).await;
+    hard.client.reserve(reserve("a", 3, 60)).await.unwrap();
+    assert!(hard.client.reserve(reserve("a", 4, 60)).await.is_err());
+
+    let mut fx = start(&["--reservation-mode", "soft"]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    fx.client.reserve(reserve("a", 3, 60)).await.unwrap();
+    fx.client.reserve(reserve("a", 4, 60)).await.unwrap();
+    let stock = fx.stock("a").await;
+    assert_eq!((stock.quantity, stock.reserved, stock.oversold), (5, 7, 2));
+
+    fx.client.decrease_quantity(change("a", 5)).await.unwrap();
+    let stock = fx.stock("a").await;
+    assert_eq!((stock.quantity, stock.reserved, stock.oversold), (0, 7, 7));
+}
+
+// synth-194~2: carts quoted per currency, with an optional discount.
+
+fn priced(sku: &str, price: f32, prices: &[(&str, f32)]) -> Item {
+    let mut item = item(sku, price, 10);
+    let stock = item.stock.as_mut().unwrap();
+    stock.prices = prices.iter().map(|&(c, p)| (c.into(), p)).collect();
+    item
+}
+
+fn cart(discount_percent: f64, currencies: &[&str]) -> QuoteRequest {
+    QuoteRequest {
+        lines: vec![
+            QuoteLine {
+                sku: "a".into(),
+                quantity: 3,
+            },
+            QuoteLine {
+                sku: "b".into(),
+                quantity: 1,
+            },
+        ],
+        discount_percent,
+        currencies: currencies.iter().map(|&c| c.into()).collect(),
+    }
+}
+
+fn totals(quote: &Quote) -> Vec<(&str, f64, f64, f64)> {
+    quote
+        .totals
+        .iter()
+        .map(|t| (t.currency.as_str(), t.subtotal, t.discount, t.total))
+        .collect()
+}
+
+#[tokio::test]
+async fn carts_are_quoted_per_currency_with_rounded_discounts() {
+    let mut fx = start(&[]).await;
+    fx.add(priced("a", 1.99, &[("EUR", 1.80), ("JPY", 300.0)]))
+        .await;
+    fx.add(priced("b", 0.35, &[("EUR", 0.33), ("JPY", 50.0)]))
+        .await;
+
+    let full = fx.client.quote_cart(cart(0.0, &["EUR"])).await.unwrap();
+    let full = full.into_inner();
+    assert_eq!(
+        (full.lines[0].unit_price, full.lines[0].subtotal),
+        (1.99, 5.97)
+    );
+    assert_eq!(
+        totals(&full),
+        [("USD", 6.32, 0.0, 6.32), ("EUR", 5.73, 0.0, 5.73)]
+    );
+
+    let off = fx
+        .client
+        .quote_cart(cart(10.0, &["EUR", "jpy"]))
+        .await
+        .unwrap();
+    assert_eq!(
+        totals(&off.into_inner()),
+        [
+            ("USD", 6.32, 0.63, 5.69),
+            ("EUR", 5.73, 0.57, 5.16),
+            ("JPY", 950.0, 95.0, 855.0),
+        ]
+    );
+    assert_eq!(fx.stock("a").await.quantity, 10);
+
+    let mut up = start(&["--price-rounding", "up"]).await;
+    up.add(priced("a", 1.99, &[])).await;
+    up.add(priced("b", 0.35, &[])).await;
+    let off = up.client.quote_cart(cart(10.0, &[])).await.unwrap();
+    assert_eq!(totals(&off.into_inner()), [("USD", 6.32, 0.64, 5.68)]);
+}
+
+// synth-195: watch_many keeps streaming after the client stops sending.
+
+#[tokio::test]
+async fn watch_many_survives_the_client_half_closing() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 10)).await;
+
+    let first = tokio_stream::iter([watch_request(&["a"], &[])]);
+    let mut events = fx.client.watch_many(first).await.unwrap().into_inner();
+
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    let event = events.message().await.unwrap().unwrap();
+    assert_eq!(event.item.unwrap().stock.unwrap().quantity, 11);
+
+    fx.client.remove(id("a")).await.unwrap();
+    let event = events.message().await.unwrap().unwrap();
+    assert!(event.removed);
+    // Nothing is left to watch and nothing more can be asked for.
+    assert!(events.message().await.unwrap().is_none());
+}
+
+// synth-195~2: concurrent adds with one idempotency key run once.
+
+#[tokio::test]
+async fn concurrent_adds_with_one_key_insert_once() {
+    let mut fx = start(&[]).await;
+    let keyed = |price: f32| with_header(item("a", price, 1), "x-idempotency-key", "k1");
+
+    let held = fx.inventory.inventory.lock().await;
+    let adds: Vec<_> = [1.0, 2.0]
+        .into_iter()
+        .map(|price| {
+            let mut client = fx.client.clone();
+            let request = keyed(price);
+            tokio::spawn(async move { client.add(request).await })
+        })
+        .collect();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let generation = fx.inventory.generation.load(Ordering::SeqCst);
+    drop(held);
+
+    for add in adds {
+        assert!(add.await.unwrap().is_ok());
+    }
+    assert_eq!(
+        fx.inventory.generation.load(Ordering::SeqCst),
+        generation + 1
+    );
+    let retried = fx.client.add(keyed(3.0)).await;
+    assert!(retried.is_ok());
+    assert_eq!(
+        fx.inventory.generation.load(Ordering::SeqCst),
+        generation + 1
+    );
+
+    let reused = with_header(item("b", 1.0, 1), "x-idempotency-key", "k1");
+    let reused = fx.client.add(reused).await.unwrap_err();
+    assert_eq!(reused.message(), super::KEY_REUSED_ERR);
+    assert_eq!(code(fx.get("b").await), Code::NotFound);
+}
+
+#[tokio::test]
+async fn keyed_batches_add_every_item() {
+    let mut fx = start(&[]).await;
+    let items = tokio_stream::iter([item("a", 1.0, 1), item("b", 1.0, 1)]);
+    let summary = fx
+        .client
+        .add_batch(with_header(items, "x-idempotency-key", "k1"))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(summary.added, 2);
+    assert!(summary.failures.is_empty());
+
+    let items = vec![item("c", 1.0, 1), item("d", 1.0, 1)];
+    let upserted = with_header(UpsertRequest { items }, "x-idempotency-key", "k2");
+    let response = fx.client.upsert_many(upserted).await.unwrap().into_inner();
+    assert!(response
+        .results
+        .iter()
+        .all(|result| result.error.is_empty()));
+    for sku in ["a", "b", "c", "d"] {
+        assert!(fx.get(sku).await.is_ok());
+    }
+}
+
+// synth-196: sales allocated across warehouses by the configured strategy.
+
+fn stocked_in(sku: &str, warehouses: &[(&str, u64)]) -> Item {
+    let mut item = item(sku, 1.0, warehouses.iter().map(|&(_, held)| held).sum());
+    let stock = item.stock.as_mut().unwrap();
+    stock.warehouses = warehouses
+        .iter()
+        .map(|&(warehouse, held)| (warehouse.into(), held))
+        .collect();
+    item
+}
+
+fn drawn(response: &InventoryUpdateResponse) -> Vec<(&str, u64)> {
+    response
+        .drawn
+        .iter()
+        .map(|draw| (draw.warehouse.as_str(), draw.quantity))
+        .collect()
+}
+
+#[tokio::test]
+async fn sales_draw_from_the_warehouse_with_most_stock_first() {
+    let mut fx = start(&[]).await;
+    fx.add(stocked_in("a", &[("east", 3), ("west", 5)])).await;
+
+    let sale = fx.client.decrease_quantity(change("a", 6)).await.unwrap();
+    let sale = sale.into_inner();
+    assert_eq!(drawn(&sale), [("west", 5), ("east", 1)]);
+    assert_eq!(sale.quantity, 2);
+    let stock = fx.stock("a").await;
+    assert_eq!(stock.warehouses.get("east"), Some(&2));
+    assert_eq!(stock.warehouses.get("west"), Some(&0));
+
+    let short = fx.client.decrease_quantity(change("a", 3)).await;
+    assert_eq!(short.unwrap_err().message(), super::LOW_QUANT_ERR);
+    assert_eq!(fx.stock("a").await.quantity, 2);
+
+    let unnamed = fx.client.increase_quantity(change("a", 1)).await;
+    assert_eq!(
+        unnamed.unwrap_err().message(),
+        super::WAREHOUSE_REQUIRED_ERR
+    );
+    let restock = QuantityChangeRequest {
+        warehouse: "west".into(),
+        ..change("a", 4)
+    };
+    fx.client.increase_quantity(restock).await.unwrap();
+    let stock = fx.stock("a").await;
+    assert_eq!(
+        (stock.quantity, stock.warehouses.get("west")),
+        (6, Some(&4))
+    );
+
+    let mismatched = Item {
+        stock: Some(ItemStock {
+            quantity: 9,
+            ..stocked_in("b", &[("east", 1)]).stock.unwrap()
+        }),
+        ..item("b", 1.0, 9)
+    };
+    let mismatched = fx.client.add(mismatched).await;
+    assert_eq!(
+        mismatched.unwrap_err().message(),
+        super::WAREHOUSE_TOTAL_ERR
+    );
+}
+
+#[tokio::test]
+async fn nearest_allocation_follows_the_region_hint() {
+    let mut fx = start(&[
+        "--allocation-strategy",
+        "nearest",
+        "--warehouse-region",
+        "east=us-east",
+    ])
+    .await;
+    fx.add(stocked_in("a", &[("east", 3), ("west", 5)])).await;
+
+    let near = with_header(change("a", 4), "x-region", "us-east");
+    let sale = fx.client.decrease_quantity(near).await.unwrap();
+    assert_eq!(drawn(sale.get_ref()), [("east", 3), ("west", 1)]);
+
+    let anywhere = fx.client.decrease_quantity(change("a", 1)).await.unwrap();
+    assert_eq!(drawn(anywhere.get_ref()), [("west", 1)]);
+}
+
+// synth-196~2: the cost of restocking every item at its reorder point.
+
+fn low(sku: &str, quantity: u64, reorder_point: u64, reorder_quantity: u64) -> Item {
+    let mut item = item(sku, 2.5, quantity);
+    let stock = item.stock.as_mut().unwrap();
+    stock.reorder_point = reorder_point;
+    stock.reorder_quantity = reorder_quantity;
+    item
+}
+
+#[tokio::test]
+async fn reorder_cost_covers_only_low_items() {
+    let mut fx = start(&[]).await;
+    let mut costed = low("a", 2, 5, 10);
+    costed.stock.as_mut().unwrap().cost = Some(1.25);
+    fx.add(costed).await;
+    fx.add(low("b", 3, 3, 0)).await;
+    fx.add(low("c", 9, 3, 10)).await;
+    fx.add(item("d", 1.0, 0)).await;
+
+    let plain = fx.client.get_reorder_cost(ReorderCostRequest {}).await;
+    assert_eq!(code(plain), Code::PermissionDenied);
+    let report = fx
+        .client
+        .get_reorder_cost(admin(ReorderCostRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+    let lines: Vec<_> = report
+        .lines
+        .iter()
+        .map(|line| (line.sku.as_str(), line.reorder, line.at_price, line.cost))
+        .collect();
+    // b has no reorder quantity, so it's lifted just past its reorder point
+    // at its price.
+    assert_eq!(lines, [("a", 10, false, 12.5), ("b", 1, true, 2.5)]);
+    assert_eq!(report.total, 15.0);
+}
+
+// synth-197: get_all includes tombstones only when an admin asks.
+
+#[tokio::test]
+async fn get_all_includes_tombstones_for_admins_who_ask() {
+    let mut fx = start(&["--soft-delete"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.add(item("b", 1.0, 1)).await;
+    fx.client.remove(id("a")).await.unwrap();
+
+    let skus = |items: &Items| -> Vec<(String, bool)> {
+        let mut skus: Vec<_> = items
+            .items
+            .iter()
+            .map(|item| (super::item_sku(item).to_owned(), item.deleted))
+            .collect();
+        skus.sort();
+        skus
+    };
+    let default = get_all(&mut fx, Request::new(ItemAll::default())).await;
+    assert_eq!(skus(&default), [("b".into(), false)]);
+    let asked = with_header(ItemAll::default(), "x-include-deleted", "true");
+    assert_eq!(skus(&get_all(&mut fx, asked).await), skus(&default));
+
+    let mut asked = admin(ItemAll::default());
+    asked
+        .metadata_mut()
+        .insert("x-include-deleted", "true".parse().unwrap());
+    let everything = get_all(&mut fx, asked).await;
+    assert_eq!(skus(&everything), [("a".into(), true), ("b".into(), false)]);
+}
+
+// synth-197~2: reads signed with the configured key.
+
+fn verifies(key: &str, item: &Item, signature: &str) -> bool {
+    let mac = signing::hmac_sha256(key.as_bytes(), &prost::Message::encode_to_vec(item));
+    signature == format!("sha256={}", signing::hex(&mac))
+}
+
+#[tokio::test]
+async fn signed_reads_verify_until_the_payload_changes() {
+    let mut unsigned = start(&[]).await;
+    unsigned.add(item("a", 1.0, 1)).await;
+    let read = unsigned.client.get(id("a")).await.unwrap();
+    assert!(read.metadata().get("x-signature").is_none());
+
+    let mut fx = start(&["--response-signing-key", "k1"]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    let read = fx.client.get(id("a")).await.unwrap();
+    let signature = read
+        .metadata()
+        .get("x-signature")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let signature = signature.to_owned();
+    let mut item = read.into_inner();
+    assert!(verifies("k1", &item, &signature));
+    assert!(!verifies("k2", &item, &signature));
+
+    item.stock.as_mut().unwrap().quantity = 100;
+    assert!(!verifies("k1", &item, &signature));
+}
+
+// synth-198: a panicking watch task ends its stream with an error.
+
+#[tokio::test]
+async fn watch_task_panics_reach_the_subscriber() {
+    let (tx, mut rx) = mpsc::unbounded_channel::<Result<Item, Status>>();
+    let sender = tx.clone();
+    super::spawn_watch("a".into(), tx, async move {
+        sender.send(Ok(item("a", 1.0, 1))).unwrap();
+        panic!("corrupt item");
+    });
+
+    assert!(rx.recv().await.unwrap().is_ok());
+    let status = rx.recv().await.unwrap().unwrap_err();
+    assert_eq!(status.code(), Code::Internal);
+    assert_eq!(status.message(), super::WATCH_PANIC_ERR);
+    assert!(rx.recv().await.is_none());
+}
+
+// synth-198~2: price changes carry a reason into the price history.
+
+#[tokio::test]
+async fn price_change_reasons_are_kept_in_the_history() {
+    let dir = scratch("price_change_reasons_are_kept_in_the_history");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.add(item("b", 1.0, 1)).await;
+    let reasoned = PriceChangeRequest {
+        reason: "supplier increase".into(),
+        ..price("a", 2.0)
+    };
+    fx.client.update_price(reasoned).await.unwrap();
+    fx.client.update_price(price("a", 3.0)).await.unwrap();
+    fx.client.update_price(price("b", 4.0)).await.unwrap();
+    audited(&audit, 5).await;
+
+    let plain = fx.client.get_price_history(id("a")).await;
+    assert_eq!(code(plain), Code::PermissionDenied);
+    let history = fx.client.get_price_history(admin(id("a"))).await.unwrap();
+    let reasons: Vec<_> = history
+        .get_ref()
+        .changes
+        .iter()
+        .map(|change| change.reason.as_str())
+        .collect();
+    assert_eq!(reasons, ["", "supplier increase", ""]);
+}
+
+#[tokio::test]
+async fn price_history_covers_adds_upserts_and_adjustments() {
+    let dir = scratch("price_history_covers_adds_upserts_and_adjustments");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+    fx.add(in_category("tools", item("a", 10.0, 1))).await;
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    fx.client
+        .adjust_price_percent(adjust("tools", -50.0, None))
+        .await
+        .unwrap();
+    let items = vec![in_category("tools", item("a", 8.0, 1))];
+    fx.client
+        .upsert_many(UpsertRequest { items })
+        .await
+        .unwrap();
+    audited(&audit, 4).await;
+
+    let history = fx.client.get_price_history(admin(id("a"))).await.unwrap();
+    let changes: Vec<_> = history
+        .get_ref()
+        .changes
+        .iter()
+        .map(|change| (change.rpc.as_str(), change.detail.as_str()))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("add", "price 10 quantity 1"),
+            ("adjust_price_percent", "10 to 5"),
+            ("upsert_many", "price 8 quantity 1"),
+        ]
+    );
+}
+
+// synth-199: lifecycle states gate what an item may undergo.
+
+fn transition(sku: &str, state: LifecycleState) -> StateChange {
+    StateChange {
+        sku: sku.into(),
+        state: state as i32,
+    }
+}
+
+#[tokio::test]
+async fn lifecycle_transitions_follow_the_configured_graph() {
+    let mut fx = start(&[]).await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    let same = fx
+        .client
+        .transition_state(transition("a", LifecycleState::Active))
+        .await;
+    assert_eq!(code(same), Code::InvalidArgument);
+    let illegal = fx
+        .client
+        .transition_state(transition("a", LifecycleState::Draft))
+        .await
+        .unwrap_err();
+    assert_eq!(illegal.code(), Code::FailedPrecondition);
+    assert!(illegal.message().starts_with(super::ILLEGAL_TRANSITION_ERR));
+    assert!(illegal.message().ends_with("active to draft"));
+
+    let discontinued = transition("a", LifecycleState::Discontinued);
+    fx.client.transition_state(discontinued).await.unwrap();
+    fx.client
+        .transition_state(transition("a", LifecycleState::Archived))
+        .await
+        .unwrap();
+    assert_eq!(fx.get("a").await.unwrap().state(), LifecycleState::Archived);
+}
+
+#[tokio::test]
+async fn lifecycle_states_forbid_their_operations() {
+    let mut fx = start(&[]).await;
+    let mut draft = item("d", 1.0, 5);
+    draft.set_state(LifecycleState::Draft);
+    fx.insert(draft).await;
+    fx.add(item("x", 1.0, 5)).await;
+    let discontinued = transition("x", LifecycleState::Discontinued);
+    fx.client.transition_state(discontinued).await.unwrap();
+
+    let sold = fx
+        .client
+        .decrease_quantity(change("d", 1))
+        .await
+        .unwrap_err();
+    assert_eq!(sold.code(), Code::FailedPrecondition);
+    assert_eq!(
+        sold.message(),
+        format!("{} draft", super::STATE_FORBIDS_ERR)
+    );
+    fx.client.increase_quantity(change("d", 1)).await.unwrap();
+    fx.client.update_price(price("d", 2.0)).await.unwrap();
+
+    let restocked = fx.client.increase_quantity(change("x", 1)).await;
+    assert_eq!(code(restocked), Code::FailedPrecondition);
+    fx.client.decrease_quantity(change("x", 1)).await.unwrap();
+    assert_eq!(fx.stock("d").await.quantity, 6);
+    assert_eq!(fx.stock("x").await.quantity, 4);
+
+    let archived = transition("x", LifecycleState::Archived);
+    fx.client.transition_state(archived).await.unwrap();
+    let edited = fx.client.update_price(price("x", 2.0)).await;
+    assert_eq!(code(edited), Code::FailedPrecondition);
+}
+
+// synth-199~2: searches ignore case unless asked not to.
+
+async fn search_cased(fx: &mut Fixture, query: &str, mode: SearchMode) -> Vec<(String, u32)> {
+    let request = SearchRequest {
+        query: query.into(),
+        mode: mode as i32,
+        max_distance: 1,
+        case_sensitive: true,
+        ..Default::default()
+    };
+    let results = fx.client.search(request).await.unwrap().into_inner();
+    let sku = |found: &SearchMatch| super::item_sku(found.item.as_ref().unwrap()).to_owned();
+    let matches = results.matches.iter();
+    matches.map(|found| (sku(found), found.distance)).collect()
+}
+
+#[tokio::test]
+async fn case_sensitive_searches_tell_case_apart() {
+    let mut fx = start(&[]).await;
+    fx.add(named("a", "Ceramic Mug")).await;
+    fx.add(named("b", "ceramic bowl")).await;
+
+    let folded = SearchRequest {
+        query: "CERAMIC".into(),
+        ..Default::default()
+    };
+    let folded = fx.client.search(folded).await.unwrap().into_inner();
+    assert_eq!(folded.matches.len(), 2);
+    assert_eq!(
+        search_cased(&mut fx, "Ceramic", SearchMode::Substring).await,
+        [("a".into(), 0)]
+    );
+    assert!(search_cased(&mut fx, "MUG", SearchMode::Substring)
+        .await
+        .is_empty());
+
+    assert_eq!(fx.search("mug", 1).await, [("a".into(), 0)]);
+    assert_eq!(
+        search_cased(&mut fx, "mug", SearchMode::Fuzzy).await,
+        [("a".into(), 1)]
+    );
+    assert!(search_cased(&mut fx, "BOWL", SearchMode::Fuzzy)
+        .await
+        .is_empty());
+}
+
+// synth-200: get_all and stream_all export only what the filter passes.
+
+#[tokio::test]
+async fn exports_filter_by_price_tags_and_category() {
+    let mut fx = start(&[]).await;
+    fx.add(in_category("mugs", item("a", 2.0, 1))).await;
+    fx.add(in_category("mugs", item("b", 5.0, 1))).await;
+    fx.add(in_category("bowls", item("c", 5.0, 1))).await;
+    fx.add(in_category("mugs", item("d", 9.0, 1))).await;
+    fx.client
+        .add_tag_to_items(tag("sale", &["b", "d"]))
+        .await
+        .unwrap();
+
+    let export = |filter: ExportFilter| ItemAll {
+        etag: String::new(),
+        filter: Some(filter),
+    };
+    let ranged = ExportFilter {
+        min_price: Some(2.0),
+        max_price: Some(5.0),
+        ..Default::default()
+    };
+    let response = get_all(&mut fx, Request::new(export(ranged.clone()))).await;
+    let mut ranged_skus = skus(&response.items);
+    ranged_skus.sort();
+    assert_eq!(ranged_skus, ["a", "b", "c"]);
+
+    let narrowed = ExportFilter {
+        category: "mugs".into(),
+        tags: Some(TagsRequest {
+            tags: vec!["sale".into()],
+            mode: TagMatch::Any as i32,
+        }),
+        ..ranged
+    };
+    let mut stream = fx
+        .client
+        .stream_all(export(narrowed))
+        .await
+        .unwrap()
+        .into_inner();
+    let mut streamed = Vec::new();
+    while let Some(chunk) = stream.message().await.unwrap() {
+        streamed.extend(chunk.items);
+    }
+    assert_eq!(skus(&streamed), ["b"]);
+
+    let inverted = ExportFilter {
+        min_price: Some(6.0),
+        max_price: Some(5.0),
+        ..Default::default()
+    };
+    let status = fx.client.get_all(export(inverted)).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), super::INVERTED_RANGE_ERR);
+}
+
+// synth-200~2: streamed stock counts correct quantities and audit variances.
+
+fn count(sku: &str, counted: u64) -> StockCount {
+    StockCount {
+        sku: sku.into(),
+        counted,
+    }
+}
+
+#[tokio::test]
+async fn stocktakes_correct_variances_and_report_failures() {
+    let dir = scratch("stocktakes_correct_variances_and_report_failures");
+    let audit = dir.join("audit.jsonl");
+    let mut fx = start(&["--audit-file", audit.to_str().unwrap()]).await;
+    fx.add(item("a", 1.0, 5)).await;
+    fx.add(item("b", 1.0, 3)).await;
+    fx.add(item("c", 1.0, 2)).await;
+
+    let counts = [
+        count("a", 7),
+        count("b", 3),
+        count("gone", 1),
+        count("c", 0),
+    ];
+    let summary = fx
+        .client
+        .stream_stocktake(tokio_stream::iter(counts))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(summary.counted, 3);
+    let corrections: Vec<_> = summary
+        .corrections
+        .iter()
+        .map(|correction| {
+            (
+                correction.sku.as_str(),
+                correction.previous,
+                correction.variance,
+            )
+        })
+        .collect();
+    assert_eq!(corrections, [("a", 5, 2), ("c", 2, -2)]);
+    assert_eq!(summary.failures.len(), 1);
+    assert_eq!(summary.failures[0].index, 2);
+    assert_eq!(summary.failures[0].error, super::NO_ITEM_ERR);
+    assert_eq!(fx.stock("a").await.quantity, 7);
+    assert_eq!(fx.stock("c").await.quantity, 0);
+
+    audited(&audit, 5).await;
+    let written = std::fs::read_to_string(&audit).unwrap();
+    let details: Vec<_> = written
+        .lines()
+        .filter_map(RecordedEntry::parse)
+        .filter(|entry| entry.rpc == "stream_stocktake")
+        .map(|entry| entry.detail)
+        .collect();
+    assert_eq!(details, ["counted 7 variance +2", "counted 0 variance -2"]);
+}
+
+// synth-201: reservation TTLs over the maximum are rejected or clamped.
+
+#[tokio::test]
+async fn reservation_ttls_over_the_maximum_are_rejected() {
+    let mut fx = start(&["--max-reservation-ttl", "60"]).await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    let long = fx.client.reserve(reserve("a", 1, 61)).await.unwrap_err();
+    assert_eq!(long.code(), Code::InvalidArgument);
+    assert_eq!(
+        long.message(),
+        format!("{} 60 seconds", super::TTL_TOO_LONG_ERR)
+    );
+    let long_claim = ClaimRequest {
+        ttl: 120,
+        ..claim(&[("a", 1)])
+    };
+    assert_eq!(
+        code(fx.client.claim_items(long_claim).await),
+        Code::InvalidArgument
+    );
+
+    // A reservation without a TTL gets the maximum rather than lasting forever.
+    fx.client.reserve(reserve("a", 5, 0)).await.unwrap();
+    let held = fx.client.reserve(reserve("a", 1, 60)).await;
+    assert_eq!(code(held), Code::FailedPrecondition);
+    fx.clock.advance(60);
+    fx.client.reserve(reserve("a", 1, 60)).await.unwrap();
+}
+
+#[tokio::test]
+async fn reservation_ttls_over_the_maximum_are_clamped() {
+    let mut fx = start(&[
+        "--max-reservation-ttl",
+        "60",
+        "--reservation-ttl-policy",
+        "clamp",
+    ])
+    .await;
+    fx.add(item("a", 1.0, 5)).await;
+
+    fx.client.reserve(reserve("a", 5, 3600)).await.unwrap();
+    let held = fx.client.reserve(reserve("a", 1, 0)).await;
+    assert_eq!(code(held), Code::FailedPrecondition);
+    fx.clock.advance(59);
+    assert_eq!(
+        code(fx.client.reserve(reserve("a", 1, 0)).await),
+        Code::FailedPrecondition
+    );
+    fx.clock.advance(1);
+    fx.client.reserve(reserve("a", 1, 0)).await.unwrap();
+}
+
+// synth-201~2: get_stale lists items not changed lately, oldest first.
+
+impl Fixture {
+    async fn stale(&mut self, older_than: u64) -> Vec<String> {
+        let request = StaleRequest { older_than };
+        let stale = self.client.get_stale(request).await.unwrap().into_inner();
+        skus(&stale.items).into_iter().map(String::from).collect()
+    }
+}
+
+#[tokio::test]
+async fn stale_items_come_least_recently_changed_first() {
+    let mut fx = start(&[]).await;
+    fx.add(item("d", 1.0, 1)).await;
+    fx.add(item("b", 1.0, 1)).await;
+    fx.add(item("a", 1.0, 1)).await;
+    fx.clock.advance(10);
+    fx.add(item("c", 1.0, 1)).await;
+    fx.clock.advance(10);
+    fx.client.increase_quantity(change("a", 1)).await.unwrap();
+    assert_eq!(fx.get("a").await.unwrap().updated_at, START + 20);
+
+    fx.clock.advance(80);
+    assert_eq!(fx.stale(0).await, ["b", "d", "c", "a"]);
+    assert_eq!(fx.stale(85).await, ["b", "d", "c"]);
+    assert_eq!(fx.stale(90).await, ["b", "d"]);
+    assert!(fx.stale(100).await.is_empty());
+}
+
+// synth-202: chunked bulk writes keep the chunks before a failure.
+
+#[tokio::test]
+async fn chunked_repricing_keeps_the_chunks_before_a_failure() {
+    let args = ["--below-cost-policy", "reject"];
+    let mut whole = start(&args).await;
+    let mut chunked = start(&[&args[..], &["--batch-chunk-size", "2"]].concat()).await;
+    for fx in [&mut whole, &mut chunked] {
+        fx.add(in_category("tools", costed("a", 10.0, 1.0))).await;
+        fx.add(in_category("tools", costed("b", 10.0, 1.0))).await;
+        fx.add(in_category("tools", costed("c", 10.0, 8.0))).await;
+    }
+
+    let failed = whole
+        .client
+        .adjust_price_percent(adjust("tools", -50.0, None))
+        .await;
+    assert_eq!(code(failed), Code::FailedPrecondition);
+    assert_eq!(whole.stock("a").await.price, 10.0);
+
+    let failed = chunked
+        .client
+        .adjust_price_percent(adjust("tools", -50.0, None))
+        .await
+        .unwrap_err();
+    assert_eq!(failed.code(), Code::FailedPrecondition);
+    assert!(failed
+        .message()
+        .ends_with("(2 of 3 items were processed first)"));
+    assert_eq!(chunked.stock("a").await.price, 5.0);
+    assert_eq!(chunked.stock("b").await.price, 5.0);
+    assert_eq!(chunked.stock("c").await.price, 10.0);
+
+    let tagged = chunked
+        .client
+        .add_tag_to_items(tag("sale", &["a", "gone", "b", "c"]))
+        .await
+        .unwrap()
+        .into_inner();
+    let succeeded: Vec<_> = tagged.results.iter().map(|result| result.success).collect();
+    assert_eq!(succeeded, [true, false, true, true]);
+    assert_eq!(chunked.get("c").await.unwrap().tags, ["sale"]);
+}
+
+// synth-202~2: bundles are checked against their components when added.
+
+#[tokio::test]
+async fn bundles_need_existing_unbundled_components() {
+    let mut fx = start(&[]).await;
+    fx.add(item("x", 1.0, 10)).await;
+    fx.add(bundle("kit", &[("x", 2)])).await;
+
+    let missing = fx
+        .client
+        .add(bundle("a", &[("x", 1), ("gone", 1)]))
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+    assert_eq!(missing.message(), format!("gone: {}", super::NO_ITEM_ERR));
+    let nested = fx.client.add(bundle("b", &[("kit", 1)])).await;
+    assert_eq!(code(nested), Code::InvalidArgument);
+    let own = fx.client.add(bundle("c", &[("c", 1)])).await;
+    assert_eq!(code(own), Code::InvalidArgument);
+    let empty_line = fx.client.add(bundle("d", &[("x", 0)])).await;
+    assert_eq!(code(empty_line), Code::InvalidArgument);
+
+    // Repeated components merge into one line.
+    fx.add(bundle("pair", &[("x", 1), ("x", 2)])).await;
+    let pair = fx.get("pair").await.unwrap();
+    assert_eq!(pair.bundle.len(), 1);
+    assert_eq!(pair.bundle[0].quantity, 3);
+    assert_eq!(pair.stock.unwrap().quantity, 3);
+}
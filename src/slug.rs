@@ -0,0 +1,45 @@
+/// Lowercases a name, turns whitespace into dashes and drops anything else
+/// that isn't alphanumeric.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_owned()
+}
+
+/// Returns `base`, or `base-N` for the smallest N >= 2 that isn't taken.
+pub fn unique(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_owned();
+    }
+
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_slugified() {
+        assert_eq!(slugify("Red  Widget"), "red-widget");
+        assert_eq!(slugify(" Café Crème! 2-pack "), "café-crème-2-pack");
+        assert_eq!(slugify("--"), "");
+    }
+
+    #[test]
+    fn collisions_get_the_next_free_suffix() {
+        let taken = ["widget", "widget-2"];
+        assert_eq!(unique("gadget", |s| taken.contains(&s)), "gadget");
+        assert_eq!(unique("widget", |s| taken.contains(&s)), "widget-3");
+    }
+}
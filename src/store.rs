@@ -11,6 +11,48 @@ pub struct ItemStock {
     pub price: f32,
     #[prost(uint64, tag = "2")]
     pub quantity: u64,
+    #[prost(bool, tag = "3")]
+    pub backorder: bool,
+    #[prost(float, optional, tag = "4")]
+    pub cost: ::core::option::Option<f32>,
+    #[prost(uint64, tag = "5")]
+    pub reorder_point: u64,
+    #[prost(enumeration = "Unit", tag = "6")]
+    pub unit: i32,
+    #[prost(btree_map = "string, float", tag = "7")]
+    pub prices: ::prost::alloc::collections::BTreeMap<::prost::alloc::string::String, f32>,
+    #[prost(message, repeated, tag = "8")]
+    pub schedule: ::prost::alloc::vec::Vec<ScheduledPrice>,
+    #[prost(string, tag = "9")]
+    pub stock_unit: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub sale_unit: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "11")]
+    pub sale_units_per: u64,
+    #[prost(uint64, tag = "12")]
+    pub sale_quantity: u64,
+    #[prost(uint64, tag = "13")]
+    pub reserved: u64,
+    #[prost(uint64, tag = "14")]
+    pub oversold: u64,
+    #[prost(uint64, tag = "15")]
+    pub reorder_quantity: u64,
+    #[prost(btree_map = "string, uint64", tag = "16")]
+    pub warehouses: ::prost::alloc::collections::BTreeMap<::prost::alloc::string::String, u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScheduledPrice {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(float, tag = "2")]
+    pub price: f32,
+    #[prost(uint64, tag = "3")]
+    pub effective_at: u64,
+    #[prost(string, tag = "4")]
+    pub price_list: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub reason: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -29,16 +71,103 @@ pub struct Item {
     pub stock: ::core::option::Option<ItemStock>,
     #[prost(message, optional, tag = "3")]
     pub information: ::core::option::Option<ItemInformation>,
+    #[prost(uint64, tag = "4")]
+    pub ttl: u64,
+    #[prost(uint64, tag = "5")]
+    pub created_at: u64,
+    #[prost(uint32, tag = "6")]
+    pub featured_weight: u32,
+    #[prost(string, tag = "7")]
+    pub category: ::prost::alloc::string::String,
+    #[prost(bool, tag = "8")]
+    pub deleted: bool,
+    #[prost(uint64, tag = "9")]
+    pub deleted_at: u64,
+    #[prost(string, repeated, tag = "10")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag = "11")]
+    pub slug: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "12")]
+    pub margin: ::core::option::Option<Margin>,
+    #[prost(message, repeated, tag = "13")]
+    pub bundle: ::prost::alloc::vec::Vec<BundleLine>,
+    #[prost(string, tag = "14")]
+    pub tax_category: ::prost::alloc::string::String,
+    #[prost(enumeration = "Availability", tag = "15")]
+    pub availability: i32,
+    #[prost(string, tag = "16")]
+    pub parent_sku: ::prost::alloc::string::String,
+    #[prost(enumeration = "LifecycleState", tag = "17")]
+    pub state: i32,
+    #[prost(uint64, tag = "18")]
+    pub updated_at: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BundleLine {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Margin {
+    #[prost(float, tag = "1")]
+    pub amount: f32,
+    #[prost(float, tag = "2")]
+    pub percent: f32,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Items {
     #[prost(message, repeated, tag = "2")]
     pub items: ::prost::alloc::vec::Vec<Item>,
+    #[prost(string, tag = "3")]
+    pub etag: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub not_modified: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Snapshot {
+    #[prost(message, repeated, tag = "2")]
+    pub items: ::prost::alloc::vec::Vec<Item>,
+    #[prost(uint64, tag = "5")]
+    pub version: u64,
+    #[prost(uint64, tag = "6")]
+    pub taken_at: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WalEntry {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub item: ::core::option::Option<Item>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ItemAll {
+    #[prost(string, tag = "1")]
+    pub etag: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub filter: ::core::option::Option<ExportFilter>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ItemAll {}
+pub struct ExportFilter {
+    #[prost(message, optional, tag = "1")]
+    pub tags: ::core::option::Option<TagsRequest>,
+    #[prost(float, optional, tag = "2")]
+    pub min_price: ::core::option::Option<f32>,
+    #[prost(float, optional, tag = "3")]
+    pub max_price: ::core::option::Option<f32>,
+    #[prost(string, tag = "4")]
+    pub category: ::prost::alloc::string::String,
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QuantityChangeRequest {
@@ -46,6 +175,14 @@ pub struct QuantityChangeRequest {
     pub sku: ::prost::alloc::string::String,
     #[prost(uint64, tag = "2")]
     pub quantity: u64,
+    #[prost(string, tag = "3")]
+    pub operation_id: ::prost::alloc::string::String,
+    #[prost(double, tag = "4")]
+    pub measured_quantity: f64,
+    #[prost(string, tag = "5")]
+    pub unit: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub warehouse: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -54,6 +191,10 @@ pub struct PriceChangeRequest {
     pub sku: ::prost::alloc::string::String,
     #[prost(float, tag = "2")]
     pub price: f32,
+    #[prost(uint64, tag = "3")]
+    pub effective_at: u64,
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -86,210 +227,2039 @@ pub struct InventoryUpdateResponse {
     pub price: f32,
     #[prost(uint64, tag = "3")]
     pub quantity: u64,
+    #[prost(string, tag = "4")]
+    pub scheduled_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "5")]
+    pub drawn: ::prost::alloc::vec::Vec<WarehouseDraw>,
 }
-/// Generated client implementations.
-pub mod inventory_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::http::Uri;
-    use tonic::codegen::*;
-    #[derive(Debug, Clone)]
-    pub struct InventoryClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl InventoryClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: std::convert::TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WarehouseDraw {
+    #[prost(string, tag = "1")]
+    pub warehouse: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedRequest {
+    #[prost(bool, tag = "1")]
+    pub active_only: bool,
+    #[prost(bool, tag = "2")]
+    pub in_stock_only: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Feed {
+    #[prost(string, tag = "1")]
+    pub json: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionRequest {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(oneof = "session_request::Command", tags = "2, 3, 4")]
+    pub command: ::core::option::Option<session_request::Command>,
+}
+/// Nested message and enum types in `SessionRequest`.
+pub mod session_request {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Command {
+        #[prost(message, tag = "2")]
+        Get(super::ItemIdentifier),
+        #[prost(message, tag = "3")]
+        Decrease(super::QuantityChangeRequest),
+        #[prost(message, tag = "4")]
+        Subscribe(super::ItemIdentifier),
     }
-    impl<T> InventoryClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InventoryClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
-                Into<StdError> + Send + Sync,
-        {
-            InventoryClient::new(InterceptedService::new(inner, interceptor))
-        }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
-        }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
-        }
-        pub async fn add(
-            &mut self,
-            request: impl tonic::IntoRequest<super::Item>,
-        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Add");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn get(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ItemIdentifier>,
-        ) -> Result<tonic::Response<super::Item>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Get");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn get_all(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ItemAll>,
-        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetAll");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn remove(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ItemIdentifier>,
-        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Remove");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn increase_quantity(
-            &mut self,
-            request: impl tonic::IntoRequest<super::QuantityChangeRequest>,
-        ) -> Result<tonic::Response<super::InventoryUpdateResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/IncreaseQuantity");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn decrease_quantity(
-            &mut self,
-            request: impl tonic::IntoRequest<super::QuantityChangeRequest>,
-        ) -> Result<tonic::Response<super::InventoryUpdateResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/DecreaseQuantity");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn update_price(
-            &mut self,
-            request: impl tonic::IntoRequest<super::PriceChangeRequest>,
-        ) -> Result<tonic::Response<super::InventoryUpdateResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/UpdatePrice");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        pub async fn watch(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ItemIdentifier>,
-        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Item>>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Watch");
-            self.inner
-                .server_streaming(request.into_request(), path, codec)
-                .await
-        }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionError {
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionResponse {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(oneof = "session_response::Outcome", tags = "2, 3, 4, 5")]
+    pub outcome: ::core::option::Option<session_response::Outcome>,
+}
+/// Nested message and enum types in `SessionResponse`.
+pub mod session_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Outcome {
+        #[prost(message, tag = "2")]
+        Item(super::Item),
+        #[prost(message, tag = "3")]
+        Update(super::InventoryUpdateResponse),
+        #[prost(message, tag = "4")]
+        Error(super::SessionError),
+        #[prost(message, tag = "5")]
+        Changed(super::Item),
     }
 }
-/// Generated server implementations.
-pub mod inventory_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with InventoryServer.
-    #[async_trait]
-    pub trait Inventory: Send + Sync + 'static {
-        async fn add(
-            &self,
-            request: tonic::Request<super::Item>,
-        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
-        async fn get(
-            &self,
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedRandomRequest {
+    #[prost(uint32, tag = "1")]
+    pub count: u32,
+    #[prost(uint64, optional, tag = "2")]
+    pub seed: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TopRequest {
+    #[prost(uint32, tag = "1")]
+    pub k: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CategoryRepriceRequest {
+    #[prost(string, tag = "1")]
+    pub category: ::prost::alloc::string::String,
+    #[prost(double, tag = "2")]
+    pub percent: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RepriceLine {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(float, tag = "2")]
+    pub old_price: f32,
+    #[prost(float, tag = "3")]
+    pub new_price: f32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RepricePreview {
+    #[prost(message, repeated, tag = "1")]
+    pub lines: ::prost::alloc::vec::Vec<RepriceLine>,
+    #[prost(double, tag = "2")]
+    pub revenue_delta: f64,
+    #[prost(double, tag = "3")]
+    pub margin_before: f64,
+    #[prost(double, tag = "4")]
+    pub margin_after: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DescribeRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldDescription {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub r#type: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub repeated: bool,
+    #[prost(bool, tag = "4")]
+    pub required: bool,
+    #[prost(bool, tag = "5")]
+    pub server_set: bool,
+    #[prost(string, repeated, tag = "6")]
+    pub constraints: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ItemDescription {
+    #[prost(message, repeated, tag = "1")]
+    pub fields: ::prost::alloc::vec::Vec<FieldDescription>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingRequest {
+    #[prost(uint64, tag = "1")]
+    pub nonce: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingResponse {
+    #[prost(uint64, tag = "1")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "2")]
+    pub server_time: u64,
+    #[prost(bool, tag = "3")]
+    pub ready: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TagRequest {
+    #[prost(string, tag = "1")]
+    pub tag: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "2")]
+    pub skus: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SkuResult {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub success: bool,
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BulkResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<SkuResult>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchManyRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub skus: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "2")]
+    pub unsubscribe: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ItemEvent {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub item: ::core::option::Option<Item>,
+    #[prost(bool, tag = "3")]
+    pub removed: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TotalValueRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TotalValue {
+    #[prost(double, tag = "1")]
+    pub total: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveRequest {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    #[prost(uint64, tag = "3")]
+    pub ttl: u64,
+    #[prost(double, tag = "4")]
+    pub measured_quantity: f64,
+    #[prost(string, tag = "5")]
+    pub unit: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveResponse {
+    #[prost(string, tag = "1")]
+    pub reservation_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub available: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReleaseRequest {
+    #[prost(string, tag = "1")]
+    pub reservation_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SlugRequest {
+    #[prost(string, tag = "1")]
+    pub slug: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchFailure {
+    #[prost(uint64, tag = "1")]
+    pub index: u64,
+    #[prost(string, tag = "2")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchSummary {
+    #[prost(uint64, tag = "1")]
+    pub added: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub failures: ::prost::alloc::vec::Vec<BatchFailure>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceAdjustRequest {
+    #[prost(string, tag = "1")]
+    pub category: ::prost::alloc::string::String,
+    #[prost(double, tag = "2")]
+    pub percent: f64,
+    #[prost(float, optional, tag = "3")]
+    pub floor: ::core::option::Option<f32>,
+    #[prost(bool, tag = "4")]
+    pub floor_at_cost: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceAdjustResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub lines: ::prost::alloc::vec::Vec<RepriceLine>,
+    #[prost(string, repeated, tag = "2")]
+    pub clamped: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MetricsRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Metrics {
+    #[prost(uint64, tag = "1")]
+    pub slow_lock_acquisitions: u64,
+    #[prost(uint64, tag = "2")]
+    pub active_watch_streams: u64,
+    #[prost(uint64, tag = "3")]
+    pub watch_rounds_skipped: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ItemStatus {
+    #[prost(message, optional, tag = "1")]
+    pub item: ::core::option::Option<Item>,
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CategoryListRequest {
+    #[prost(string, tag = "1")]
+    pub category: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub page_size: u32,
+    #[prost(string, tag = "3")]
+    pub cursor: ::prost::alloc::string::String,
+    #[prost(enumeration = "CategorySort", tag = "4")]
+    pub sort: i32,
+    #[prost(bool, tag = "5")]
+    pub active_only: bool,
+    #[prost(bool, tag = "6")]
+    pub in_stock_only: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CategoryPage {
+    #[prost(message, repeated, tag = "1")]
+    pub items: ::prost::alloc::vec::Vec<Item>,
+    #[prost(string, tag = "2")]
+    pub next_cursor: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub total: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimLine {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    #[prost(double, tag = "3")]
+    pub measured_quantity: f64,
+    #[prost(string, tag = "4")]
+    pub unit: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub lines: ::prost::alloc::vec::Vec<ClaimLine>,
+    #[prost(uint64, tag = "2")]
+    pub ttl: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimResponse {
+    #[prost(string, tag = "1")]
+    pub claim_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimIdRequest {
+    #[prost(string, tag = "1")]
+    pub claim_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PartialDecreaseResponse {
+    #[prost(message, optional, tag = "1")]
+    pub update: ::core::option::Option<InventoryUpdateResponse>,
+    #[prost(uint64, tag = "2")]
+    pub decreased: u64,
+    #[prost(uint64, tag = "3")]
+    pub shortfall: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetManyRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub skus: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag = "2")]
+    pub preserve_order: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetManyResult {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub item: ::core::option::Option<Item>,
+    #[prost(bool, tag = "3")]
+    pub missing: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetManyResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub items: ::prost::alloc::vec::Vec<Item>,
+    #[prost(string, repeated, tag = "2")]
+    pub missing: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "3")]
+    pub results: ::prost::alloc::vec::Vec<GetManyResult>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoundPriceRequest {
+    #[prost(double, tag = "1")]
+    pub price: f64,
+    #[prost(string, tag = "2")]
+    pub currency: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoundPriceResponse {
+    #[prost(double, tag = "1")]
+    pub price: f64,
+    #[prost(uint32, tag = "2")]
+    pub decimals: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConsistencyRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndexIssue {
+    #[prost(string, tag = "1")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub index: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub sku: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConsistencyReport {
+    #[prost(message, repeated, tag = "1")]
+    pub issues: ::prost::alloc::vec::Vec<IndexIssue>,
+    #[prost(uint32, tag = "2")]
+    pub repaired: u32,
+    #[prost(message, repeated, tag = "3")]
+    pub violations: ::prost::alloc::vec::Vec<InvariantViolation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InvariantViolation {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub rule: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub detail: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MaintenanceRequest {
+    #[prost(bool, tag = "1")]
+    pub enabled: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MaintenanceResponse {
+    #[prost(bool, tag = "1")]
+    pub enabled: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub items: ::prost::alloc::vec::Vec<Item>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertResult {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(enumeration = "UpsertOutcome", tag = "2")]
+    pub outcome: i32,
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<UpsertResult>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FormatPriceRequest {
+    #[prost(double, tag = "1")]
+    pub amount: f64,
+    #[prost(string, tag = "2")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub locale: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FormatPriceResponse {
+    #[prost(string, tag = "1")]
+    pub formatted: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramRequest {
+    #[prost(uint64, repeated, tag = "1")]
+    pub bounds: ::prost::alloc::vec::Vec<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramBucket {
+    #[prost(uint64, tag = "1")]
+    pub min: u64,
+    #[prost(uint64, optional, tag = "2")]
+    pub max: ::core::option::Option<u64>,
+    #[prost(uint64, tag = "3")]
+    pub count: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuantityHistogram {
+    #[prost(message, repeated, tag = "1")]
+    pub buckets: ::prost::alloc::vec::Vec<HistogramBucket>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TagsRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(enumeration = "TagMatch", tag = "2")]
+    pub mode: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceSchedule {
+    #[prost(message, repeated, tag = "1")]
+    pub changes: ::prost::alloc::vec::Vec<ScheduledPrice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelScheduleRequest {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportChangesRequest {
+    #[prost(uint64, tag = "1")]
+    pub offset: u64,
+    #[prost(uint64, tag = "2")]
+    pub since: u64,
+    #[prost(bool, tag = "3")]
+    pub follow: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeEvent {
+    #[prost(uint64, tag = "1")]
+    pub offset: u64,
+    #[prost(uint64, tag = "2")]
+    pub at: u64,
+    #[prost(string, tag = "3")]
+    pub rpc: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub detail: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompactRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompactReport {
+    #[prost(uint64, tag = "1")]
+    pub tombstones_purged: u64,
+    #[prost(uint64, tag = "2")]
+    pub expired_purged: u64,
+    #[prost(uint64, tag = "3")]
+    pub index_repairs: u64,
+    #[prost(uint64, tag = "4")]
+    pub bytes_reclaimed: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuarantineRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuarantinedItem {
+    #[prost(message, optional, tag = "1")]
+    pub item: ::core::option::Option<Item>,
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Quarantine {
+    #[prost(message, repeated, tag = "1")]
+    pub items: ::prost::alloc::vec::Vec<QuarantinedItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaxCategoryRequest {
+    #[prost(string, tag = "1")]
+    pub tax_category: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaxCategoryChange {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub tax_category: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceWatchRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub skus: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceChange {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(float, tag = "2")]
+    pub old_price: f32,
+    #[prost(float, tag = "3")]
+    pub new_price: f32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditLogRequest {
+    #[prost(uint64, tag = "1")]
+    pub start: u64,
+    #[prost(uint64, tag = "2")]
+    pub end: u64,
+    #[prost(uint32, tag = "3")]
+    pub page_size: u32,
+    #[prost(string, tag = "4")]
+    pub cursor: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditLogPage {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<ChangeEvent>,
+    #[prost(string, tag = "2")]
+    pub next_cursor: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PollRequest {
+    #[prost(uint64, tag = "1")]
+    pub since_version: u64,
+    #[prost(uint64, tag = "2")]
+    pub timeout_ms: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PollResponse {
+    #[prost(uint64, tag = "1")]
+    pub version: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub items: ::prost::alloc::vec::Vec<Item>,
+    #[prost(string, repeated, tag = "3")]
+    pub removed: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag = "4")]
+    pub reset: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreatedRangeRequest {
+    #[prost(uint64, tag = "1")]
+    pub start: u64,
+    #[prost(uint64, tag = "2")]
+    pub end: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StaleRequest {
+    #[prost(uint64, tag = "1")]
+    pub older_than: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchRequest {
+    #[prost(string, tag = "1")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(enumeration = "SearchMode", tag = "2")]
+    pub mode: i32,
+    #[prost(uint32, tag = "3")]
+    pub max_distance: u32,
+    #[prost(uint32, tag = "4")]
+    pub limit: u32,
+    #[prost(bool, tag = "5")]
+    pub case_sensitive: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatch {
+    #[prost(message, optional, tag = "1")]
+    pub item: ::core::option::Option<Item>,
+    #[prost(uint32, tag = "2")]
+    pub distance: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchResults {
+    #[prost(message, repeated, tag = "1")]
+    pub matches: ::prost::alloc::vec::Vec<SearchMatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuoteLine {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuoteRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub lines: ::prost::alloc::vec::Vec<QuoteLine>,
+    #[prost(double, tag = "2")]
+    pub discount_percent: f64,
+    #[prost(string, repeated, tag = "3")]
+    pub currencies: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuotedLine {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    #[prost(double, tag = "3")]
+    pub unit_price: f64,
+    #[prost(double, tag = "4")]
+    pub subtotal: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CurrencyTotal {
+    #[prost(string, tag = "1")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(double, tag = "2")]
+    pub subtotal: f64,
+    #[prost(double, tag = "3")]
+    pub discount: f64,
+    #[prost(double, tag = "4")]
+    pub total: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Quote {
+    #[prost(message, repeated, tag = "1")]
+    pub lines: ::prost::alloc::vec::Vec<QuotedLine>,
+    #[prost(message, repeated, tag = "2")]
+    pub totals: ::prost::alloc::vec::Vec<CurrencyTotal>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReorderCostRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReorderLine {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    #[prost(uint64, tag = "3")]
+    pub reorder: u64,
+    #[prost(float, tag = "4")]
+    pub unit_cost: f32,
+    #[prost(bool, tag = "5")]
+    pub at_price: bool,
+    #[prost(double, tag = "6")]
+    pub cost: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReorderCost {
+    #[prost(message, repeated, tag = "1")]
+    pub lines: ::prost::alloc::vec::Vec<ReorderLine>,
+    #[prost(double, tag = "2")]
+    pub total: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceHistory {
+    #[prost(message, repeated, tag = "1")]
+    pub changes: ::prost::alloc::vec::Vec<ChangeEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StateChange {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(enumeration = "LifecycleState", tag = "2")]
+    pub state: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StockCount {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub counted: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StockCorrection {
+    #[prost(string, tag = "1")]
+    pub sku: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub previous: u64,
+    #[prost(uint64, tag = "3")]
+    pub counted: u64,
+    #[prost(int64, tag = "4")]
+    pub variance: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StocktakeSummary {
+    #[prost(uint64, tag = "1")]
+    pub counted: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub corrections: ::prost::alloc::vec::Vec<StockCorrection>,
+    #[prost(message, repeated, tag = "3")]
+    pub failures: ::prost::alloc::vec::Vec<BatchFailure>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Unit {
+    Each = 0,
+    Kg = 1,
+    Liter = 2,
+    Meter = 3,
+}
+impl Unit {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Unit::Each => "EACH",
+            Unit::Kg => "KG",
+            Unit::Liter => "LITER",
+            Unit::Meter => "METER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "EACH" => Some(Self::Each),
+            "KG" => Some(Self::Kg),
+            "LITER" => Some(Self::Liter),
+            "METER" => Some(Self::Meter),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Availability {
+    Unset = 0,
+    InStock = 1,
+    LowStock = 2,
+    OutOfStock = 3,
+    Backordered = 4,
+}
+impl Availability {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Availability::Unset => "AVAILABILITY_UNSET",
+            Availability::InStock => "IN_STOCK",
+            Availability::LowStock => "LOW_STOCK",
+            Availability::OutOfStock => "OUT_OF_STOCK",
+            Availability::Backordered => "BACKORDERED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AVAILABILITY_UNSET" => Some(Self::Unset),
+            "IN_STOCK" => Some(Self::InStock),
+            "LOW_STOCK" => Some(Self::LowStock),
+            "OUT_OF_STOCK" => Some(Self::OutOfStock),
+            "BACKORDERED" => Some(Self::Backordered),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum LifecycleState {
+    Active = 0,
+    Draft = 1,
+    Discontinued = 2,
+    Archived = 3,
+}
+impl LifecycleState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            LifecycleState::Active => "ACTIVE",
+            LifecycleState::Draft => "DRAFT",
+            LifecycleState::Discontinued => "DISCONTINUED",
+            LifecycleState::Archived => "ARCHIVED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ACTIVE" => Some(Self::Active),
+            "DRAFT" => Some(Self::Draft),
+            "DISCONTINUED" => Some(Self::Discontinued),
+            "ARCHIVED" => Some(Self::Archived),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CategorySort {
+    Sku = 0,
+    PriceAsc = 1,
+    PriceDesc = 2,
+    Name = 3,
+    Quantity = 4,
+}
+impl CategorySort {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CategorySort::Sku => "SKU",
+            CategorySort::PriceAsc => "PRICE_ASC",
+            CategorySort::PriceDesc => "PRICE_DESC",
+            CategorySort::Name => "NAME",
+            CategorySort::Quantity => "QUANTITY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SKU" => Some(Self::Sku),
+            "PRICE_ASC" => Some(Self::PriceAsc),
+            "PRICE_DESC" => Some(Self::PriceDesc),
+            "NAME" => Some(Self::Name),
+            "QUANTITY" => Some(Self::Quantity),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum UpsertOutcome {
+    Failed = 0,
+    Created = 1,
+    Updated = 2,
+}
+impl UpsertOutcome {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            UpsertOutcome::Failed => "FAILED",
+            UpsertOutcome::Created => "CREATED",
+            UpsertOutcome::Updated => "UPDATED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FAILED" => Some(Self::Failed),
+            "CREATED" => Some(Self::Created),
+            "UPDATED" => Some(Self::Updated),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TagMatch {
+    Any = 0,
+    All = 1,
+}
+impl TagMatch {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TagMatch::Any => "ANY",
+            TagMatch::All => "ALL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ANY" => Some(Self::Any),
+            "ALL" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SearchMode {
+    Substring = 0,
+    Fuzzy = 1,
+}
+impl SearchMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SearchMode::Substring => "SUBSTRING",
+            SearchMode::Fuzzy => "FUZZY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SUBSTRING" => Some(Self::Substring),
+            "FUZZY" => Some(Self::Fuzzy),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod inventory_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct InventoryClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl InventoryClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> InventoryClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InventoryClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
+                Into<StdError> + Send + Sync,
+        {
+            InventoryClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        pub async fn add(
+            &mut self,
+            request: impl tonic::IntoRequest<super::Item>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Add");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::Item>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Get");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_all(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemAll>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetAll");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn stream_all(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemAll>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Items>>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/StreamAll");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn remove(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Remove");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn increase_quantity(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QuantityChangeRequest>,
+        ) -> Result<tonic::Response<super::InventoryUpdateResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/IncreaseQuantity");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn decrease_quantity(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QuantityChangeRequest>,
+        ) -> Result<tonic::Response<super::InventoryUpdateResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/DecreaseQuantity");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn update_price(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PriceChangeRequest>,
+        ) -> Result<tonic::Response<super::InventoryUpdateResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/UpdatePrice");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn watch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Item>>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Watch");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn export_feed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FeedRequest>,
+        ) -> Result<tonic::Response<super::Feed>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ExportFeed");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn interactive(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::SessionRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::SessionResponse>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Interactive");
+            self.inner
+                .streaming(request.into_streaming_request(), path, codec)
+                .await
+        }
+        pub async fn get_weighted_random(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WeightedRandomRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetWeightedRandom");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_top_by_value(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TopRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetTopByValue");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn preview_category_reprice(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CategoryRepriceRequest>,
+        ) -> Result<tonic::Response<super::RepricePreview>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/store.Inventory/PreviewCategoryReprice");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn ping(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PingRequest>,
+        ) -> Result<tonic::Response<super::PingResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Ping");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn describe_item(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DescribeRequest>,
+        ) -> Result<tonic::Response<super::ItemDescription>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/DescribeItem");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn add_tag_to_items(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TagRequest>,
+        ) -> Result<tonic::Response<super::BulkResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/AddTagToItems");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn remove_tag_from_items(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TagRequest>,
+        ) -> Result<tonic::Response<super::BulkResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/RemoveTagFromItems");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn watch_many(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::WatchManyRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::ItemEvent>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/WatchMany");
+            self.inner
+                .streaming(request.into_streaming_request(), path, codec)
+                .await
+        }
+        pub async fn watch_total_value(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TotalValueRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::TotalValue>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/WatchTotalValue");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn reserve(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReserveRequest>,
+        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Reserve");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn release(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReleaseRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Release");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn update_name(
+            &mut self,
+            request: impl tonic::IntoRequest<super::NameChangeRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/UpdateName");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_by_slug(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SlugRequest>,
+        ) -> Result<tonic::Response<super::Item>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetBySlug");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn add_batch(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::Item>,
+        ) -> Result<tonic::Response<super::BatchSummary>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/AddBatch");
+            self.inner
+                .client_streaming(request.into_streaming_request(), path, codec)
+                .await
+        }
+        pub async fn adjust_price_percent(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PriceAdjustRequest>,
+        ) -> Result<tonic::Response<super::PriceAdjustResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/AdjustPricePercent");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_metrics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MetricsRequest>,
+        ) -> Result<tonic::Response<super::Metrics>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetMetrics");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_with_status(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::ItemStatus>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetWithStatus");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_by_category(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CategoryListRequest>,
+        ) -> Result<tonic::Response<super::CategoryPage>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ListByCategory");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn claim_items(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ClaimRequest>,
+        ) -> Result<tonic::Response<super::ClaimResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ClaimItems");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn release_claim(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ClaimIdRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ReleaseClaim");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn commit_claim(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ClaimIdRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/CommitClaim");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn decrease_quantity_partial(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QuantityChangeRequest>,
+        ) -> Result<tonic::Response<super::PartialDecreaseResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/store.Inventory/DecreaseQuantityPartial");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_many(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetManyRequest>,
+        ) -> Result<tonic::Response<super::GetManyResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetMany");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn round_price(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RoundPriceRequest>,
+        ) -> Result<tonic::Response<super::RoundPriceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/RoundPrice");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn verify_consistency(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ConsistencyRequest>,
+        ) -> Result<tonic::Response<super::ConsistencyReport>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/VerifyConsistency");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn repair_indexes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ConsistencyRequest>,
+        ) -> Result<tonic::Response<super::ConsistencyReport>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/RepairIndexes");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn set_maintenance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MaintenanceRequest>,
+        ) -> Result<tonic::Response<super::MaintenanceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/SetMaintenance");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn upsert_many(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpsertRequest>,
+        ) -> Result<tonic::Response<super::UpsertResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/UpsertMany");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn format_price(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FormatPriceRequest>,
+        ) -> Result<tonic::Response<super::FormatPriceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/FormatPrice");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_quantity_histogram(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HistogramRequest>,
+        ) -> Result<tonic::Response<super::QuantityHistogram>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/store.Inventory/GetQuantityHistogram");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_by_tags(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TagsRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetByTags");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_price_schedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::PriceSchedule>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetPriceSchedule");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn cancel_scheduled_price(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelScheduleRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/store.Inventory/CancelScheduledPrice");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn export_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExportChangesRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::ChangeEvent>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ExportChanges");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn compact(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CompactRequest>,
+        ) -> Result<tonic::Response<super::CompactReport>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Compact");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_quarantine(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QuarantineRequest>,
+        ) -> Result<tonic::Response<super::Quarantine>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetQuarantine");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_by_tax_category(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TaxCategoryRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ListByTaxCategory");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn set_tax_category(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TaxCategoryChange>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/SetTaxCategory");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn watch_price_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PriceWatchRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::PriceChange>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/WatchPriceChanges");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
+        pub async fn get_audit_log(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AuditLogRequest>,
+        ) -> Result<tonic::Response<super::AuditLogPage>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetAuditLog");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_variants(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/ListVariants");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn poll_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PollRequest>,
+        ) -> Result<tonic::Response<super::PollResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/PollChanges");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_created_between(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreatedRangeRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetCreatedBetween");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn search(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchRequest>,
+        ) -> Result<tonic::Response<super::SearchResults>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/Search");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn quote_cart(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QuoteRequest>,
+        ) -> Result<tonic::Response<super::Quote>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/QuoteCart");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_reorder_cost(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReorderCostRequest>,
+        ) -> Result<tonic::Response<super::ReorderCost>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetReorderCost");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn get_price_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::PriceHistory>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetPriceHistory");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn transition_state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StateChange>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/TransitionState");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn stream_stocktake(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::StockCount>,
+        ) -> Result<tonic::Response<super::StocktakeSummary>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/StreamStocktake");
+            self.inner
+                .client_streaming(request.into_streaming_request(), path, codec)
+                .await
+        }
+        pub async fn get_stale(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StaleRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/store.Inventory/GetStale");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod inventory_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with InventoryServer.
+    #[async_trait]
+    pub trait Inventory: Send + Sync + 'static {
+        async fn add(
+            &self,
+            request: tonic::Request<super::Item>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        async fn get(
+            &self,
             request: tonic::Request<super::ItemIdentifier>,
         ) -> Result<tonic::Response<super::Item>, tonic::Status>;
         async fn get_all(
             &self,
             request: tonic::Request<super::ItemAll>,
         ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        /// Server streaming response type for the StreamAll method.
+        type StreamAllStream: futures_core::Stream<Item = Result<super::Items, tonic::Status>>
+            + Send
+            + 'static;
+        async fn stream_all(
+            &self,
+            request: tonic::Request<super::ItemAll>,
+        ) -> Result<tonic::Response<Self::StreamAllStream>, tonic::Status>;
         async fn remove(
             &self,
             request: tonic::Request<super::ItemIdentifier>,
@@ -314,6 +2284,234 @@ pub mod inventory_server {
             &self,
             request: tonic::Request<super::ItemIdentifier>,
         ) -> Result<tonic::Response<Self::WatchStream>, tonic::Status>;
+        async fn export_feed(
+            &self,
+            request: tonic::Request<super::FeedRequest>,
+        ) -> Result<tonic::Response<super::Feed>, tonic::Status>;
+        /// Server streaming response type for the Interactive method.
+        type InteractiveStream: futures_core::Stream<Item = Result<super::SessionResponse, tonic::Status>>
+            + Send
+            + 'static;
+        async fn interactive(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::SessionRequest>>,
+        ) -> Result<tonic::Response<Self::InteractiveStream>, tonic::Status>;
+        async fn get_weighted_random(
+            &self,
+            request: tonic::Request<super::WeightedRandomRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        async fn get_top_by_value(
+            &self,
+            request: tonic::Request<super::TopRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        async fn preview_category_reprice(
+            &self,
+            request: tonic::Request<super::CategoryRepriceRequest>,
+        ) -> Result<tonic::Response<super::RepricePreview>, tonic::Status>;
+        async fn ping(
+            &self,
+            request: tonic::Request<super::PingRequest>,
+        ) -> Result<tonic::Response<super::PingResponse>, tonic::Status>;
+        async fn describe_item(
+            &self,
+            request: tonic::Request<super::DescribeRequest>,
+        ) -> Result<tonic::Response<super::ItemDescription>, tonic::Status>;
+        async fn add_tag_to_items(
+            &self,
+            request: tonic::Request<super::TagRequest>,
+        ) -> Result<tonic::Response<super::BulkResponse>, tonic::Status>;
+        async fn remove_tag_from_items(
+            &self,
+            request: tonic::Request<super::TagRequest>,
+        ) -> Result<tonic::Response<super::BulkResponse>, tonic::Status>;
+        /// Server streaming response type for the WatchMany method.
+        type WatchManyStream: futures_core::Stream<Item = Result<super::ItemEvent, tonic::Status>>
+            + Send
+            + 'static;
+        async fn watch_many(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::WatchManyRequest>>,
+        ) -> Result<tonic::Response<Self::WatchManyStream>, tonic::Status>;
+        /// Server streaming response type for the WatchTotalValue method.
+        type WatchTotalValueStream: futures_core::Stream<Item = Result<super::TotalValue, tonic::Status>>
+            + Send
+            + 'static;
+        async fn watch_total_value(
+            &self,
+            request: tonic::Request<super::TotalValueRequest>,
+        ) -> Result<tonic::Response<Self::WatchTotalValueStream>, tonic::Status>;
+        async fn reserve(
+            &self,
+            request: tonic::Request<super::ReserveRequest>,
+        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
+        async fn release(
+            &self,
+            request: tonic::Request<super::ReleaseRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        async fn update_name(
+            &self,
+            request: tonic::Request<super::NameChangeRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        async fn get_by_slug(
+            &self,
+            request: tonic::Request<super::SlugRequest>,
+        ) -> Result<tonic::Response<super::Item>, tonic::Status>;
+        async fn add_batch(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::Item>>,
+        ) -> Result<tonic::Response<super::BatchSummary>, tonic::Status>;
+        async fn adjust_price_percent(
+            &self,
+            request: tonic::Request<super::PriceAdjustRequest>,
+        ) -> Result<tonic::Response<super::PriceAdjustResponse>, tonic::Status>;
+        async fn get_metrics(
+            &self,
+            request: tonic::Request<super::MetricsRequest>,
+        ) -> Result<tonic::Response<super::Metrics>, tonic::Status>;
+        async fn get_with_status(
+            &self,
+            request: tonic::Request<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::ItemStatus>, tonic::Status>;
+        async fn list_by_category(
+            &self,
+            request: tonic::Request<super::CategoryListRequest>,
+        ) -> Result<tonic::Response<super::CategoryPage>, tonic::Status>;
+        async fn claim_items(
+            &self,
+            request: tonic::Request<super::ClaimRequest>,
+        ) -> Result<tonic::Response<super::ClaimResponse>, tonic::Status>;
+        async fn release_claim(
+            &self,
+            request: tonic::Request<super::ClaimIdRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        async fn commit_claim(
+            &self,
+            request: tonic::Request<super::ClaimIdRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        async fn decrease_quantity_partial(
+            &self,
+            request: tonic::Request<super::QuantityChangeRequest>,
+        ) -> Result<tonic::Response<super::PartialDecreaseResponse>, tonic::Status>;
+        async fn get_many(
+            &self,
+            request: tonic::Request<super::GetManyRequest>,
+        ) -> Result<tonic::Response<super::GetManyResponse>, tonic::Status>;
+        async fn round_price(
+            &self,
+            request: tonic::Request<super::RoundPriceRequest>,
+        ) -> Result<tonic::Response<super::RoundPriceResponse>, tonic::Status>;
+        async fn verify_consistency(
+            &self,
+            request: tonic::Request<super::ConsistencyRequest>,
+        ) -> Result<tonic::Response<super::ConsistencyReport>, tonic::Status>;
+        async fn repair_indexes(
+            &self,
+            request: tonic::Request<super::ConsistencyRequest>,
+        ) -> Result<tonic::Response<super::ConsistencyReport>, tonic::Status>;
+        async fn set_maintenance(
+            &self,
+            request: tonic::Request<super::MaintenanceRequest>,
+        ) -> Result<tonic::Response<super::MaintenanceResponse>, tonic::Status>;
+        async fn upsert_many(
+            &self,
+            request: tonic::Request<super::UpsertRequest>,
+        ) -> Result<tonic::Response<super::UpsertResponse>, tonic::Status>;
+        async fn format_price(
+            &self,
+            request: tonic::Request<super::FormatPriceRequest>,
+        ) -> Result<tonic::Response<super::FormatPriceResponse>, tonic::Status>;
+        async fn get_quantity_histogram(
+            &self,
+            request: tonic::Request<super::HistogramRequest>,
+        ) -> Result<tonic::Response<super::QuantityHistogram>, tonic::Status>;
+        async fn get_by_tags(
+            &self,
+            request: tonic::Request<super::TagsRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        async fn get_price_schedule(
+            &self,
+            request: tonic::Request<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::PriceSchedule>, tonic::Status>;
+        async fn cancel_scheduled_price(
+            &self,
+            request: tonic::Request<super::CancelScheduleRequest>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        /// Server streaming response type for the ExportChanges method.
+        type ExportChangesStream: futures_core::Stream<Item = Result<super::ChangeEvent, tonic::Status>>
+            + Send
+            + 'static;
+        async fn export_changes(
+            &self,
+            request: tonic::Request<super::ExportChangesRequest>,
+        ) -> Result<tonic::Response<Self::ExportChangesStream>, tonic::Status>;
+        async fn compact(
+            &self,
+            request: tonic::Request<super::CompactRequest>,
+        ) -> Result<tonic::Response<super::CompactReport>, tonic::Status>;
+        async fn get_quarantine(
+            &self,
+            request: tonic::Request<super::QuarantineRequest>,
+        ) -> Result<tonic::Response<super::Quarantine>, tonic::Status>;
+        async fn list_by_tax_category(
+            &self,
+            request: tonic::Request<super::TaxCategoryRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        async fn set_tax_category(
+            &self,
+            request: tonic::Request<super::TaxCategoryChange>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        /// Server streaming response type for the WatchPriceChanges method.
+        type WatchPriceChangesStream: futures_core::Stream<Item = Result<super::PriceChange, tonic::Status>>
+            + Send
+            + 'static;
+        async fn watch_price_changes(
+            &self,
+            request: tonic::Request<super::PriceWatchRequest>,
+        ) -> Result<tonic::Response<Self::WatchPriceChangesStream>, tonic::Status>;
+        async fn get_audit_log(
+            &self,
+            request: tonic::Request<super::AuditLogRequest>,
+        ) -> Result<tonic::Response<super::AuditLogPage>, tonic::Status>;
+        async fn list_variants(
+            &self,
+            request: tonic::Request<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        async fn poll_changes(
+            &self,
+            request: tonic::Request<super::PollRequest>,
+        ) -> Result<tonic::Response<super::PollResponse>, tonic::Status>;
+        async fn get_created_between(
+            &self,
+            request: tonic::Request<super::CreatedRangeRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
+        async fn search(
+            &self,
+            request: tonic::Request<super::SearchRequest>,
+        ) -> Result<tonic::Response<super::SearchResults>, tonic::Status>;
+        async fn quote_cart(
+            &self,
+            request: tonic::Request<super::QuoteRequest>,
+        ) -> Result<tonic::Response<super::Quote>, tonic::Status>;
+        async fn get_reorder_cost(
+            &self,
+            request: tonic::Request<super::ReorderCostRequest>,
+        ) -> Result<tonic::Response<super::ReorderCost>, tonic::Status>;
+        async fn get_price_history(
+            &self,
+            request: tonic::Request<super::ItemIdentifier>,
+        ) -> Result<tonic::Response<super::PriceHistory>, tonic::Status>;
+        async fn transition_state(
+            &self,
+            request: tonic::Request<super::StateChange>,
+        ) -> Result<tonic::Response<super::InventoryChangeResponse>, tonic::Status>;
+        async fn stream_stocktake(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::StockCount>>,
+        ) -> Result<tonic::Response<super::StocktakeSummary>, tonic::Status>;
+        async fn get_stale(
+            &self,
+            request: tonic::Request<super::StaleRequest>,
+        ) -> Result<tonic::Response<super::Items>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct InventoryServer<T: Inventory> {
@@ -370,13 +2568,1543 @@ pub mod inventory_server {
             match req.uri().path() {
                 "/store.Inventory/Add" => {
                     #[allow(non_camel_case_types)]
-                    struct AddSvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::Item> for AddSvc<T> {
+                    struct AddSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::Item> for AddSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(&mut self, request: tonic::Request<super::Item>) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).add(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Get" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for GetSvc<T> {
+                        type Response = super::Item;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemIdentifier>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetAll" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAllSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemAll> for GetAllSvc<T> {
+                        type Response = super::Items;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemAll>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_all(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetAllSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/StreamAll" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamAllSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::ServerStreamingService<super::ItemAll> for StreamAllSvc<T> {
+                        type Response = super::Items;
+                        type ResponseStream = T::StreamAllStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemAll>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).stream_all(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamAllSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Remove" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for RemoveSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemIdentifier>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).remove(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RemoveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/IncreaseQuantity" => {
+                    #[allow(non_camel_case_types)]
+                    struct IncreaseQuantitySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::QuantityChangeRequest>
+                        for IncreaseQuantitySvc<T>
+                    {
+                        type Response = super::InventoryUpdateResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QuantityChangeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).increase_quantity(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = IncreaseQuantitySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/DecreaseQuantity" => {
+                    #[allow(non_camel_case_types)]
+                    struct DecreaseQuantitySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::QuantityChangeRequest>
+                        for DecreaseQuantitySvc<T>
+                    {
+                        type Response = super::InventoryUpdateResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QuantityChangeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).decrease_quantity(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DecreaseQuantitySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/UpdatePrice" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdatePriceSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::PriceChangeRequest> for UpdatePriceSvc<T> {
+                        type Response = super::InventoryUpdateResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PriceChangeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update_price(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdatePriceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Watch" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::ServerStreamingService<super::ItemIdentifier> for WatchSvc<T> {
+                        type Response = super::Item;
+                        type ResponseStream = T::WatchStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemIdentifier>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).watch(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/ExportFeed" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExportFeedSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::FeedRequest> for ExportFeedSvc<T> {
+                        type Response = super::Feed;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FeedRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).export_feed(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExportFeedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Interactive" => {
+                    #[allow(non_camel_case_types)]
+                    struct InteractiveSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::StreamingService<super::SessionRequest> for InteractiveSvc<T> {
+                        type Response = super::SessionResponse;
+                        type ResponseStream = T::InteractiveStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::SessionRequest>>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).interactive(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = InteractiveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetWeightedRandom" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetWeightedRandomSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::WeightedRandomRequest>
+                        for GetWeightedRandomSvc<T>
+                    {
+                        type Response = super::Items;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WeightedRandomRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_weighted_random(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetWeightedRandomSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetTopByValue" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTopByValueSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::TopRequest> for GetTopByValueSvc<T> {
+                        type Response = super::Items;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TopRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_top_by_value(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetTopByValueSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/PreviewCategoryReprice" => {
+                    #[allow(non_camel_case_types)]
+                    struct PreviewCategoryRepriceSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::CategoryRepriceRequest>
+                        for PreviewCategoryRepriceSvc<T>
+                    {
+                        type Response = super::RepricePreview;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CategoryRepriceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).preview_category_reprice(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PreviewCategoryRepriceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Ping" => {
+                    #[allow(non_camel_case_types)]
+                    struct PingSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::PingRequest> for PingSvc<T> {
+                        type Response = super::PingResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PingRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).ping(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PingSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/DescribeItem" => {
+                    #[allow(non_camel_case_types)]
+                    struct DescribeItemSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::DescribeRequest> for DescribeItemSvc<T> {
+                        type Response = super::ItemDescription;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DescribeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).describe_item(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DescribeItemSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/AddTagToItems" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddTagToItemsSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::TagRequest> for AddTagToItemsSvc<T> {
+                        type Response = super::BulkResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TagRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).add_tag_to_items(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddTagToItemsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/RemoveTagFromItems" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveTagFromItemsSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::TagRequest> for RemoveTagFromItemsSvc<T> {
+                        type Response = super::BulkResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TagRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).remove_tag_from_items(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RemoveTagFromItemsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/WatchMany" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchManySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::StreamingService<super::WatchManyRequest> for WatchManySvc<T> {
+                        type Response = super::ItemEvent;
+                        type ResponseStream = T::WatchManyStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::WatchManyRequest>>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).watch_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchManySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/WatchTotalValue" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchTotalValueSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory>
+                        tonic::server::ServerStreamingService<super::TotalValueRequest>
+                        for WatchTotalValueSvc<T>
+                    {
+                        type Response = super::TotalValue;
+                        type ResponseStream = T::WatchTotalValueStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TotalValueRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).watch_total_value(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchTotalValueSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Reserve" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReserveSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ReserveRequest> for ReserveSvc<T> {
+                        type Response = super::ReserveResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReserveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Release" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReleaseSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ReleaseRequest> for ReleaseSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReleaseRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).release(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReleaseSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/UpdateName" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateNameSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::NameChangeRequest> for UpdateNameSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::NameChangeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update_name(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateNameSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetBySlug" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBySlugSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::SlugRequest> for GetBySlugSvc<T> {
+                        type Response = super::Item;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SlugRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_by_slug(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBySlugSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/AddBatch" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddBatchSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::ClientStreamingService<super::Item> for AddBatchSvc<T> {
+                        type Response = super::BatchSummary;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::Item>>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).add_batch(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddBatchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/AdjustPricePercent" => {
+                    #[allow(non_camel_case_types)]
+                    struct AdjustPricePercentSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::PriceAdjustRequest>
+                        for AdjustPricePercentSvc<T>
+                    {
+                        type Response = super::PriceAdjustResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PriceAdjustRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).adjust_price_percent(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AdjustPricePercentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetMetrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetMetricsSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::MetricsRequest> for GetMetricsSvc<T> {
+                        type Response = super::Metrics;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MetricsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_metrics(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetMetricsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetWithStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetWithStatusSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for GetWithStatusSvc<T> {
+                        type Response = super::ItemStatus;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemIdentifier>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_with_status(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetWithStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/ListByCategory" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListByCategorySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::CategoryListRequest>
+                        for ListByCategorySvc<T>
+                    {
+                        type Response = super::CategoryPage;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CategoryListRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_by_category(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListByCategorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/ClaimItems" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClaimItemsSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ClaimRequest> for ClaimItemsSvc<T> {
+                        type Response = super::ClaimResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClaimRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).claim_items(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ClaimItemsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/ReleaseClaim" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReleaseClaimSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ClaimIdRequest> for ReleaseClaimSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClaimIdRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).release_claim(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReleaseClaimSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/CommitClaim" => {
+                    #[allow(non_camel_case_types)]
+                    struct CommitClaimSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ClaimIdRequest> for CommitClaimSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClaimIdRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).commit_claim(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CommitClaimSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/DecreaseQuantityPartial" => {
+                    #[allow(non_camel_case_types)]
+                    struct DecreaseQuantityPartialSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::QuantityChangeRequest>
+                        for DecreaseQuantityPartialSvc<T>
+                    {
+                        type Response = super::PartialDecreaseResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QuantityChangeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).decrease_quantity_partial(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DecreaseQuantityPartialSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetMany" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetManySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::GetManyRequest> for GetManySvc<T> {
+                        type Response = super::GetManyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetManyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetManySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/RoundPrice" => {
+                    #[allow(non_camel_case_types)]
+                    struct RoundPriceSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::RoundPriceRequest> for RoundPriceSvc<T> {
+                        type Response = super::RoundPriceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RoundPriceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).round_price(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RoundPriceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/VerifyConsistency" => {
+                    #[allow(non_camel_case_types)]
+                    struct VerifyConsistencySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ConsistencyRequest>
+                        for VerifyConsistencySvc<T>
+                    {
+                        type Response = super::ConsistencyReport;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConsistencyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).verify_consistency(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = VerifyConsistencySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/RepairIndexes" => {
+                    #[allow(non_camel_case_types)]
+                    struct RepairIndexesSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ConsistencyRequest> for RepairIndexesSvc<T> {
+                        type Response = super::ConsistencyReport;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConsistencyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).repair_indexes(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RepairIndexesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/SetMaintenance" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetMaintenanceSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::MaintenanceRequest> for SetMaintenanceSvc<T> {
+                        type Response = super::MaintenanceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MaintenanceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).set_maintenance(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetMaintenanceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/UpsertMany" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpsertManySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::UpsertRequest> for UpsertManySvc<T> {
+                        type Response = super::UpsertResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpsertRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).upsert_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpsertManySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/FormatPrice" => {
+                    #[allow(non_camel_case_types)]
+                    struct FormatPriceSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::FormatPriceRequest> for FormatPriceSvc<T> {
+                        type Response = super::FormatPriceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FormatPriceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).format_price(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FormatPriceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetQuantityHistogram" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetQuantityHistogramSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::HistogramRequest>
+                        for GetQuantityHistogramSvc<T>
+                    {
+                        type Response = super::QuantityHistogram;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HistogramRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_quantity_histogram(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetQuantityHistogramSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetByTags" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetByTagsSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::TagsRequest> for GetByTagsSvc<T> {
+                        type Response = super::Items;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TagsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_by_tags(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetByTagsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetPriceSchedule" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPriceScheduleSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for GetPriceScheduleSvc<T> {
+                        type Response = super::PriceSchedule;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ItemIdentifier>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_price_schedule(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPriceScheduleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/CancelScheduledPrice" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelScheduledPriceSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::CancelScheduleRequest>
+                        for CancelScheduledPriceSvc<T>
+                    {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelScheduleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel_scheduled_price(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CancelScheduledPriceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/ExportChanges" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExportChangesSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory>
+                        tonic::server::ServerStreamingService<super::ExportChangesRequest>
+                        for ExportChangesSvc<T>
+                    {
+                        type Response = super::ChangeEvent;
+                        type ResponseStream = T::ExportChangesStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExportChangesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).export_changes(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExportChangesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/Compact" => {
+                    #[allow(non_camel_case_types)]
+                    struct CompactSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::CompactRequest> for CompactSvc<T> {
+                        type Response = super::CompactReport;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CompactRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).compact(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CompactSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetQuarantine" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetQuarantineSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::QuarantineRequest> for GetQuarantineSvc<T> {
+                        type Response = super::Quarantine;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QuarantineRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_quarantine(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetQuarantineSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/ListByTaxCategory" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListByTaxCategorySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::TaxCategoryRequest>
+                        for ListByTaxCategorySvc<T>
+                    {
+                        type Response = super::Items;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TaxCategoryRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_by_tax_category(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListByTaxCategorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/SetTaxCategory" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetTaxCategorySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::TaxCategoryChange> for SetTaxCategorySvc<T> {
                         type Response = super::InventoryChangeResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
-                        fn call(&mut self, request: tonic::Request<super::Item>) -> Self::Future {
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TaxCategoryChange>,
+                        ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).add(request).await };
+                            let fut = async move { (*inner).set_tax_category(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -385,7 +4113,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddSvc(inner);
+                        let method = SetTaxCategorySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -396,18 +4124,54 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/Get" => {
+                "/store.Inventory/WatchPriceChanges" => {
                     #[allow(non_camel_case_types)]
-                    struct GetSvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for GetSvc<T> {
-                        type Response = super::Item;
+                    struct WatchPriceChangesSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory>
+                        tonic::server::ServerStreamingService<super::PriceWatchRequest>
+                        for WatchPriceChangesSvc<T>
+                    {
+                        type Response = super::PriceChange;
+                        type ResponseStream = T::WatchPriceChangesStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PriceWatchRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).watch_price_changes(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchPriceChangesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetAuditLog" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAuditLogSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::AuditLogRequest> for GetAuditLogSvc<T> {
+                        type Response = super::AuditLogPage;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ItemIdentifier>,
+                            request: tonic::Request<super::AuditLogRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get(request).await };
+                            let fut = async move { (*inner).get_audit_log(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -416,7 +4180,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetSvc(inner);
+                        let method = GetAuditLogSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -427,18 +4191,18 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/GetAll" => {
+                "/store.Inventory/ListVariants" => {
                     #[allow(non_camel_case_types)]
-                    struct GetAllSvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::ItemAll> for GetAllSvc<T> {
+                    struct ListVariantsSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for ListVariantsSvc<T> {
                         type Response = super::Items;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ItemAll>,
+                            request: tonic::Request<super::ItemIdentifier>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get_all(request).await };
+                            let fut = async move { (*inner).list_variants(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -447,7 +4211,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetAllSvc(inner);
+                        let method = ListVariantsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -458,18 +4222,18 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/Remove" => {
+                "/store.Inventory/PollChanges" => {
                     #[allow(non_camel_case_types)]
-                    struct RemoveSvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for RemoveSvc<T> {
-                        type Response = super::InventoryChangeResponse;
+                    struct PollChangesSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::PollRequest> for PollChangesSvc<T> {
+                        type Response = super::PollResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ItemIdentifier>,
+                            request: tonic::Request<super::PollRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).remove(request).await };
+                            let fut = async move { (*inner).poll_changes(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -478,7 +4242,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RemoveSvc(inner);
+                        let method = PollChangesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -489,20 +4253,20 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/IncreaseQuantity" => {
+                "/store.Inventory/GetCreatedBetween" => {
                     #[allow(non_camel_case_types)]
-                    struct IncreaseQuantitySvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::QuantityChangeRequest>
-                        for IncreaseQuantitySvc<T>
+                    struct GetCreatedBetweenSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::CreatedRangeRequest>
+                        for GetCreatedBetweenSvc<T>
                     {
-                        type Response = super::InventoryUpdateResponse;
+                        type Response = super::Items;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QuantityChangeRequest>,
+                            request: tonic::Request<super::CreatedRangeRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).increase_quantity(request).await };
+                            let fut = async move { (*inner).get_created_between(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -511,7 +4275,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = IncreaseQuantitySvc(inner);
+                        let method = GetCreatedBetweenSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -522,20 +4286,18 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/DecreaseQuantity" => {
+                "/store.Inventory/Search" => {
                     #[allow(non_camel_case_types)]
-                    struct DecreaseQuantitySvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::QuantityChangeRequest>
-                        for DecreaseQuantitySvc<T>
-                    {
-                        type Response = super::InventoryUpdateResponse;
+                    struct SearchSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::SearchRequest> for SearchSvc<T> {
+                        type Response = super::SearchResults;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QuantityChangeRequest>,
+                            request: tonic::Request<super::SearchRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).decrease_quantity(request).await };
+                            let fut = async move { (*inner).search(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -544,7 +4306,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DecreaseQuantitySvc(inner);
+                        let method = SearchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -555,18 +4317,18 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/UpdatePrice" => {
+                "/store.Inventory/QuoteCart" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdatePriceSvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::UnaryService<super::PriceChangeRequest> for UpdatePriceSvc<T> {
-                        type Response = super::InventoryUpdateResponse;
+                    struct QuoteCartSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::QuoteRequest> for QuoteCartSvc<T> {
+                        type Response = super::Quote;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PriceChangeRequest>,
+                            request: tonic::Request<super::QuoteRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).update_price(request).await };
+                            let fut = async move { (*inner).quote_cart(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -575,7 +4337,7 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdatePriceSvc(inner);
+                        let method = QuoteCartSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -586,20 +4348,49 @@ pub mod inventory_server {
                     };
                     Box::pin(fut)
                 }
-                "/store.Inventory/Watch" => {
+                "/store.Inventory/GetReorderCost" => {
                     #[allow(non_camel_case_types)]
-                    struct WatchSvc<T: Inventory>(pub Arc<T>);
-                    impl<T: Inventory> tonic::server::ServerStreamingService<super::ItemIdentifier> for WatchSvc<T> {
-                        type Response = super::Item;
-                        type ResponseStream = T::WatchStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                    struct GetReorderCostSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ReorderCostRequest> for GetReorderCostSvc<T> {
+                        type Response = super::ReorderCost;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReorderCostRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_reorder_cost(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetReorderCostSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetPriceHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPriceHistorySvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::ItemIdentifier> for GetPriceHistorySvc<T> {
+                        type Response = super::PriceHistory;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::ItemIdentifier>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).watch(request).await };
+                            let fut = async move { (*inner).get_price_history(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -608,13 +4399,108 @@ pub mod inventory_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = WatchSvc(inner);
+                        let method = GetPriceHistorySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
                             send_compression_encodings,
                         );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/TransitionState" => {
+                    #[allow(non_camel_case_types)]
+                    struct TransitionStateSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::StateChange> for TransitionStateSvc<T> {
+                        type Response = super::InventoryChangeResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StateChange>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).transition_state(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TransitionStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/StreamStocktake" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamStocktakeSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::ClientStreamingService<super::StockCount>
+                        for StreamStocktakeSvc<T>
+                    {
+                        type Response = super::StocktakeSummary;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::StockCount>>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).stream_stocktake(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamStocktakeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/store.Inventory/GetStale" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetStaleSvc<T: Inventory>(pub Arc<T>);
+                    impl<T: Inventory> tonic::server::UnaryService<super::StaleRequest> for GetStaleSvc<T> {
+                        type Response = super::Items;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StaleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_stale(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetStaleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use prost::Message;
+
+use crate::store::{Item, Snapshot, WalEntry};
+
+/// Why a snapshot and a WAL can't be combined into one catalog.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Conflict {
+    /// The WAL starts past the entry after the snapshot, so the changes in
+    /// between were lost.
+    Gap { version: u64, first: u64 },
+    /// The WAL ends before the snapshot was taken, so it comes from another
+    /// history than the snapshot.
+    Behind { version: u64, last: u64 },
+    /// A WAL entry doesn't follow the one before it.
+    OutOfOrder { previous: u64, sequence: u64 },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Conflict::Gap { version, first } => write!(
+                f,
+                "the snapshot is at version {} but the WAL starts at {}; entries in between are missing",
+                version, first
+            ),
+            Conflict::Behind { version, last } => write!(
+                f,
+                "the snapshot is at version {} but the WAL ends at {}; they don't belong together",
+                version, last
+            ),
+            Conflict::OutOfOrder { previous, sequence } => write!(
+                f,
+                "WAL entry {} follows entry {}; the WAL is corrupt",
+                sequence, previous
+            ),
+        }
+    }
+}
+
+/// Reads the WAL at `path`, returning its entries and how many bytes hold
+/// whole ones. A missing file is an empty WAL. A torn entry at the end,
+/// left by a crash mid-write, is dropped.
+pub fn read(path: &Path) -> io::Result<(Vec<WalEntry>, u64)> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+    let mut rest = bytes.as_slice();
+    let mut whole = 0;
+    while !rest.is_empty() {
+        match WalEntry::decode_length_delimited(&mut rest) {
+            Ok(entry) => {
+                entries.push(entry);
+                whole = bytes.len() - rest.len();
+            }
+            Err(err) => {
+                tracing::warn!(offset = whole as u64, error = ?err, "dropping torn WAL entry");
+                break;
+            }
+        }
+    }
+
+    Ok((entries, whole as u64))
+}
+
+/// Replays the WAL entries after `snapshot`'s version on top of it. The
+/// result's version is the last entry applied. Fails rather than guess when
+/// the WAL doesn't carry on from the snapshot.
+pub fn reconcile(mut snapshot: Snapshot, entries: Vec<WalEntry>) -> Result<Snapshot, Conflict> {
+    let version = snapshot.version;
+    let (first, last) = match (entries.first(), entries.last()) {
+        (Some(first), Some(last)) => (first.sequence, last.sequence),
+        _ => return Ok(snapshot),
+    };
+    if first > version + 1 {
+        return Err(Conflict::Gap { version, first });
+    }
+    if last < version {
+        return Err(Conflict::Behind { version, last });
+    }
+    if let Some(pair) = entries
+        .windows(2)
+        .find(|pair| pair[1].sequence != pair[0].sequence + 1)
+    {
+        return Err(Conflict::OutOfOrder {
+            previous: pair[0].sequence,
+            sequence: pair[1].sequence,
+        });
+    }
+
+    // Replaced in place so the snapshot's order, and any duplicates loading
+    // reports, survive.
+    let mut items: Vec<Option<Item>> = snapshot.items.drain(..).map(Some).collect();
+    let mut positions = HashMap::new();
+    for (position, item) in items.iter().enumerate() {
+        let sku = item.as_ref().and_then(|item| item.identifier.as_ref());
+        let sku = sku.map(|id| id.sku.clone()).unwrap_or_default();
+        positions.entry(sku).or_insert(position);
+    }
+
+    for entry in entries.into_iter().filter(|entry| entry.sequence > version) {
+        match positions.get(&entry.sku) {
+            Some(&position) => items[position] = entry.item,
+            None => {
+                positions.insert(entry.sku, items.len());
+                items.push(entry.item);
+            }
+        }
+        snapshot.version = entry.sequence;
+    }
+
+    snapshot.items = items.into_iter().flatten().collect();
+    Ok(snapshot)
+}
+
+/// An append-only log of item states, each entry numbered one past the last.
+#[derive(Debug)]
+pub struct Wal {
+    file: File,
+    sequence: u64,
+}
+
+impl Wal {
+    /// Opens the WAL at `path` to append after `sequence`, the last entry
+    /// the catalog already holds, dropping a torn entry at its end.
+    pub fn open(path: &Path, sequence: u64) -> io::Result<Self> {
+        let (entries, whole) = read(path)?;
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.set_len(whole)?;
+
+        let last = entries.last().map_or(0, |entry| entry.sequence);
+        Ok(Wal {
+            file,
+            sequence: sequence.max(last),
+        })
+    }
+
+    /// The sequence of the last entry written.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Appends `sku`'s state, or `None` once it has been removed. Entries
+    /// reach the disk on the next `sync`.
+    pub fn append(&mut self, sku: &str, item: Option<Item>) -> io::Result<()> {
+        let entry = WalEntry {
+            sequence: self.sequence + 1,
+            sku: sku.to_owned(),
+            item,
+        };
+        self.file
+            .write_all(&entry.encode_length_delimited_to_vec())?;
+        self.sequence = entry.sequence;
+        Ok(())
+    }
+
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.file.sync_data()
+    }
+
+    /// Empties the WAL once a snapshot covers all of it. Numbering carries
+    /// on from the last entry.
+    pub fn truncate(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.file.sync_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{ItemIdentifier, ItemStock};
+
+    fn item(sku: &str, quantity: u64) -> Item {
+        Item {
+            identifier: Some(ItemIdentifier { sku: sku.into() }),
+            stock: Some(ItemStock {
+                quantity,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn entry(sequence: u64, sku: &str, quantity: Option<u64>) -> WalEntry {
+        WalEntry {
+            sequence,
+            sku: sku.into(),
+            item: quantity.map(|quantity| item(sku, quantity)),
+        }
+    }
+
+    fn snapshot(version: u64, items: Vec<Item>) -> Snapshot {
+        Snapshot {
+            items,
+            version,
+            taken_at: 0,
+        }
+    }
+
+    #[test]
+    fn overlapping_entries_replay_after_the_snapshot() {
+        let entries = vec![
+            entry(3, "a", Some(30)),
+            entry(4, "b", None),
+            entry(5, "a", Some(50)),
+            entry(6, "c", Some(6)),
+        ];
+        let base = snapshot(4, vec![item("a", 30), item("d", 1)]);
+
+        let replayed = reconcile(base, entries).unwrap();
+        assert_eq!(replayed.version, 6);
+        assert_eq!(replayed.items, [item("a", 50), item("d", 1), item("c", 6)]);
+    }
+
+    #[test]
+    fn removals_replay() {
+        let base = snapshot(1, vec![item("a", 1), item("b", 1)]);
+        let replayed = reconcile(base, vec![entry(2, "a", None)]).unwrap();
+        assert_eq!(replayed.items, [item("b", 1)]);
+    }
+
+    #[test]
+    fn incompatible_pairs_conflict() {
+        let gap = reconcile(snapshot(2, vec![]), vec![entry(4, "a", Some(1))]);
+        assert_eq!(
+            gap,
+            Err(Conflict::Gap {
+                version: 2,
+                first: 4
+            })
+        );
+
+        let behind = reconcile(snapshot(9, vec![]), vec![entry(1, "a", Some(1))]);
+        assert_eq!(
+            behind,
+            Err(Conflict::Behind {
+                version: 9,
+                last: 1
+            })
+        );
+
+        let order = vec![entry(1, "a", Some(1)), entry(3, "a", Some(2))];
+        let out_of_order = reconcile(snapshot(0, vec![]), order);
+        assert_eq!(
+            out_of_order,
+            Err(Conflict::OutOfOrder {
+                previous: 1,
+                sequence: 3
+            })
+        );
+    }
+
+    #[test]
+    fn torn_tail_is_dropped_and_appends_carry_on() {
+        let dir = std::env::temp_dir().join(format!("wal-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("torn.wal");
+        let _ = std::fs::remove_file(&path);
+
+        let mut wal = Wal::open(&path, 0).unwrap();
+        wal.append("a", Some(item("a", 1))).unwrap();
+        wal.append("b", Some(item("b", 2))).unwrap();
+        drop(wal);
+        let whole = std::fs::metadata(&path).unwrap().len();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[0x20, 1, 2]).unwrap();
+
+        let mut wal = Wal::open(&path, 0).unwrap();
+        assert_eq!(wal.sequence(), 2);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), whole);
+        wal.append("a", None).unwrap();
+
+        let (entries, _) = read(&path).unwrap();
+        let sequences: Vec<_> = entries.iter().map(|entry| entry.sequence).collect();
+        assert_eq!(sequences, [1, 2, 3]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::config::AllocationStrategy;
+use crate::store::{ItemStock, WarehouseDraw};
+
+/// Parses a `WAREHOUSE=REGION` pair from the command line.
+pub fn parse_warehouse_region(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((warehouse, region)) if !warehouse.is_empty() && !region.is_empty() => {
+            Ok((warehouse.to_owned(), region.to_owned()))
+        }
+        _ => Err(format!("expected WAREHOUSE=REGION, got `{}`", arg)),
+    }
+}
+
+/// The order a decrease naming no warehouse draws from `stock`'s
+/// warehouses in. `region` is the client's hint for `nearest`, and `turn`
+/// counts the decreases before this one for `round-robin`. Ties go to the
+/// warehouse whose name sorts first.
+pub fn draw_order(
+    stock: &ItemStock,
+    strategy: AllocationStrategy,
+    regions: &[(String, String)],
+    region: Option<&str>,
+    turn: u64,
+) -> Vec<String> {
+    // BTreeMap keys are already in name order.
+    let mut order: Vec<String> = stock.warehouses.keys().cloned().collect();
+    let most_stock = |warehouse: &String| std::cmp::Reverse(stock.warehouses[warehouse]);
+    match strategy {
+        AllocationStrategy::MostStock => order.sort_by_key(most_stock),
+        AllocationStrategy::Nearest => order.sort_by_key(|warehouse| {
+            let home = regions.iter().find(|(name, _)| name == warehouse);
+            let far = region.is_none() || home.map(|(_, home)| home.as_str()) != region;
+            (far, most_stock(warehouse))
+        }),
+        AllocationStrategy::RoundRobin => {
+            if !order.is_empty() {
+                let start = (turn % order.len() as u64) as usize;
+                order.rotate_left(start);
+            }
+        }
+    }
+    order
+}
+
+/// Takes `quantity` off `stock`, emptying its warehouses in `order` one
+/// after another when it has any. The caller has checked there's enough.
+/// Returns what came out of each warehouse drawn from.
+pub fn take(stock: &mut ItemStock, quantity: u64, order: &[String]) -> Vec<WarehouseDraw> {
+    stock.quantity -= quantity;
+
+    let mut drawn = Vec::new();
+    let mut left = quantity;
+    for warehouse in order {
+        if left == 0 {
+            break;
+        }
+        let held = match stock.warehouses.get_mut(warehouse) {
+            Some(held) if *held > 0 => held,
+            _ => continue,
+        };
+        let taken = left.min(*held);
+        *held -= taken;
+        left -= taken;
+        drawn.push(WarehouseDraw {
+            warehouse: warehouse.clone(),
+            quantity: taken,
+        });
+    }
+    drawn
+}
+
+/// Whether `stock` is kept per warehouse, with `quantity` their total.
+pub fn is_split(stock: &ItemStock) -> bool {
+    !stock.warehouses.is_empty()
+}
+
+/// The total of `stock`'s warehouses, or `None` if it overflows.
+pub fn total(stock: &ItemStock) -> Option<u64> {
+    stock
+        .warehouses
+        .values()
+        .try_fold(0u64, |total, &held| total.checked_add(held))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(warehouses: &[(&str, u64)]) -> ItemStock {
+        let warehouses: std::collections::BTreeMap<_, _> = warehouses
+            .iter()
+            .map(|&(warehouse, held)| (warehouse.to_owned(), held))
+            .collect();
+        ItemStock {
+            quantity: warehouses.values().sum(),
+            warehouses,
+            ..Default::default()
+        }
+    }
+
+    fn order(stock: &ItemStock, strategy: AllocationStrategy, region: Option<&str>) -> Vec<String> {
+        let regions = [("north".to_owned(), "eu".to_owned())];
+        draw_order(stock, strategy, &regions, region, 0)
+    }
+
+    #[test]
+    fn nearest_prefers_the_region_then_most_stock() {
+        let stock = split(&[("east", 4), ("north", 1), ("west", 9)]);
+        assert_eq!(
+            order(&stock, AllocationStrategy::Nearest, Some("eu")),
+            ["north", "west", "east"]
+        );
+        assert_eq!(
+            order(&stock, AllocationStrategy::Nearest, None),
+            order(&stock, AllocationStrategy::MostStock, None)
+        );
+    }
+
+    #[test]
+    fn round_robin_rotates_by_turn() {
+        let stock = split(&[("a", 1), ("b", 1), ("c", 1)]);
+        let turn = |turn| draw_order(&stock, AllocationStrategy::RoundRobin, &[], None, turn);
+        assert_eq!(turn(0), ["a", "b", "c"]);
+        assert_eq!(turn(4), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn takes_skip_empty_warehouses() {
+        let mut stock = split(&[("a", 0), ("b", 2), ("c", 5)]);
+        let drawn = take(&mut stock, 4, &["a".into(), "b".into(), "c".into()]);
+        let drawn: Vec<_> = drawn
+            .iter()
+            .map(|draw| (draw.warehouse.as_str(), draw.quantity))
+            .collect();
+        assert_eq!(drawn, [("b", 2), ("c", 2)]);
+        assert_eq!((stock.quantity, total(&stock)), (3, Some(3)));
+    }
+
+    #[test]
+    fn regions_parse() {
+        let parsed = parse_warehouse_region("north=eu").unwrap();
+        assert_eq!(parsed, ("north".to_owned(), "eu".to_owned()));
+        assert!(parse_warehouse_region("north").is_err());
+        assert!(parse_warehouse_region("=eu").is_err());
+    }
+}
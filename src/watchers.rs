@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::Stream;
+
+/// Counts open watch streams across the whole server, optionally capped.
+#[derive(Debug, Clone)]
+pub struct WatchLimit {
+    max: Option<usize>,
+    active: Arc<AtomicUsize>,
+}
+
+impl WatchLimit {
+    pub fn new(max: Option<usize>) -> Self {
+        WatchLimit {
+            max,
+            active: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Takes a slot for a new stream, or `None` when the cap is reached. The
+    /// slot is given back when it's dropped.
+    pub fn acquire(&self) -> Option<WatchSlot> {
+        let max = self.max.unwrap_or(usize::MAX);
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < max).then_some(active + 1)
+            })
+            .ok()?;
+
+        Some(WatchSlot {
+            active: self.active.clone(),
+        })
+    }
+
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Acquire)
+    }
+}
+
+#[derive(Debug)]
+pub struct WatchSlot {
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for WatchSlot {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A response stream holding a watch slot. Tonic drops the stream once it
+/// ends or the client disconnects, which frees the slot.
+pub struct Guarded<S> {
+    inner: S,
+    _slot: WatchSlot,
+}
+
+impl<S> Guarded<S> {
+    pub fn new(inner: S, slot: WatchSlot) -> Self {
+        Guarded { inner, _slot: slot }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for Guarded<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().inner).poll_next(cx)
+    }
+}
+
+/// Clients tracked before idle ones are forgotten.
+const TRACKED_CLIENTS: usize = 1024;
+
+/// Token buckets limiting how fast each client may open streams, so a
+/// reconnect storm can't keep spawning watch tasks.
+#[derive(Debug, Clone)]
+pub struct OpenRateLimit {
+    rate: f64,
+    burst: f64,
+    buckets: Arc<Mutex<HashMap<IpAddr, Bucket>>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl OpenRateLimit {
+    /// Allows `rate` stream opens per second per client, with bursts of up to
+    /// `burst`.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        OpenRateLimit {
+            rate,
+            burst: burst.max(1) as f64,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Spends a token for `client`, or returns how long until one is free.
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= TRACKED_CLIENTS {
+            let (rate, burst) = (self.rate, self.burst);
+            buckets.retain(|_, bucket| bucket.refilled(now, rate) < burst);
+        }
+
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = bucket.refilled(now, self.rate).min(self.burst);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+    }
+}
+
+impl Bucket {
+    fn refilled(&self, now: Instant, rate: f64) -> f64 {
+        self.tokens + now.duration_since(self.updated).as_secs_f64() * rate
+    }
+}
@@ -0,0 +1,161 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use hyper::{Body, Client, Method, Request};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::audit::AuditEntry;
+use crate::feed::quote;
+use crate::signing;
+
+/// Carries `sha256=<hex HMAC of the body>` when a webhook secret is set.
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Delay before the first retry of a failed delivery, doubled for each one
+/// after.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub urls: Vec<String>,
+    pub secret: Option<String>,
+    pub timeout: Duration,
+    pub retries: u32,
+    /// RPCs whose changes are sent; every change is when empty.
+    pub events: Vec<String>,
+}
+
+/// Appends deliveries that ran out of retries to a file as JSON lines, so
+/// they can be replayed by hand.
+#[derive(Debug)]
+pub struct DeadLetters {
+    file: File,
+}
+
+impl DeadLetters {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(DeadLetters { file })
+    }
+
+    fn record(&mut self, url: &str, payload: &str) {
+        let line = format!("{{\"url\":{},\"event\":{}}}", quote(url), payload);
+        if let Err(err) = writeln!(self.file, "{}", line) {
+            tracing::error!(error = ?err, "failed to write webhook dead letter");
+        }
+    }
+}
+
+/// POSTs each change event as JSON to every configured endpoint from a
+/// background task. Events are delivered in order, so a failing endpoint
+/// delays later events while it's retried; when the bounded queue is full,
+/// new events are dropped with a warning.
+#[derive(Debug, Clone, Default)]
+pub struct Webhooks {
+    tx: Option<mpsc::Sender<AuditEntry>>,
+    events: Vec<String>,
+}
+
+impl Webhooks {
+    pub fn spawn(
+        config: WebhookConfig,
+        capacity: usize,
+        mut dead_letters: Option<DeadLetters>,
+    ) -> Self {
+        let events = config.events.clone();
+        let (tx, mut rx) = mpsc::channel::<AuditEntry>(capacity);
+        tokio::spawn(async move {
+            let client = Client::new();
+            while let Some(entry) = rx.recv().await {
+                let payload = entry.to_json();
+                let signature = config.secret.as_ref().map(|secret| {
+                    let mac = signing::hmac_sha256(secret.as_bytes(), payload.as_bytes());
+                    format!("sha256={}", signing::hex(&mac))
+                });
+
+                for url in &config.urls {
+                    let delivered =
+                        deliver(&client, &config, url, &payload, signature.as_deref()).await;
+                    if let (false, Some(dead_letters)) = (delivered, dead_letters.as_mut()) {
+                        dead_letters.record(url, &payload);
+                    }
+                }
+            }
+        });
+
+        Webhooks {
+            tx: Some(tx),
+            events,
+        }
+    }
+
+    pub fn send(&self, entry: &AuditEntry) {
+        let tx = match self.tx.as_ref() {
+            Some(tx) => tx,
+            None => return,
+        };
+
+        if !self.events.is_empty() && !self.events.iter().any(|event| event == entry.rpc) {
+            return;
+        }
+
+        if let Err(TrySendError::Full(entry)) = tx.try_send(entry.clone()) {
+            tracing::warn!(
+                rpc = entry.rpc,
+                sku = entry.sku.as_str(),
+                "webhook queue full, dropping event"
+            );
+        }
+    }
+}
+
+/// Whether the endpoint accepted the payload within the allowed retries.
+async fn deliver(
+    client: &Client<hyper::client::HttpConnector>,
+    config: &WebhookConfig,
+    url: &str,
+    payload: &str,
+    signature: Option<&str>,
+) -> bool {
+    let mut backoff = RETRY_BACKOFF;
+    for attempt in 0..=config.retries {
+        if attempt > 0 {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri(url)
+            .header("content-type", "application/json");
+        if let Some(signature) = signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+        let request = match request.body(Body::from(payload.to_owned())) {
+            Ok(request) => request,
+            Err(err) => {
+                tracing::error!(url, error = ?err, "invalid webhook url");
+                return false;
+            }
+        };
+
+        match tokio::time::timeout(config.timeout, client.request(request)).await {
+            Ok(Ok(response)) if response.status().is_success() => return true,
+            Ok(Ok(response)) => {
+                tracing::warn!(
+                    url,
+                    attempt,
+                    status = response.status().as_u16(),
+                    "webhook rejected"
+                )
+            }
+            Ok(Err(err)) => tracing::warn!(url, attempt, error = %err, "webhook failed"),
+            Err(_) => tracing::warn!(url, attempt, "webhook timed out"),
+        }
+    }
+
+    tracing::error!(url, attempts = config.retries + 1, "giving up on webhook");
+    false
+}